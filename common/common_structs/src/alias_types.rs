@@ -4,6 +4,7 @@ use crate::{LockedAssetTokenAttributes, LockedAssetTokenAttributesEx, UnlockSche
 
 pub type Nonce = u64;
 pub type Epoch = u64;
+pub type Timestamp = u64;
 pub type Week = usize;
 pub type Percent = u64;
 pub type PaymentsVec<M> = ManagedVec<M, EsdtTokenPayment<M>>;
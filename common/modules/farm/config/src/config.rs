@@ -3,7 +3,7 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
-use common_structs::Nonce;
+use common_structs::{Nonce, Timestamp};
 use pausable::State;
 
 pub const DEFAULT_NFT_DEPOSIT_MAX_LEN: usize = 10;
@@ -35,6 +35,18 @@ pub trait ConfigModule: pausable::PausableModule + permissions_module::Permissio
     #[storage_mapper("last_reward_block_nonce")]
     fn last_reward_block_nonce(&self) -> SingleValueMapper<Nonce>;
 
+    #[view(getPerSecondRewardAmount)]
+    #[storage_mapper("per_second_reward_amount")]
+    fn per_second_reward_amount(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getLastRewardTimestamp)]
+    #[storage_mapper("last_reward_timestamp")]
+    fn last_reward_timestamp(&self) -> SingleValueMapper<Timestamp>;
+
+    #[view(isTimestampBasedRewards)]
+    #[storage_mapper("timestamp_based_rewards")]
+    fn timestamp_based_rewards(&self) -> SingleValueMapper<bool>;
+
     #[view(getDivisionSafetyConstant)]
     #[storage_mapper("division_safety_constant")]
     fn division_safety_constant(&self) -> SingleValueMapper<BigUint>;
@@ -34,6 +34,16 @@ pub struct ExitFarmEvent<M: ManagedTypeApi> {
     farm_attributes: ManagedBuffer<M>,
 }
 
+#[derive(TypeAbi, TopEncode)]
+pub struct EmergencyExitEvent<M: ManagedTypeApi> {
+    farming_token_id: TokenIdentifier<M>,
+    farming_token_amount: BigUint<M>,
+    farm_token: EsdtTokenPayment<M>,
+    farm_supply: BigUint<M>,
+    reward_reserve: BigUint<M>,
+    farm_attributes: ManagedBuffer<M>,
+}
+
 #[derive(TypeAbi, TopEncode)]
 pub struct ClaimRewardsEvent<M: ManagedTypeApi> {
     old_farm_token: EsdtTokenPayment<M>,
@@ -239,6 +249,44 @@ pub trait EventsModule {
         )
     }
 
+    fn emit_emergency_exit_event<
+        'a,
+        C: FarmContracTraitBounds<Api = Self::Api>,
+        AttributesType: Clone + TopEncode + TopDecode + NestedEncode + NestedDecode,
+    >(
+        &self,
+        caller: &ManagedAddress,
+        exit_farm_context: ExitFarmContext<Self::Api, AttributesType>,
+        output_farming_tokens: EsdtTokenPayment<Self::Api>,
+        storage_cache: StorageCache<'a, C>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let block = self.blockchain().get_block_nonce();
+        let timestamp = self.blockchain().get_block_timestamp();
+
+        let mut farm_attributes = ManagedBuffer::new();
+        let _ = exit_farm_context
+            .farm_token
+            .attributes
+            .top_encode(&mut farm_attributes);
+
+        self.emergency_exit_event(
+            caller,
+            epoch,
+            block,
+            timestamp,
+            &storage_cache.farm_token_id,
+            &EmergencyExitEvent {
+                farming_token_id: output_farming_tokens.token_identifier,
+                farming_token_amount: output_farming_tokens.amount,
+                farm_token: exit_farm_context.farm_token.payment,
+                farm_supply: storage_cache.farm_token_supply.clone(),
+                reward_reserve: storage_cache.reward_reserve.clone(),
+                farm_attributes,
+            },
+        )
+    }
+
     #[event("enter_farm")]
     fn enter_farm_event(
         &self,
@@ -282,4 +330,15 @@ pub trait EventsModule {
         #[indexed] farm_token: &TokenIdentifier,
         compound_rewards_event: &CompoundRewardsEvent<Self::Api>,
     );
+
+    #[event("emergency_exit")]
+    fn emergency_exit_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        #[indexed] block: u64,
+        #[indexed] timestamp: u64,
+        #[indexed] farm_token: &TokenIdentifier,
+        emergency_exit_event: &EmergencyExitEvent<Self::Api>,
+    );
 }
@@ -1,6 +1,6 @@
 elrond_wasm::imports!();
 
-use common_structs::{FarmToken, FarmTokenAttributes, Nonce};
+use common_structs::{FarmToken, FarmTokenAttributes, Nonce, Timestamp};
 use config::ConfigModule;
 use contexts::storage_cache::StorageCache;
 use core::marker::PhantomData;
@@ -78,11 +78,60 @@ pub trait FarmContract {
         }
     }
 
+    fn calculate_per_second_rewards(
+        sc: &Self::FarmSc,
+        current_timestamp: Timestamp,
+        last_reward_timestamp: Timestamp,
+    ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
+        if current_timestamp <= last_reward_timestamp || !sc.produces_per_block_rewards() {
+            return BigUint::zero();
+        }
+
+        let per_second_reward = sc.per_second_reward_amount().get();
+        let timestamp_diff = current_timestamp - last_reward_timestamp;
+
+        per_second_reward * timestamp_diff
+    }
+
+    fn mint_per_second_rewards(
+        sc: &Self::FarmSc,
+        token_id: &TokenIdentifier<<Self::FarmSc as ContractBase>::Api>,
+    ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
+        let current_timestamp = sc.blockchain().get_block_timestamp();
+        let last_reward_timestamp = sc.last_reward_timestamp().get();
+        if current_timestamp > last_reward_timestamp {
+            let to_mint =
+                Self::calculate_per_second_rewards(sc, current_timestamp, last_reward_timestamp);
+            if to_mint != 0 {
+                Self::mint_rewards(sc, token_id, &to_mint);
+            }
+
+            sc.last_reward_timestamp().set(current_timestamp);
+
+            to_mint
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    /// Mints the rewards accrued since the last update,
+    /// either per block or per second, depending on the farm's configuration
+    fn mint_accrued_rewards(
+        sc: &Self::FarmSc,
+        token_id: &TokenIdentifier<<Self::FarmSc as ContractBase>::Api>,
+    ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
+        if sc.timestamp_based_rewards().get() {
+            Self::mint_per_second_rewards(sc, token_id)
+        } else {
+            Self::mint_per_block_rewards(sc, token_id)
+        }
+    }
+
     fn generate_aggregated_rewards(
         sc: &Self::FarmSc,
         storage_cache: &mut StorageCache<Self::FarmSc>,
     ) {
-        let total_reward = Self::mint_per_block_rewards(sc, &storage_cache.reward_token_id);
+        let total_reward = Self::mint_accrued_rewards(sc, &storage_cache.reward_token_id);
         if total_reward > 0u64 {
             storage_cache.reward_reserve += &total_reward;
 
@@ -99,7 +148,7 @@ pub trait FarmContract {
         _caller: &ManagedAddress<<Self::FarmSc as ContractBase>::Api>,
         farm_token_amount: &BigUint<<Self::FarmSc as ContractBase>::Api>,
         token_attributes: &Self::AttributesType,
-        storage_cache: &StorageCache<Self::FarmSc>,
+        storage_cache: &mut StorageCache<Self::FarmSc>,
     ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
         let token_rps = token_attributes.get_reward_per_share();
         if storage_cache.reward_per_share > token_rps {
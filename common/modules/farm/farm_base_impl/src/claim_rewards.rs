@@ -78,7 +78,7 @@ pub trait BaseClaimRewardsModule:
             &caller,
             farm_token_amount,
             &token_attributes,
-            &storage_cache,
+            &mut storage_cache,
         );
         storage_cache.reward_reserve -= &reward;
 
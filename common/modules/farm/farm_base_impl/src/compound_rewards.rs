@@ -66,7 +66,7 @@ pub trait BaseCompoundRewardsModule:
             &caller,
             farm_token_amount,
             &token_attributes,
-            &storage_cache,
+            &mut storage_cache,
         );
         storage_cache.reward_reserve -= &reward;
         storage_cache.farm_token_supply += &reward;
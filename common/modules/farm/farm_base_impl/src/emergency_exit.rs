@@ -0,0 +1,85 @@
+elrond_wasm::imports!();
+
+use crate::{base_traits_impl::FarmContract, elrond_codec::TopEncode};
+use common_errors::ERROR_NO_FARM_TOKEN;
+use contexts::{
+    exit_farm_context::ExitFarmContext,
+    storage_cache::{FarmContracTraitBounds, StorageCache},
+};
+use fixed_supply_token::FixedSupplyToken;
+use pausable::State;
+
+pub struct InternalEmergencyExitResult<'a, C, T>
+where
+    C: FarmContracTraitBounds,
+    T: Clone + TopEncode + TopDecode + NestedEncode + NestedDecode,
+{
+    pub context: ExitFarmContext<C::Api, T>,
+    pub storage_cache: StorageCache<'a, C>,
+    pub farming_token_payment: EsdtTokenPayment<C::Api>,
+}
+
+#[elrond_wasm::module]
+pub trait BaseEmergencyExitModule:
+    rewards::RewardsModule
+    + config::ConfigModule
+    + token_send::TokenSendModule
+    + farm_token::FarmTokenModule
+    + pausable::PausableModule
+    + permissions_module::PermissionsModule
+    + events::EventsModule
+    + elrond_wasm_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + utils::UtilsModule
+{
+    /// Returns the farming tokens and burns the farm token, without generating or paying any rewards.
+    /// Any rewards accumulated by the position are forfeited.
+    fn emergency_exit_base<FC: FarmContract<FarmSc = Self>>(
+        &self,
+        payment: EsdtTokenPayment<Self::Api>,
+    ) -> InternalEmergencyExitResult<Self, FC::AttributesType> {
+        let mut storage_cache = StorageCache::new(self);
+        require!(
+            storage_cache.contract_state == State::Inactive,
+            "Emergency exit is only available while the farm is paused"
+        );
+        require!(
+            storage_cache.farm_token_id.is_valid_esdt_identifier(),
+            ERROR_NO_FARM_TOKEN
+        );
+
+        let exit_farm_context = ExitFarmContext::<Self::Api, FC::AttributesType>::new(
+            payment,
+            &storage_cache.farm_token_id,
+            self.blockchain(),
+        );
+
+        let farm_token_amount = &exit_farm_context.farm_token.payment.amount;
+        let token_attributes = exit_farm_context
+            .farm_token
+            .attributes
+            .clone()
+            .into_part(farm_token_amount);
+
+        let farming_token_amount = token_attributes.get_total_supply();
+        let farming_token_payment = EsdtTokenPayment::new(
+            storage_cache.farming_token_id.clone(),
+            0,
+            farming_token_amount,
+        );
+
+        let farm_token_payment = &exit_farm_context.farm_token.payment;
+        self.send().esdt_local_burn(
+            &farm_token_payment.token_identifier,
+            farm_token_payment.token_nonce,
+            &farm_token_payment.amount,
+        );
+
+        storage_cache.farm_token_supply -= &farming_token_payment.amount;
+
+        InternalEmergencyExitResult {
+            context: exit_farm_context,
+            farming_token_payment,
+            storage_cache,
+        }
+    }
+}
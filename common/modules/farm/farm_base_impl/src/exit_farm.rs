@@ -59,7 +59,7 @@ pub trait BaseExitFarmModule:
             &caller,
             farm_token_amount,
             &token_attributes,
-            &storage_cache,
+            &mut storage_cache,
         );
         storage_cache.reward_reserve -= &reward;
 
@@ -12,6 +12,7 @@ pub mod base_farm_validation;
 pub mod base_traits_impl;
 pub mod claim_rewards;
 pub mod compound_rewards;
+pub mod emergency_exit;
 pub mod enter_farm;
 pub mod exit_farm;
 
@@ -31,6 +32,7 @@ pub trait FarmBaseImpl:
     + claim_rewards::BaseClaimRewardsModule
     + compound_rewards::BaseCompoundRewardsModule
     + exit_farm::BaseExitFarmModule
+    + emergency_exit::BaseEmergencyExitModule
     + utils::UtilsModule
 {
 }
@@ -7,17 +7,50 @@ pub trait RewardsModule:
     config::ConfigModule + pausable::PausableModule + permissions_module::PermissionsModule
 {
     fn start_produce_rewards(&self) {
-        require!(
-            self.per_block_reward_amount().get() != 0u64,
-            "Cannot produce zero reward amount"
-        );
+        let reward_amount = if self.timestamp_based_rewards().get() {
+            self.per_second_reward_amount().get()
+        } else {
+            self.per_block_reward_amount().get()
+        };
+        require!(reward_amount != 0u64, "Cannot produce zero reward amount");
         require!(
             !self.produce_rewards_enabled().get(),
             "Producing rewards is already enabled"
         );
         let current_nonce = self.blockchain().get_block_nonce();
+        let current_timestamp = self.blockchain().get_block_timestamp();
         self.produce_rewards_enabled().set(true);
         self.last_reward_block_nonce().set(current_nonce);
+        self.last_reward_timestamp().set(current_timestamp);
+    }
+
+    /// Must only be called after the block-based rewards were aggregated up to the current block
+    fn enable_timestamp_based_rewards(&self, per_second_amount: &BigUint) {
+        require!(
+            per_second_amount != &0u64,
+            "Cannot produce zero reward amount"
+        );
+        require!(
+            !self.timestamp_based_rewards().get(),
+            "Timestamp based rewards already enabled"
+        );
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        self.per_second_reward_amount().set(per_second_amount);
+        self.last_reward_timestamp().set(current_timestamp);
+        self.timestamp_based_rewards().set(true);
+    }
+
+    /// Must only be called after the timestamp-based rewards were aggregated up to the current block
+    fn disable_timestamp_based_rewards(&self) {
+        require!(
+            self.timestamp_based_rewards().get(),
+            "Timestamp based rewards not enabled"
+        );
+
+        let current_nonce = self.blockchain().get_block_nonce();
+        self.last_reward_block_nonce().set(current_nonce);
+        self.timestamp_based_rewards().set(false);
     }
 
     #[inline]
@@ -36,14 +36,17 @@ pub trait Farm:
     + farm::exit_penalty::ExitPenaltyModule
     + farm::progress_update::ProgressUpdateModule
     + farm::claim_boost_only::ClaimBoostOnlyModule
+    + farm::delegated_harvest::DelegatedHarvestModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
     + farm_base_impl::claim_rewards::BaseClaimRewardsModule
     + farm_base_impl::compound_rewards::BaseCompoundRewardsModule
     + farm_base_impl::exit_farm::BaseExitFarmModule
+    + farm_base_impl::emergency_exit::BaseEmergencyExitModule
     + farm_boosted_yields::FarmBoostedYieldsModule
     + farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule
+    + farm_boosted_yields::boosted_yields_formulas::BoostedYieldsFormulasModule
     + week_timekeeping::WeekTimekeepingModule
     + weekly_rewards_splitting::WeeklyRewardsSplittingModule
     + weekly_rewards_splitting::events::WeeklyRewardsSplittingEventsModule
@@ -147,6 +150,44 @@ pub trait Farm:
         (output_farm_token_payment, locked_rewards_payment).into()
     }
 
+    /// Claims the rewards for the user's escrowed farm tokens.
+    /// May be called by the user or by one of their harvest operators.
+    /// The new farm token stays escrowed, and the locked rewards are sent to the user.
+    #[endpoint(claimRewardsOnBehalf)]
+    fn claim_rewards_on_behalf(&self, user: ManagedAddress) -> ClaimRewardsResultType<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        self.require_harvest_permission(&caller, &user);
+
+        let payments = self.take_escrowed_farm_tokens(&user);
+        let base_claim_rewards_result =
+            self.claim_rewards_base::<NoMintWrapper<Self>>(user.clone(), payments);
+        let output_farm_token_payment = base_claim_rewards_result.new_farm_token.payment.clone();
+        self.escrow_farm_token(&user, output_farm_token_payment.clone());
+
+        let rewards_payment = base_claim_rewards_result.rewards;
+        let locked_rewards_payment = self.send_to_lock_contract_non_zero(
+            rewards_payment.token_identifier,
+            rewards_payment.amount,
+            user.clone(),
+            user.clone(),
+        );
+
+        self.set_farm_supply_for_current_week(
+            &base_claim_rewards_result.storage_cache.farm_token_supply,
+        );
+
+        self.emit_claim_rewards_event::<_, FarmTokenAttributes<Self::Api>>(
+            &user,
+            base_claim_rewards_result.context,
+            base_claim_rewards_result.new_farm_token,
+            locked_rewards_payment.clone(),
+            base_claim_rewards_result.created_with_merge,
+            base_claim_rewards_result.storage_cache,
+        );
+
+        (output_farm_token_payment, locked_rewards_payment).into()
+    }
+
     #[payable("*")]
     #[endpoint(exitFarm)]
     fn exit_farm_endpoint(
@@ -204,6 +245,24 @@ pub trait Farm:
             .into()
     }
 
+    /// Only available while the farm is paused.
+    /// Returns the farming tokens and forfeits all the position's rewards.
+    #[payable("*")]
+    #[endpoint(emergencyExit)]
+    fn emergency_exit_endpoint(
+        &self,
+        opt_orig_caller: OptionalValue<ManagedAddress>,
+    ) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
+
+        let payment = self.call_value().single_esdt();
+        let farming_tokens = self.emergency_exit::<NoMintWrapper<Self>>(orig_caller, payment);
+        self.send_payment_non_zero(&caller, &farming_tokens);
+
+        farming_tokens
+    }
+
     #[view(calculateRewardsForGivenPosition)]
     fn calculate_rewards_for_given_position(
         &self,
@@ -221,7 +280,7 @@ pub trait Farm:
             &user,
             &farm_token_amount,
             &attributes,
-            &storage_cache,
+            &mut storage_cache,
         )
     }
 
@@ -259,6 +318,28 @@ pub trait Farm:
         self.set_per_block_rewards::<NoMintWrapper<Self>>(per_block_amount);
     }
 
+    #[endpoint(setPerSecondRewardAmount)]
+    fn set_per_second_rewards_endpoint(&self, per_second_amount: BigUint) {
+        self.require_caller_has_admin_permissions();
+        self.set_per_second_rewards::<NoMintWrapper<Self>>(per_second_amount);
+    }
+
+    /// Settles the rewards accrued per block so far,
+    /// after which rewards are accrued per second, based on the block timestamp
+    #[endpoint(switchToTimestampBasedRewards)]
+    fn switch_to_timestamp_based_rewards_endpoint(&self, per_second_amount: BigUint) {
+        self.require_caller_has_admin_permissions();
+        self.switch_to_timestamp_based_rewards::<NoMintWrapper<Self>>(per_second_amount);
+    }
+
+    /// Settles the rewards accrued per second so far,
+    /// after which rewards are accrued per block again, using the per block reward amount
+    #[endpoint(switchToBlockBasedRewards)]
+    fn switch_to_block_based_rewards_endpoint(&self) {
+        self.require_caller_has_admin_permissions();
+        self.switch_to_block_based_rewards::<NoMintWrapper<Self>>();
+    }
+
     fn send_to_lock_contract_non_zero(
         &self,
         token_id: TokenIdentifier,
@@ -296,7 +377,7 @@ where
         sc: &Self::FarmSc,
         storage_cache: &mut StorageCache<Self::FarmSc>,
     ) {
-        let total_reward = Self::mint_per_block_rewards(sc, &storage_cache.reward_token_id);
+        let total_reward = Self::mint_accrued_rewards(sc, &storage_cache.reward_token_id);
         if total_reward > 0u64 {
             storage_cache.reward_reserve += &total_reward;
             let split_rewards = sc.take_reward_slice(total_reward);
@@ -314,7 +395,7 @@ where
         caller: &ManagedAddress<<Self::FarmSc as ContractBase>::Api>,
         farm_token_amount: &BigUint<<Self::FarmSc as ContractBase>::Api>,
         token_attributes: &Self::AttributesType,
-        storage_cache: &StorageCache<Self::FarmSc>,
+        storage_cache: &mut StorageCache<Self::FarmSc>,
     ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
         Wrapper::<T>::calculate_rewards(
             sc,
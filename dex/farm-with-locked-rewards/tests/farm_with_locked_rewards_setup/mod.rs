@@ -15,7 +15,7 @@ mod fees_collector_mock;
 use fees_collector_mock::*;
 
 use elrond_wasm_modules::pause::PauseModule;
-use energy_factory::{energy::EnergyModule, SimpleLockEnergy};
+use energy_factory::{energy::EnergyModule, virtual_lock::VirtualLockModule, SimpleLockEnergy};
 use energy_query::{Energy, EnergyQueryModule};
 use farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule;
 use farm_boosted_yields::FarmBoostedYieldsModule;
@@ -204,6 +204,10 @@ where
             .execute_tx(&owner, &energy_factory_wrapper, &rust_zero, |sc| {
                 sc.sc_whitelist_addresses()
                     .add(&managed_address!(farm_wrapper.address_ref()));
+                sc.set_virtual_lock_quota(
+                    managed_address!(farm_wrapper.address_ref()),
+                    managed_biguint!(u64::MAX),
+                );
             })
             .assert_ok();
 
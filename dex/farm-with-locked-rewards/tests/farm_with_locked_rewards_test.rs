@@ -1,5 +1,7 @@
 use common_structs::FarmTokenAttributes;
+use config::ConfigModule;
 use elrond_wasm_debug::{managed_address, managed_biguint, rust_biguint, DebugApi};
+use farm_with_locked_rewards::Farm;
 use simple_lock::locked_token::LockedTokenAttributes;
 
 use crate::farm_with_locked_rewards_setup::{
@@ -226,3 +228,61 @@ fn farm_with_boosted_yields_no_proxy_test() {
             None,
         );
 }
+
+#[test]
+fn farm_switch_between_block_and_timestamp_based_rewards_test() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup = FarmSetup::new(
+        farm_with_locked_rewards::contract_obj,
+        energy_factory::contract_obj,
+    );
+    let per_second_reward_amount = 100;
+
+    let farm_token_amount = 100_000_000;
+    let first_user = farm_setup.first_user.clone();
+    farm_setup.enter_farm(&first_user, farm_token_amount);
+
+    // 10 blocks of per block rewards - 10 * 1_000
+    farm_setup.b_mock.set_block_nonce(10);
+    farm_setup.b_mock.set_block_timestamp(100);
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &farm_setup.owner,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.switch_to_timestamp_based_rewards_endpoint(managed_biguint!(
+                    per_second_reward_amount
+                ));
+                assert!(sc.timestamp_based_rewards().get());
+            },
+        )
+        .assert_ok();
+
+    // 60 seconds of per second rewards - 60 * 100, block nonce is ignored
+    farm_setup.b_mock.set_block_nonce(500);
+    farm_setup.b_mock.set_block_timestamp(160);
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &farm_setup.owner,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.switch_to_block_based_rewards_endpoint();
+                assert!(!sc.timestamp_based_rewards().get());
+            },
+        )
+        .assert_ok();
+
+    // 10 more blocks of per block rewards - 10 * 1_000, timestamp is ignored
+    farm_setup.b_mock.set_block_nonce(510);
+    farm_setup.b_mock.set_block_timestamp(10_000);
+
+    let received_reward_amt = farm_setup.claim_rewards(&first_user, 1, farm_token_amount);
+    assert_eq!(
+        received_reward_amt,
+        10 * 1_000 + 60 * per_second_reward_amount + 10 * 1_000
+    );
+}
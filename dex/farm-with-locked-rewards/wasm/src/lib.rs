@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           62
+// Endpoints:                           80
 // Async Callback:                       1
-// Total number of exported functions:  64
+// Total number of exported functions:  82
 
 #![no_std]
 
@@ -17,17 +17,25 @@ elrond_wasm_node::wasm_endpoints! {
         enterFarm
         claimRewards
         exitFarm
+        claimRewardsOnBehalf
+        emergencyExit
         calculateRewardsForGivenPosition
         mergeFarmTokens
         startProduceRewards
         endProduceRewards
         setPerBlockRewardAmount
+        setPerSecondRewardAmount
+        switchToTimestampBasedRewards
+        switchToBlockBasedRewards
         getRewardPerShare
         getRewardReserve
         getFarmingTokenId
         getRewardTokenId
         getPerBlockRewardAmount
         getLastRewardBlockNonce
+        getPerSecondRewardAmount
+        getLastRewardTimestamp
+        isTimestampBasedRewards
         getDivisionSafetyConstant
         setLockingScAddress
         setLockEpochs
@@ -55,6 +63,12 @@ elrond_wasm_node::wasm_endpoints! {
         getMinimumFarmingEpoch
         getBurnGasLimit
         getPairContractManagedAddress
+        addHarvestOperator
+        removeHarvestOperator
+        depositFarmTokensForHarvest
+        withdrawFarmTokensFromHarvest
+        getHarvestOperators
+        getEscrowedFarmTokens
         setBoostedYieldsRewardsPercentage
         collectUndistributedBoostedRewards
         getBoostedYieldsRewardsPercentage
@@ -64,6 +78,10 @@ elrond_wasm_node::wasm_endpoints! {
         getUndistributedBoostedRewards
         setBoostedYieldsFactors
         getBoostedYieldsFactors
+        setBoostedYieldsFormula
+        getBoostedYieldsFormula
+        getBoostedRewardsSimulation
+        getUserBoostedRewardsEstimate
         getCurrentWeek
         getFirstWeekStartEpoch
         getLastActiveWeekForUser
@@ -7,7 +7,7 @@ elrond_wasm::derive_imports!();
 use core::marker::PhantomData;
 
 use common_errors::ERROR_ZERO_AMOUNT;
-use common_structs::FarmTokenAttributes;
+use common_structs::{FarmTokenAttributes, PaymentsVec};
 use contexts::storage_cache::StorageCache;
 
 use farm_base_impl::base_traits_impl::{DefaultFarmWrapper, FarmContract};
@@ -58,9 +58,11 @@ pub trait BaseFunctionsModule:
     + farm_base_impl::claim_rewards::BaseClaimRewardsModule
     + farm_base_impl::compound_rewards::BaseCompoundRewardsModule
     + farm_base_impl::exit_farm::BaseExitFarmModule
+    + farm_base_impl::emergency_exit::BaseEmergencyExitModule
     + utils::UtilsModule
     + farm_boosted_yields::FarmBoostedYieldsModule
     + farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule
+    + farm_boosted_yields::boosted_yields_formulas::BoostedYieldsFormulasModule
     + week_timekeeping::WeekTimekeepingModule
     + weekly_rewards_splitting::WeeklyRewardsSplittingModule
     + weekly_rewards_splitting::events::WeeklyRewardsSplittingEventsModule
@@ -94,8 +96,8 @@ pub trait BaseFunctionsModule:
     fn claim_rewards<FC: FarmContract<FarmSc = Self>>(
         &self,
         caller: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
     ) -> ClaimRewardsResultWrapper<Self::Api> {
-        let payments = self.call_value().all_esdt_transfers();
         let base_claim_rewards_result = self.claim_rewards_base::<FC>(caller.clone(), payments);
 
         let output_farm_token_payment = base_claim_rewards_result.new_farm_token.payment.clone();
@@ -123,8 +125,8 @@ pub trait BaseFunctionsModule:
     fn compound_rewards<FC: FarmContract<FarmSc = Self>>(
         &self,
         caller: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
     ) -> EsdtTokenPayment<Self::Api> {
-        let payments = self.call_value().all_esdt_transfers();
         let base_compound_rewards_result =
             self.compound_rewards_base::<FC>(caller.clone(), payments);
 
@@ -181,6 +183,28 @@ pub trait BaseFunctionsModule:
         }
     }
 
+    fn emergency_exit<FC: FarmContract<FarmSc = Self>>(
+        &self,
+        caller: ManagedAddress,
+        payment: EsdtTokenPayment,
+    ) -> EsdtTokenPayment<Self::Api> {
+        let emergency_exit_result = self.emergency_exit_base::<FC>(payment);
+        let farming_token_payment = emergency_exit_result.farming_token_payment;
+
+        self.set_farm_supply_for_current_week(
+            &emergency_exit_result.storage_cache.farm_token_supply,
+        );
+
+        self.emit_emergency_exit_event(
+            &caller,
+            emergency_exit_result.context,
+            farming_token_payment.clone(),
+            emergency_exit_result.storage_cache,
+        );
+
+        farming_token_payment
+    }
+
     fn merge_farm_tokens<FC: FarmContract<FarmSc = Self>>(&self) -> EsdtTokenPayment<Self::Api> {
         let payments = self.get_non_empty_payments();
         let token_mapper = self.farm_token();
@@ -208,6 +232,32 @@ pub trait BaseFunctionsModule:
         self.per_block_reward_amount().set(&per_block_amount);
     }
 
+    fn set_per_second_rewards<FC: FarmContract<FarmSc = Self>>(&self, per_second_amount: BigUint) {
+        require!(per_second_amount != 0u64, ERROR_ZERO_AMOUNT);
+
+        let mut storage = StorageCache::new(self);
+        FC::generate_aggregated_rewards(self, &mut storage);
+
+        self.per_second_reward_amount().set(&per_second_amount);
+    }
+
+    fn switch_to_timestamp_based_rewards<FC: FarmContract<FarmSc = Self>>(
+        &self,
+        per_second_amount: BigUint,
+    ) {
+        let mut storage = StorageCache::new(self);
+        FC::generate_aggregated_rewards(self, &mut storage);
+
+        self.enable_timestamp_based_rewards(&per_second_amount);
+    }
+
+    fn switch_to_block_based_rewards<FC: FarmContract<FarmSc = Self>>(&self) {
+        let mut storage = StorageCache::new(self);
+        FC::generate_aggregated_rewards(self, &mut storage);
+
+        self.disable_timestamp_based_rewards();
+    }
+
     fn set_farm_supply_for_current_week(&self, farm_supply: &BigUint) {
         let current_week = self.get_current_week();
         self.farm_supply_for_week(current_week).set(farm_supply);
@@ -266,7 +316,7 @@ where
         sc: &Self::FarmSc,
         storage_cache: &mut StorageCache<Self::FarmSc>,
     ) {
-        let total_reward = Self::mint_per_block_rewards(sc, &storage_cache.reward_token_id);
+        let total_reward = Self::mint_accrued_rewards(sc, &storage_cache.reward_token_id);
         if total_reward > 0u64 {
             storage_cache.reward_reserve += &total_reward;
             let split_rewards = sc.take_reward_slice(total_reward);
@@ -284,7 +334,7 @@ where
         caller: &ManagedAddress<<Self::FarmSc as ContractBase>::Api>,
         farm_token_amount: &BigUint<<Self::FarmSc as ContractBase>::Api>,
         token_attributes: &Self::AttributesType,
-        storage_cache: &StorageCache<Self::FarmSc>,
+        storage_cache: &mut StorageCache<Self::FarmSc>,
     ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
         let base_farm_reward = DefaultFarmWrapper::<T>::calculate_rewards(
             sc,
@@ -26,9 +26,11 @@ pub trait ClaimBoostOnlyModule:
     + farm_base_impl::claim_rewards::BaseClaimRewardsModule
     + farm_base_impl::compound_rewards::BaseCompoundRewardsModule
     + farm_base_impl::exit_farm::BaseExitFarmModule
+    + farm_base_impl::emergency_exit::BaseEmergencyExitModule
     + utils::UtilsModule
     + farm_boosted_yields::FarmBoostedYieldsModule
     + farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule
+    + farm_boosted_yields::boosted_yields_formulas::BoostedYieldsFormulasModule
     + crate::base_functions::BaseFunctionsModule
 {
     fn claim_only_boosted_payment(
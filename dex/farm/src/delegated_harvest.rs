@@ -0,0 +1,103 @@
+elrond_wasm::imports!();
+
+use common_structs::PaymentsVec;
+use config::DEFAULT_NFT_DEPOSIT_MAX_LEN;
+
+#[elrond_wasm::module]
+pub trait DelegatedHarvestModule:
+    farm_token::FarmTokenModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + elrond_wasm_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + utils::UtilsModule
+{
+    /// Allows the given address to claim or compound rewards for the caller's escrowed positions.
+    /// Rewards are always sent to the owner of the positions.
+    #[endpoint(addHarvestOperator)]
+    fn add_harvest_operator(&self, operator: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        require!(caller != operator, "Cannot add self as operator");
+
+        let is_new = self.harvest_operators(&caller).insert(operator);
+        require!(is_new, "Address is already an operator");
+    }
+
+    #[endpoint(removeHarvestOperator)]
+    fn remove_harvest_operator(&self, operator: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        let was_removed = self.harvest_operators(&caller).swap_remove(&operator);
+        require!(was_removed, "Address is not an operator");
+    }
+
+    #[payable("*")]
+    #[endpoint(depositFarmTokensForHarvest)]
+    fn deposit_farm_tokens_for_harvest(&self) {
+        let payments = self.get_non_empty_payments();
+        let farm_token_mapper = self.farm_token();
+        farm_token_mapper.require_all_same_token(&payments);
+
+        let caller = self.blockchain().get_caller();
+        self.escrowed_farm_tokens(&caller)
+            .update(|escrowed_tokens| {
+                escrowed_tokens.append_vec(payments);
+                require!(
+                    escrowed_tokens.len() <= DEFAULT_NFT_DEPOSIT_MAX_LEN,
+                    "Too many escrowed farm tokens"
+                );
+            });
+    }
+
+    #[endpoint(withdrawFarmTokensFromHarvest)]
+    fn withdraw_farm_tokens_from_harvest(&self) -> PaymentsVec<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let escrowed_tokens = self.take_escrowed_farm_tokens(&caller);
+        self.send_multiple_tokens_if_not_zero(&caller, &escrowed_tokens);
+
+        escrowed_tokens
+    }
+
+    fn require_harvest_permission(&self, caller: &ManagedAddress, user: &ManagedAddress) {
+        require!(
+            caller == user || self.harvest_operators(user).contains(caller),
+            "Caller may not harvest for this user"
+        );
+    }
+
+    fn take_escrowed_farm_tokens(&self, user: &ManagedAddress) -> PaymentsVec<Self::Api> {
+        let escrowed_tokens_mapper = self.escrowed_farm_tokens(user);
+        let escrowed_tokens = escrowed_tokens_mapper.get();
+        escrowed_tokens_mapper.clear();
+        require!(!escrowed_tokens.is_empty(), "No escrowed farm tokens");
+
+        escrowed_tokens
+    }
+
+    fn escrow_farm_token(&self, user: &ManagedAddress, farm_token: EsdtTokenPayment) {
+        if farm_token.amount == 0 {
+            return;
+        }
+
+        self.escrowed_farm_tokens(user)
+            .update(|escrowed_tokens| escrowed_tokens.push(farm_token));
+    }
+
+    #[view(getHarvestOperators)]
+    fn get_harvest_operators(&self, user: ManagedAddress) -> MultiValueEncoded<ManagedAddress> {
+        let mut result = MultiValueEncoded::new();
+        for operator in self.harvest_operators(&user).iter() {
+            result.push(operator);
+        }
+
+        result
+    }
+
+    #[storage_mapper("harvestOperators")]
+    fn harvest_operators(&self, user: &ManagedAddress) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getEscrowedFarmTokens)]
+    #[storage_mapper("escrowedFarmTokens")]
+    fn escrowed_farm_tokens(
+        &self,
+        user: &ManagedAddress,
+    ) -> SingleValueMapper<PaymentsVec<Self::Api>>;
+}
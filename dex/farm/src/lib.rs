@@ -7,6 +7,7 @@ elrond_wasm::derive_imports!();
 
 pub mod base_functions;
 pub mod claim_boost_only;
+pub mod delegated_harvest;
 pub mod exit_penalty;
 pub mod progress_update;
 
@@ -39,14 +40,17 @@ pub trait Farm:
     + exit_penalty::ExitPenaltyModule
     + progress_update::ProgressUpdateModule
     + claim_boost_only::ClaimBoostOnlyModule
+    + delegated_harvest::DelegatedHarvestModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
     + farm_base_impl::claim_rewards::BaseClaimRewardsModule
     + farm_base_impl::compound_rewards::BaseCompoundRewardsModule
     + farm_base_impl::exit_farm::BaseExitFarmModule
+    + farm_base_impl::emergency_exit::BaseEmergencyExitModule
     + farm_boosted_yields::FarmBoostedYieldsModule
     + farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule
+    + farm_boosted_yields::boosted_yields_formulas::BoostedYieldsFormulasModule
     + week_timekeeping::WeekTimekeepingModule
     + weekly_rewards_splitting::WeeklyRewardsSplittingModule
     + weekly_rewards_splitting::events::WeeklyRewardsSplittingEventsModule
@@ -118,7 +122,8 @@ pub trait Farm:
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
-        let claim_rewards_result = self.claim_rewards::<Wrapper<Self>>(orig_caller);
+        let payments = self.call_value().all_esdt_transfers();
+        let claim_rewards_result = self.claim_rewards::<Wrapper<Self>>(orig_caller, payments);
         self.send_payment_non_zero(&caller, &claim_rewards_result.new_farm_token);
         self.send_payment_non_zero(&caller, &claim_rewards_result.rewards);
 
@@ -134,12 +139,45 @@ pub trait Farm:
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
-        let output_farm_token_payment = self.compound_rewards::<Wrapper<Self>>(orig_caller);
+        let payments = self.call_value().all_esdt_transfers();
+        let output_farm_token_payment =
+            self.compound_rewards::<Wrapper<Self>>(orig_caller, payments);
         self.send_payment_non_zero(&caller, &output_farm_token_payment);
 
         output_farm_token_payment
     }
 
+    /// Claims the rewards for the user's escrowed farm tokens.
+    /// May be called by the user or by one of their harvest operators.
+    /// The new farm token stays escrowed, and the rewards are sent to the user.
+    #[endpoint(claimRewardsOnBehalf)]
+    fn claim_rewards_on_behalf(&self, user: ManagedAddress) -> ClaimRewardsResultType<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        self.require_harvest_permission(&caller, &user);
+
+        let payments = self.take_escrowed_farm_tokens(&user);
+        let claim_rewards_result = self.claim_rewards::<Wrapper<Self>>(user.clone(), payments);
+        self.escrow_farm_token(&user, claim_rewards_result.new_farm_token.clone());
+        self.send_payment_non_zero(&user, &claim_rewards_result.rewards);
+
+        claim_rewards_result.into()
+    }
+
+    /// Compounds the rewards for the user's escrowed farm tokens.
+    /// May be called by the user or by one of their harvest operators.
+    #[endpoint(compoundRewardsOnBehalf)]
+    fn compound_rewards_on_behalf(&self, user: ManagedAddress) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        self.require_harvest_permission(&caller, &user);
+
+        let payments = self.take_escrowed_farm_tokens(&user);
+        let output_farm_token_payment =
+            self.compound_rewards::<Wrapper<Self>>(user.clone(), payments);
+        self.escrow_farm_token(&user, output_farm_token_payment.clone());
+
+        output_farm_token_payment
+    }
+
     #[payable("*")]
     #[endpoint(exitFarm)]
     fn exit_farm_endpoint(
@@ -192,6 +230,24 @@ pub trait Farm:
             .into()
     }
 
+    /// Only available while the farm is paused.
+    /// Returns the farming tokens and forfeits all the position's rewards.
+    #[payable("*")]
+    #[endpoint(emergencyExit)]
+    fn emergency_exit_endpoint(
+        &self,
+        opt_orig_caller: OptionalValue<ManagedAddress>,
+    ) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
+
+        let payment = self.call_value().single_esdt();
+        let farming_tokens = self.emergency_exit::<Wrapper<Self>>(orig_caller, payment);
+        self.send_payment_non_zero(&caller, &farming_tokens);
+
+        farming_tokens
+    }
+
     #[view(calculateRewardsForGivenPosition)]
     fn calculate_rewards_for_given_position(
         &self,
@@ -209,7 +265,7 @@ pub trait Farm:
             &user,
             &farm_token_amount,
             &attributes,
-            &storage_cache,
+            &mut storage_cache,
         )
     }
 
@@ -246,4 +302,26 @@ pub trait Farm:
         self.require_caller_has_admin_permissions();
         self.set_per_block_rewards::<Wrapper<Self>>(per_block_amount);
     }
+
+    #[endpoint(setPerSecondRewardAmount)]
+    fn set_per_second_rewards_endpoint(&self, per_second_amount: BigUint) {
+        self.require_caller_has_admin_permissions();
+        self.set_per_second_rewards::<Wrapper<Self>>(per_second_amount);
+    }
+
+    /// Settles the rewards accrued per block so far,
+    /// after which rewards are accrued per second, based on the block timestamp
+    #[endpoint(switchToTimestampBasedRewards)]
+    fn switch_to_timestamp_based_rewards_endpoint(&self, per_second_amount: BigUint) {
+        self.require_caller_has_admin_permissions();
+        self.switch_to_timestamp_based_rewards::<Wrapper<Self>>(per_second_amount);
+    }
+
+    /// Settles the rewards accrued per second so far,
+    /// after which rewards are accrued per block again, using the per block reward amount
+    #[endpoint(switchToBlockBasedRewards)]
+    fn switch_to_block_based_rewards_endpoint(&self) {
+        self.require_caller_has_admin_permissions();
+        self.switch_to_block_based_rewards::<Wrapper<Self>>();
+    }
 }
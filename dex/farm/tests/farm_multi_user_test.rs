@@ -1,9 +1,15 @@
 use common_structs::FarmTokenAttributes;
+use elrond_wasm::types::ManagedVec;
 use elrond_wasm_debug::{managed_address, managed_biguint, rust_biguint, DebugApi};
+use farm::delegated_harvest::DelegatedHarvestModule;
 
 pub mod farm_setup;
 use farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule;
 use farm_boosted_yields::boosted_yields_factors::{BoostedYieldsConfig, BoostedYieldsFactors};
+use farm_boosted_yields::boosted_yields_formulas::{
+    BoostedYieldsFormula, BoostedYieldsFormulasModule, EnergyTier,
+};
+use farm_boosted_yields::FarmBoostedYieldsModule;
 use farm_setup::multi_user_farm_setup::*;
 use permissions_module::{Permissions, PermissionsModule};
 use week_timekeeping::WeekTimekeepingModule;
@@ -167,9 +173,27 @@ fn farm_with_boosted_yields_test() {
     let first_boosted_amt = 966; // 1000 energy & 100_000_000 farm tokens
     let first_total = first_base_farm_amt + first_boosted_amt;
 
+    // estimates do not modify the claim progress
+    assert_eq!(
+        farm_setup.get_user_boosted_rewards_estimate(&first_user, first_farm_token_amount),
+        first_boosted_amt
+    );
+    assert_eq!(
+        farm_setup.get_user_boosted_rewards_estimate(&first_user, first_farm_token_amount),
+        first_boosted_amt
+    );
+    assert_eq!(
+        farm_setup.get_user_boosted_rewards_estimate(&second_user, second_farm_token_amount),
+        1533
+    );
+
     let first_receveived_reward_amt =
         farm_setup.claim_rewards(&first_user, 3, first_farm_token_amount);
     assert_eq!(first_receveived_reward_amt, first_total);
+    assert_eq!(
+        farm_setup.get_user_boosted_rewards_estimate(&first_user, first_farm_token_amount),
+        0
+    );
 
     farm_setup
         .b_mock
@@ -966,3 +990,320 @@ fn farm_claim_with_minimum_tokens() {
     farm_setup.check_remaining_boosted_rewards_to_distribute(2, 0);
     farm_setup.check_remaining_boosted_rewards_to_distribute(3, 0);
 }
+
+#[test]
+fn farm_delegated_harvest_test() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup = MultiUserFarmSetup::new(
+        farm::contract_obj,
+        energy_factory_mock::contract_obj,
+        energy_update::contract_obj,
+    );
+
+    let farm_token_amount = 100_000_000;
+    let first_user = farm_setup.first_user.clone();
+    let operator = farm_setup.second_user.clone();
+    let third_user = farm_setup.third_user.clone();
+    farm_setup.enter_farm(&first_user, farm_token_amount);
+
+    farm_setup.add_harvest_operator(&first_user, &operator);
+    farm_setup.deposit_farm_tokens_for_harvest(&first_user, 1, farm_token_amount);
+
+    // advance blocks - 10 blocks - 10 * 1_000 = 10_000 total rewards
+    farm_setup.b_mock.set_block_nonce(10);
+
+    // not an operator
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &third_user,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.claim_rewards_on_behalf(managed_address!(&first_user));
+            },
+        )
+        .assert_user_error("Caller may not harvest for this user");
+
+    // rewards go to the owner, the new position stays escrowed
+    let received_reward_amt =
+        farm_setup.claim_rewards_on_behalf(&operator, &first_user, farm_token_amount);
+    assert_eq!(received_reward_amt, 10_000);
+
+    farm_setup.b_mock.check_esdt_balance(
+        &first_user,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(received_reward_amt),
+    );
+    farm_setup
+        .b_mock
+        .check_esdt_balance(&operator, REWARD_TOKEN_ID, &rust_biguint!(0));
+    farm_setup
+        .b_mock
+        .check_nft_balance::<FarmTokenAttributes<DebugApi>>(
+            farm_setup.farm_wrapper.address_ref(),
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_token_amount),
+            None,
+        );
+
+    // revoked operator may not harvest anymore
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.remove_harvest_operator(managed_address!(&operator));
+            },
+        )
+        .assert_ok();
+    farm_setup
+        .b_mock
+        .execute_tx(
+            &operator,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.claim_rewards_on_behalf(managed_address!(&first_user));
+            },
+        )
+        .assert_user_error("Caller may not harvest for this user");
+
+    farm_setup.withdraw_farm_tokens_from_harvest(&first_user);
+    farm_setup
+        .b_mock
+        .check_nft_balance::<FarmTokenAttributes<DebugApi>>(
+            &first_user,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_token_amount),
+            None,
+        );
+}
+
+#[test]
+fn farm_boosted_yields_formulas_simulation_test() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup = MultiUserFarmSetup::new(
+        farm::contract_obj,
+        energy_factory_mock::contract_obj,
+        energy_update::contract_obj,
+    );
+
+    farm_setup.set_boosted_yields_rewards_percentage(BOOSTED_YIELDS_PERCENTAGE);
+    farm_setup.set_boosted_yields_factors();
+
+    let first_user = farm_setup.first_user.clone();
+    let second_user = farm_setup.second_user.clone();
+    farm_setup.enter_farm(&first_user, 100_000_000);
+
+    // advance blocks - 10 blocks - 10 * 1_000 = 10_000 total rewards
+    // 2_500 go to the boosted rewards of the current week
+    farm_setup.b_mock.set_block_nonce(10);
+    farm_setup.enter_farm(&second_user, 50_000_000);
+
+    // total energy = 1_000, farm supply = 150_000_000 + 50_000_000
+    let simulate = |farm_setup: &mut MultiUserFarmSetup<_, _, _>| {
+        let mut result = 0;
+        farm_setup
+            .b_mock
+            .execute_query(&farm_setup.farm_wrapper, |sc| {
+                result = sc
+                    .get_boosted_rewards_simulation(
+                        managed_biguint!(1_000),
+                        managed_biguint!(50_000_000),
+                    )
+                    .to_u64()
+                    .unwrap();
+            })
+            .assert_ok();
+
+        result
+    };
+
+    // (2_500 * 3 * 1_000 / 1_000 + 2_500 * 2 * 50_000_000 / 200_000_000) / 5
+    assert_eq!(simulate(&mut farm_setup), 1_750);
+
+    let owner = farm_setup.owner.clone();
+    farm_setup
+        .b_mock
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_boosted_yields_formula(BoostedYieldsFormula::LpShareCapped);
+        })
+        .assert_ok();
+
+    // capped to 2_500 * 50_000_000 / 200_000_000
+    assert_eq!(simulate(&mut farm_setup), 625);
+
+    farm_setup
+        .b_mock
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            let mut tiers = ManagedVec::new();
+            tiers.push(EnergyTier {
+                min_energy: managed_biguint!(500),
+                boost_percentage: 5_000,
+            });
+            tiers.push(EnergyTier {
+                min_energy: managed_biguint!(2_000),
+                boost_percentage: 10_000,
+            });
+            sc.set_boosted_yields_formula(BoostedYieldsFormula::TieredEnergy { tiers });
+        })
+        .assert_ok();
+
+    // first tier - 625 * 50%
+    assert_eq!(simulate(&mut farm_setup), 312);
+
+    // boosts can't exceed 100%
+    farm_setup
+        .b_mock
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            let mut tiers = ManagedVec::new();
+            tiers.push(EnergyTier {
+                min_energy: managed_biguint!(500),
+                boost_percentage: 10_001,
+            });
+            sc.set_boosted_yields_formula(BoostedYieldsFormula::TieredEnergy { tiers });
+        })
+        .assert_user_error("Invalid boosted yields formula");
+
+    // tiers must be sorted
+    farm_setup
+        .b_mock
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            let mut tiers = ManagedVec::new();
+            tiers.push(EnergyTier {
+                min_energy: managed_biguint!(2_000),
+                boost_percentage: 10_000,
+            });
+            tiers.push(EnergyTier {
+                min_energy: managed_biguint!(500),
+                boost_percentage: 5_000,
+            });
+            sc.set_boosted_yields_formula(BoostedYieldsFormula::TieredEnergy { tiers });
+        })
+        .assert_user_error("Invalid boosted yields formula");
+}
+
+#[test]
+fn farm_boosted_yields_sqrt_energy_test() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup = MultiUserFarmSetup::new(
+        farm::contract_obj,
+        energy_factory_mock::contract_obj,
+        energy_update::contract_obj,
+    );
+
+    farm_setup.set_boosted_yields_rewards_percentage(BOOSTED_YIELDS_PERCENTAGE);
+    farm_setup.set_boosted_yields_factors();
+
+    let owner = farm_setup.owner.clone();
+    farm_setup
+        .b_mock
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_boosted_yields_formula(BoostedYieldsFormula::SqrtEnergy);
+        })
+        .assert_ok();
+
+    let first_user = farm_setup.first_user.clone();
+    let second_user = farm_setup.second_user.clone();
+    farm_setup.set_user_energy(&first_user, 400, 0, 1);
+    farm_setup.set_user_energy(&second_user, 900, 0, 1);
+    farm_setup.enter_farm(&first_user, 100_000_000);
+
+    // advance blocks - 10 blocks - 10 * 1_000 = 10_000 total rewards
+    // 2_500 go to the boosted rewards of the current week
+    farm_setup.b_mock.set_block_nonce(10);
+    farm_setup.enter_farm(&second_user, 50_000_000);
+
+    // sqrt(400) + sqrt(900)
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let current_week = sc.get_current_week();
+            assert_eq!(
+                sc.total_sqrt_energy_for_week(current_week).get(),
+                managed_biguint!(50)
+            );
+        })
+        .assert_ok();
+
+    // (2_500 * 3 * 10 / (50 + 10) + 2_500 * 2 * 50_000_000 / 200_000_000) / 5
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let result = sc.get_boosted_rewards_simulation(
+                managed_biguint!(100),
+                managed_biguint!(50_000_000),
+            );
+            assert_eq!(result, managed_biguint!(500));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn farm_boosted_yields_sqrt_energy_legacy_user_test() {
+    let _ = DebugApi::dummy();
+    let mut farm_setup = MultiUserFarmSetup::new(
+        farm::contract_obj,
+        energy_factory_mock::contract_obj,
+        energy_update::contract_obj,
+    );
+
+    farm_setup.set_boosted_yields_rewards_percentage(BOOSTED_YIELDS_PERCENTAGE);
+    farm_setup.set_boosted_yields_factors();
+
+    // first user enters before the sqrt energy formula is set
+    let first_user = farm_setup.first_user.clone();
+    let second_user = farm_setup.second_user.clone();
+    farm_setup.set_user_energy(&first_user, 400, 0, 1);
+    farm_setup.set_user_energy(&second_user, 900, 0, 1);
+    farm_setup.enter_farm(&first_user, 100_000_000);
+
+    let owner = farm_setup.owner.clone();
+    farm_setup
+        .b_mock
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_boosted_yields_formula(BoostedYieldsFormula::SqrtEnergy);
+        })
+        .assert_ok();
+
+    farm_setup.b_mock.set_block_nonce(10);
+    farm_setup.enter_farm(&second_user, 50_000_000);
+
+    // the first user was never added, so the second user's amount is kept
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let current_week = sc.get_current_week();
+            assert_eq!(
+                sc.total_sqrt_energy_for_week(current_week).get(),
+                managed_biguint!(30)
+            );
+            assert_eq!(
+                sc.user_sqrt_energy(&managed_address!(&first_user)).get(),
+                managed_biguint!(0)
+            );
+        })
+        .assert_ok();
+
+    // the first user is added on their next interaction
+    let _ = farm_setup.claim_rewards(&first_user, 1, 100_000_000);
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let current_week = sc.get_current_week();
+            assert_eq!(
+                sc.total_sqrt_energy_for_week(current_week).get(),
+                managed_biguint!(50)
+            );
+            assert_eq!(
+                sc.user_sqrt_energy(&managed_address!(&first_user)).get(),
+                managed_biguint!(20)
+            );
+        })
+        .assert_ok();
+}
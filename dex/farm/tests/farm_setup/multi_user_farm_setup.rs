@@ -17,6 +17,7 @@ use elrond_wasm_debug::{
 use energy_factory_mock::EnergyFactoryMock;
 use energy_query::{Energy, EnergyQueryModule};
 use energy_update::EnergyUpdate;
+use farm::delegated_harvest::DelegatedHarvestModule;
 use farm::Farm;
 use farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule;
 use farm_boosted_yields::FarmBoostedYieldsModule;
@@ -360,6 +361,25 @@ where
         result
     }
 
+    pub fn get_user_boosted_rewards_estimate(
+        &mut self,
+        user: &Address,
+        farm_token_amount: u64,
+    ) -> u64 {
+        let mut result = 0;
+        self.b_mock
+            .execute_query(&self.farm_wrapper, |sc| {
+                let result_managed = sc.get_user_boosted_rewards_estimate(
+                    managed_address!(user),
+                    managed_biguint!(farm_token_amount),
+                );
+                result = result_managed.to_u64().unwrap();
+            })
+            .assert_ok();
+
+        result
+    }
+
     pub fn claim_rewards(
         &mut self,
         user: &Address,
@@ -444,6 +464,77 @@ where
         result
     }
 
+    pub fn add_harvest_operator(&mut self, user: &Address, operator: &Address) {
+        self.b_mock
+            .execute_tx(user, &self.farm_wrapper, &rust_biguint!(0), |sc| {
+                sc.add_harvest_operator(managed_address!(operator));
+            })
+            .assert_ok();
+    }
+
+    pub fn deposit_farm_tokens_for_harvest(
+        &mut self,
+        user: &Address,
+        farm_token_nonce: u64,
+        farm_token_amount: u64,
+    ) {
+        self.b_mock
+            .execute_esdt_transfer(
+                user,
+                &self.farm_wrapper,
+                FARM_TOKEN_ID,
+                farm_token_nonce,
+                &rust_biguint!(farm_token_amount),
+                |sc| {
+                    sc.deposit_farm_tokens_for_harvest();
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn withdraw_farm_tokens_from_harvest(&mut self, user: &Address) {
+        self.b_mock
+            .execute_tx(user, &self.farm_wrapper, &rust_biguint!(0), |sc| {
+                let _ = sc.withdraw_farm_tokens_from_harvest();
+            })
+            .assert_ok();
+    }
+
+    pub fn claim_rewards_on_behalf(
+        &mut self,
+        caller: &Address,
+        user: &Address,
+        farm_token_amount: u64,
+    ) -> u64 {
+        self.last_farm_token_nonce += 1;
+
+        let expected_farm_token_nonce = self.last_farm_token_nonce;
+        let mut result = 0;
+        self.b_mock
+            .execute_tx(caller, &self.farm_wrapper, &rust_biguint!(0), |sc| {
+                let (out_farm_token, out_reward_token) = sc
+                    .claim_rewards_on_behalf(managed_address!(user))
+                    .into_tuple();
+                assert_eq!(
+                    out_farm_token.token_identifier,
+                    managed_token_id!(FARM_TOKEN_ID)
+                );
+                assert_eq!(out_farm_token.token_nonce, expected_farm_token_nonce);
+                assert_eq!(out_farm_token.amount, managed_biguint!(farm_token_amount));
+
+                assert_eq!(
+                    out_reward_token.token_identifier,
+                    managed_token_id!(REWARD_TOKEN_ID)
+                );
+                assert_eq!(out_reward_token.token_nonce, 0);
+
+                result = out_reward_token.amount.to_u64().unwrap();
+            })
+            .assert_ok();
+
+        result
+    }
+
     pub fn exit_farm(
         &mut self,
         user: &Address,
@@ -6,6 +6,7 @@ use elrond_wasm_debug::{
     managed_address, managed_biguint, managed_token_id, rust_biguint, tx_mock::TxInputESDT,
     DebugApi,
 };
+use farm::Farm;
 use farm_setup::single_user_farm_setup::*;
 use sc_whitelist_module::SCWhitelistModule;
 
@@ -613,3 +614,82 @@ fn test_farm_through_simple_lock() {
         Some(&lp_proxy_token_attributes),
     );
 }
+
+#[test]
+fn test_switch_between_block_and_timestamp_based_rewards() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj);
+    let per_second_reward_amount = 1_000;
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.enter_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0, 0);
+    farm_setup.check_farm_token_supply(farm_in_amount);
+
+    // 10 blocks of per block rewards
+    farm_setup.set_block_nonce(10);
+    farm_setup.blockchain_wrapper.set_block_timestamp(100);
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_per_second_rewards_endpoint(managed_biguint!(per_second_reward_amount));
+                assert!(!sc.timestamp_based_rewards().get());
+
+                sc.switch_to_timestamp_based_rewards_endpoint(managed_biguint!(
+                    per_second_reward_amount
+                ));
+                assert!(sc.timestamp_based_rewards().get());
+                assert_eq!(sc.last_reward_timestamp().get(), 100);
+            },
+        )
+        .assert_ok();
+
+    // 60 seconds of per second rewards, block nonce is ignored
+    farm_setup.set_block_nonce(1_000);
+    farm_setup.blockchain_wrapper.set_block_timestamp(160);
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.switch_to_block_based_rewards_endpoint();
+                assert!(!sc.timestamp_based_rewards().get());
+                assert_eq!(sc.last_reward_block_nonce().get(), 1_000);
+            },
+        )
+        .assert_ok();
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.switch_to_block_based_rewards_endpoint();
+            },
+        )
+        .assert_user_error("Timestamp based rewards not enabled");
+
+    // 10 more blocks of per block rewards, timestamp is ignored
+    farm_setup.set_block_epoch(5);
+    farm_setup.set_block_nonce(1_010);
+    farm_setup.blockchain_wrapper.set_block_timestamp(1_000);
+
+    let expected_mex_out =
+        10 * PER_BLOCK_REWARD_AMOUNT + 60 * per_second_reward_amount + 10 * PER_BLOCK_REWARD_AMOUNT;
+    farm_setup.exit_farm(
+        farm_in_amount,
+        expected_farm_token_nonce,
+        expected_mex_out,
+        farm_in_amount,
+        &rust_biguint!(expected_mex_out),
+        &rust_biguint!(USER_TOTAL_LP_TOKENS),
+    );
+    farm_setup.check_farm_token_supply(0);
+}
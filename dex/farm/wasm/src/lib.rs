@@ -9,35 +9,46 @@ elrond_wasm_node::wasm_endpoints! {
     (
         callBack
         addAdmin
+        addHarvestOperator
         addSCAddressToWhitelist
         addToPauseWhitelist
         calculateRewardsForGivenPosition
         claimRewards
+        claimRewardsOnBehalf
         collectUndistributedBoostedRewards
         compoundRewards
+        compoundRewardsOnBehalf
+        depositFarmTokensForHarvest
+        emergencyExit
         endProduceRewards
         enterFarm
         exitFarm
         getAccumulatedRewardsForWeek
+        getBoostedRewardsSimulation
         getBoostedYieldsFactors
+        getBoostedYieldsFormula
         getBoostedYieldsRewardsPercentage
         getBurnGasLimit
         getCurrentClaimProgress
         getCurrentWeek
         getDivisionSafetyConstant
         getEnergyFactoryAddress
+        getEscrowedFarmTokens
         getFarmSupplyForWeek
         getFarmTokenId
         getFarmTokenSupply
         getFarmingTokenId
         getFirstWeekStartEpoch
+        getHarvestOperators
         getLastActiveWeekForUser
         getLastGlobalUpdateWeek
         getLastRewardBlockNonce
+        getLastRewardTimestamp
         getMinimumFarmingEpoch
         getPairContractManagedAddress
         getPenaltyPercent
         getPerBlockRewardAmount
+        getPerSecondRewardAmount
         getPermissions
         getRemainingBoostedRewardsToDistribute
         getRewardPerShare
@@ -48,24 +59,32 @@ elrond_wasm_node::wasm_endpoints! {
         getTotalLockedTokensForWeek
         getTotalRewardsForWeek
         getUndistributedBoostedRewards
+        getUserBoostedRewardsEstimate
         getUserEnergyForWeek
         isSCAddressWhitelisted
+        isTimestampBasedRewards
         mergeFarmTokens
         pause
         registerFarmToken
         removeAdmin
         removeFromPauseWhitelist
+        removeHarvestOperator
         removeSCAddressFromWhitelist
         resume
         setBoostedYieldsFactors
+        setBoostedYieldsFormula
         setBoostedYieldsRewardsPercentage
         setEnergyFactoryAddress
         setPerBlockRewardAmount
+        setPerSecondRewardAmount
         set_burn_gas_limit
         set_minimum_farming_epochs
         set_penalty_percent
         startProduceRewards
+        switchToBlockBasedRewards
+        switchToTimestampBasedRewards
         updateEnergyForUser
         updateOwnerOrAdmin
+        withdrawFarmTokensFromHarvest
     )
 }
@@ -1,8 +1,77 @@
 elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
 
 use farm::ProxyTrait as _;
+use farm_staking::ProxyTrait as _;
+use farm_with_locked_rewards::ProxyTrait as _;
+use pausable::{ProxyTrait as _, State};
+use permissions_module::{Permissions, ProxyTrait as _};
 
 const DIVISION_SAFETY_CONST: u64 = 1_000_000_000_000_000_000;
+static STATE_STORAGE_KEY: &[u8] = b"state";
+static PERMISSIONS_STORAGE_KEY: &[u8] = b"permissions";
+static MAX_APR_STORAGE_KEY: &[u8] = b"annualPercentageRewards";
+static MIN_UNBOND_EPOCHS_STORAGE_KEY: &[u8] = b"minUnbondEpochs";
+pub const DEFAULT_MAX_FARMS_PER_DEPLOYER: usize = 10;
+
+#[derive(
+    TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Debug,
+)]
+pub enum FarmType {
+    SimpleFarm,
+    FarmWithLockedRewards,
+    FarmStaking,
+}
+
+/// The arguments a farm was deployed with, kept for upgrades
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub enum FarmDeployArgs<M: ManagedTypeApi> {
+    SimpleFarm {
+        reward_token_id: TokenIdentifier<M>,
+        farming_token_id: TokenIdentifier<M>,
+        pair_contract_address: ManagedAddress<M>,
+    },
+    FarmWithLockedRewards {
+        reward_token_id: TokenIdentifier<M>,
+        farming_token_id: TokenIdentifier<M>,
+        pair_contract_address: ManagedAddress<M>,
+    },
+    FarmStaking {
+        farming_token_id: TokenIdentifier<M>,
+        max_apr: BigUint<M>,
+        min_unbond_epochs: u64,
+    },
+}
+
+impl<M: ManagedTypeApi> FarmDeployArgs<M> {
+    pub fn get_farm_type(&self) -> FarmType {
+        match self {
+            FarmDeployArgs::SimpleFarm { .. } => FarmType::SimpleFarm,
+            FarmDeployArgs::FarmWithLockedRewards { .. } => FarmType::FarmWithLockedRewards,
+            FarmDeployArgs::FarmStaking { .. } => FarmType::FarmStaking,
+        }
+    }
+
+    pub fn get_farming_token_id(&self) -> &TokenIdentifier<M> {
+        match self {
+            FarmDeployArgs::SimpleFarm {
+                farming_token_id, ..
+            } => farming_token_id,
+            FarmDeployArgs::FarmWithLockedRewards {
+                farming_token_id, ..
+            } => farming_token_id,
+            FarmDeployArgs::FarmStaking {
+                farming_token_id, ..
+            } => farming_token_id,
+        }
+    }
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, Clone, PartialEq, Debug)]
+pub struct DeployedFarmInfo<M: ManagedTypeApi> {
+    pub deployer: ManagedAddress<M>,
+    pub deploy_args: FarmDeployArgs<M>,
+}
 
 #[elrond_wasm::module]
 pub trait FarmDeployModule {
@@ -13,33 +82,178 @@ pub trait FarmDeployModule {
         farming_token_id: TokenIdentifier,
         pair_contract_address: ManagedAddress,
     ) -> ManagedAddress {
+        self.deploy_farm_from_args(FarmDeployArgs::SimpleFarm {
+            reward_token_id,
+            farming_token_id,
+            pair_contract_address,
+        })
+    }
+
+    #[endpoint(deployFarmWithLockedRewards)]
+    fn deploy_farm_with_locked_rewards(
+        &self,
+        reward_token_id: TokenIdentifier,
+        farming_token_id: TokenIdentifier,
+        pair_contract_address: ManagedAddress,
+    ) -> ManagedAddress {
+        self.deploy_farm_from_args(FarmDeployArgs::FarmWithLockedRewards {
+            reward_token_id,
+            farming_token_id,
+            pair_contract_address,
+        })
+    }
+
+    #[endpoint(deployFarmStaking)]
+    fn deploy_farm_staking(
+        &self,
+        farming_token_id: TokenIdentifier,
+        max_apr: BigUint,
+        min_unbond_epochs: u64,
+    ) -> ManagedAddress {
+        self.deploy_farm_from_args(FarmDeployArgs::FarmStaking {
+            farming_token_id,
+            max_apr,
+            min_unbond_epochs,
+        })
+    }
+
+    /// Upgrades the farm to the current template of its type.
+    /// The token IDs are taken from the arguments the farm was deployed with.
+    /// The farm's init pauses it and grants ADMIN to the deployer again,
+    /// so its state, the deployer's permissions and the FarmStaking settings
+    /// are read before the upgrade and restored afterwards.
+    #[only_owner]
+    #[endpoint(upgradeFarm)]
+    fn upgrade_farm(&self, farm_address: ManagedAddress) {
+        let farm_info_mapper = self.deployed_farm_info(&farm_address);
+        require!(!farm_info_mapper.is_empty(), "Unknown farm address");
+
+        let farm_info = farm_info_mapper.get();
+        let farm_template = self.get_farm_template(farm_info.deploy_args.get_farm_type());
+        let farm_state: State = self.read_storage_from_farm(&farm_address, STATE_STORAGE_KEY);
+        let deployer_permissions = self.read_farm_permissions(&farm_address, &farm_info.deployer);
         let owner = self.blockchain().get_owner_address();
-        let caller = self.blockchain().get_caller();
-        let mut admins_list = MultiValueEncoded::new();
-        admins_list.push(caller.clone());
-
-        let farm_template = self.farm_template_address().get();
-        let code_metadata =
-            CodeMetadata::PAYABLE_BY_SC & CodeMetadata::READABLE & CodeMetadata::UPGRADEABLE;
-        let (new_farm_address, ()) = self
-            .farm_deploy_proxy()
-            .init(
+        let admins_list = self.get_farm_admins_list(&farm_info.deployer);
+        let code_metadata = self.get_farm_code_metadata();
+        match farm_info.deploy_args {
+            FarmDeployArgs::SimpleFarm {
                 reward_token_id,
                 farming_token_id,
-                DIVISION_SAFETY_CONST,
                 pair_contract_address,
-                owner,
-                admins_list,
-            )
-            .deploy_from_source(&farm_template, code_metadata);
-
-        self.deployer_farm_addresses(&caller)
-            .update(|farm_addresses| {
-                farm_addresses.push(new_farm_address.clone());
-            });
-        self.deployers_list().insert(caller);
+            } => {
+                self.farm_deploy_proxy()
+                    .contract(farm_address.clone())
+                    .init(
+                        reward_token_id,
+                        farming_token_id,
+                        DIVISION_SAFETY_CONST,
+                        pair_contract_address,
+                        owner,
+                        admins_list,
+                    )
+                    .upgrade_from_source(&farm_template, code_metadata);
+            }
+            FarmDeployArgs::FarmWithLockedRewards {
+                reward_token_id,
+                farming_token_id,
+                pair_contract_address,
+            } => {
+                self.farm_with_locked_rewards_deploy_proxy()
+                    .contract(farm_address.clone())
+                    .init(
+                        reward_token_id,
+                        farming_token_id,
+                        DIVISION_SAFETY_CONST,
+                        pair_contract_address,
+                        owner,
+                        admins_list,
+                    )
+                    .upgrade_from_source(&farm_template, code_metadata);
+            }
+            FarmDeployArgs::FarmStaking {
+                farming_token_id, ..
+            } => {
+                let max_apr: BigUint =
+                    self.read_storage_from_farm(&farm_address, MAX_APR_STORAGE_KEY);
+                let min_unbond_epochs: u64 =
+                    self.read_storage_from_farm(&farm_address, MIN_UNBOND_EPOCHS_STORAGE_KEY);
+                self.farm_staking_deploy_proxy()
+                    .contract(farm_address.clone())
+                    .init(
+                        farming_token_id,
+                        DIVISION_SAFETY_CONST,
+                        max_apr,
+                        min_unbond_epochs,
+                        owner,
+                        admins_list,
+                    )
+                    .upgrade_from_source(&farm_template, code_metadata);
+            }
+        }
 
-        new_farm_address
+        // all farm types share the permissions and pausable endpoints
+        if !deployer_permissions.contains(Permissions::ADMIN) {
+            let _: IgnoreValue = self
+                .farm_deploy_proxy()
+                .contract(farm_address.clone())
+                .remove_admin_endpoint(farm_info.deployer)
+                .execute_on_dest_context();
+        }
+        if farm_state == State::Active {
+            let _: IgnoreValue = self
+                .farm_deploy_proxy()
+                .contract(farm_address)
+                .resume()
+                .execute_on_dest_context();
+        }
+    }
+
+    /// Records the deploy arguments of a farm deployed before they were stored,
+    /// so it can be upgraded and found by farming token.
+    #[only_owner]
+    #[endpoint(setDeployedFarmInfo)]
+    fn set_deployed_farm_info(
+        &self,
+        farm_address: ManagedAddress,
+        deployer: ManagedAddress,
+        deploy_args: FarmDeployArgs<Self::Api>,
+    ) {
+        let farm_info_mapper = self.deployed_farm_info(&farm_address);
+        require!(farm_info_mapper.is_empty(), "Farm info already set");
+
+        let deployer_farms = self.deployer_farm_addresses(&deployer).get();
+        require!(
+            deployer_farms
+                .iter()
+                .any(|address| *address == farm_address),
+            "Farm not deployed by the given deployer"
+        );
+
+        self.farms_by_farming_token(deploy_args.get_farming_token_id())
+            .insert(farm_address);
+        farm_info_mapper.set(&DeployedFarmInfo {
+            deployer,
+            deploy_args,
+        });
+    }
+
+    #[only_owner]
+    #[endpoint(setFarmTemplateAddress)]
+    fn set_farm_template_address(&self, farm_type: FarmType, template_address: ManagedAddress) {
+        require!(
+            self.blockchain().is_smart_contract(&template_address),
+            "Invalid farm template address"
+        );
+
+        self.farm_template_address(farm_type).set(&template_address);
+    }
+
+    #[only_owner]
+    #[endpoint(setMaxFarmsPerDeployer)]
+    fn set_max_farms_per_deployer(&self, max_farms: usize) {
+        require!(max_farms > 0, "Invalid max farms per deployer");
+
+        self.max_farms_per_deployer().set(max_farms);
     }
 
     #[only_owner]
@@ -62,6 +276,125 @@ pub trait FarmDeployModule {
         let _: IgnoreValue = contract_call.execute_on_dest_context();
     }
 
+    fn deploy_farm_from_args(&self, deploy_args: FarmDeployArgs<Self::Api>) -> ManagedAddress {
+        let caller = self.blockchain().get_caller();
+        let deployer_farms_mapper = self.deployer_farm_addresses(&caller);
+        require!(
+            deployer_farms_mapper.get().len() < self.max_farms_per_deployer().get(),
+            "Max farms per deployer reached"
+        );
+
+        let farm_template = self.get_farm_template(deploy_args.get_farm_type());
+        let owner = self.blockchain().get_owner_address();
+        let admins_list = self.get_farm_admins_list(&caller);
+        let code_metadata = self.get_farm_code_metadata();
+        let (new_farm_address, ()) = match deploy_args.clone() {
+            FarmDeployArgs::SimpleFarm {
+                reward_token_id,
+                farming_token_id,
+                pair_contract_address,
+            } => self
+                .farm_deploy_proxy()
+                .init(
+                    reward_token_id,
+                    farming_token_id,
+                    DIVISION_SAFETY_CONST,
+                    pair_contract_address,
+                    owner,
+                    admins_list,
+                )
+                .deploy_from_source(&farm_template, code_metadata),
+            FarmDeployArgs::FarmWithLockedRewards {
+                reward_token_id,
+                farming_token_id,
+                pair_contract_address,
+            } => self
+                .farm_with_locked_rewards_deploy_proxy()
+                .init(
+                    reward_token_id,
+                    farming_token_id,
+                    DIVISION_SAFETY_CONST,
+                    pair_contract_address,
+                    owner,
+                    admins_list,
+                )
+                .deploy_from_source(&farm_template, code_metadata),
+            FarmDeployArgs::FarmStaking {
+                farming_token_id,
+                max_apr,
+                min_unbond_epochs,
+            } => self
+                .farm_staking_deploy_proxy()
+                .init(
+                    farming_token_id,
+                    DIVISION_SAFETY_CONST,
+                    max_apr,
+                    min_unbond_epochs,
+                    owner,
+                    admins_list,
+                )
+                .deploy_from_source(&farm_template, code_metadata),
+        };
+
+        self.farms_by_farming_token(deploy_args.get_farming_token_id())
+            .insert(new_farm_address.clone());
+        self.deployed_farm_info(&new_farm_address)
+            .set(&DeployedFarmInfo {
+                deployer: caller.clone(),
+                deploy_args,
+            });
+        deployer_farms_mapper.update(|farm_addresses| {
+            farm_addresses.push(new_farm_address.clone());
+        });
+        self.deployers_list().insert(caller);
+
+        new_farm_address
+    }
+
+    fn get_farm_template(&self, farm_type: FarmType) -> ManagedAddress {
+        let template_mapper = self.farm_template_address(farm_type);
+        require!(
+            !template_mapper.is_empty(),
+            "No template for the given farm type"
+        );
+
+        template_mapper.get()
+    }
+
+    fn get_farm_admins_list(&self, deployer: &ManagedAddress) -> MultiValueEncoded<ManagedAddress> {
+        let mut admins_list = MultiValueEncoded::new();
+        admins_list.push(deployer.clone());
+
+        admins_list
+    }
+
+    fn read_farm_permissions(
+        &self,
+        farm_address: &ManagedAddress,
+        address: &ManagedAddress,
+    ) -> Permissions {
+        let mut key_buffer = ManagedBuffer::new_from_bytes(PERMISSIONS_STORAGE_KEY);
+        key_buffer.append(address.as_managed_buffer());
+
+        self.storage_raw()
+            .read_from_address(farm_address, key_buffer)
+    }
+
+    fn read_storage_from_farm<T: TopDecode>(
+        &self,
+        farm_address: &ManagedAddress,
+        storage_key: &[u8],
+    ) -> T {
+        let key_buffer = ManagedBuffer::new_from_bytes(storage_key);
+        self.storage_raw()
+            .read_from_address(farm_address, key_buffer)
+    }
+
+    #[inline]
+    fn get_farm_code_metadata(&self) -> CodeMetadata {
+        CodeMetadata::PAYABLE_BY_SC | CodeMetadata::READABLE | CodeMetadata::UPGRADEABLE
+    }
+
     #[view(getAllDeployedFarms)]
     fn get_all_deployed_farms(&self) -> ManagedVec<ManagedAddress> {
         let mut all_farm_addresses = ManagedVec::new();
@@ -71,11 +404,35 @@ pub trait FarmDeployModule {
         all_farm_addresses
     }
 
+    #[view(getFarmsByFarmingToken)]
+    fn get_farms_by_farming_token(
+        &self,
+        farming_token_id: TokenIdentifier,
+    ) -> MultiValueEncoded<ManagedAddress> {
+        let mut result = MultiValueEncoded::new();
+        for farm_address in self.farms_by_farming_token(&farming_token_id).iter() {
+            result.push(farm_address);
+        }
+
+        result
+    }
+
     #[proxy]
     fn farm_deploy_proxy(&self) -> farm::Proxy<Self::Api>;
 
+    #[proxy]
+    fn farm_with_locked_rewards_deploy_proxy(&self) -> farm_with_locked_rewards::Proxy<Self::Api>;
+
+    #[proxy]
+    fn farm_staking_deploy_proxy(&self) -> farm_staking::Proxy<Self::Api>;
+
+    #[view(getFarmTemplateAddress)]
     #[storage_mapper("farmTemplateAddress")]
-    fn farm_template_address(&self) -> SingleValueMapper<ManagedAddress>;
+    fn farm_template_address(&self, farm_type: FarmType) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getMaxFarmsPerDeployer)]
+    #[storage_mapper("maxFarmsPerDeployer")]
+    fn max_farms_per_deployer(&self) -> SingleValueMapper<usize>;
 
     #[storage_mapper("deployersList")]
     fn deployers_list(&self) -> UnorderedSetMapper<ManagedAddress>;
@@ -86,4 +443,17 @@ pub trait FarmDeployModule {
         &self,
         deployer_address: &ManagedAddress,
     ) -> SingleValueMapper<ManagedVec<ManagedAddress>>;
+
+    #[view(getDeployedFarmInfo)]
+    #[storage_mapper("deployedFarmInfo")]
+    fn deployed_farm_info(
+        &self,
+        farm_address: &ManagedAddress,
+    ) -> SingleValueMapper<DeployedFarmInfo<Self::Api>>;
+
+    #[storage_mapper("farmsByFarmingToken")]
+    fn farms_by_farming_token(
+        &self,
+        farming_token_id: &TokenIdentifier,
+    ) -> UnorderedSetMapper<ManagedAddress>;
 }
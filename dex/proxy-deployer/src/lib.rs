@@ -4,6 +4,8 @@ elrond_wasm::imports!();
 
 pub mod farm_deploy;
 
+use farm_deploy::{FarmType, DEFAULT_MAX_FARMS_PER_DEPLOYER};
+
 #[elrond_wasm::contract]
 pub trait ProxyDeployer: farm_deploy::FarmDeployModule {
     #[init]
@@ -13,6 +15,9 @@ pub trait ProxyDeployer: farm_deploy::FarmDeployModule {
             "Invalid farm template address"
         );
 
-        self.farm_template_address().set(&farm_template_address);
+        self.farm_template_address(FarmType::SimpleFarm)
+            .set(&farm_template_address);
+        self.max_farms_per_deployer()
+            .set_if_empty(DEFAULT_MAX_FARMS_PER_DEPLOYER);
     }
 }
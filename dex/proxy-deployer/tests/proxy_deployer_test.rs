@@ -0,0 +1,209 @@
+use config::ConfigModule;
+use elrond_wasm::types::ManagedVec;
+use elrond_wasm_debug::{
+    managed_address, managed_token_id, rust_biguint, testing_framework::BlockchainStateWrapper,
+    DebugApi,
+};
+use pausable::{PausableModule, State};
+use permissions_module::{Permissions, PermissionsModule};
+use proxy_deployer::{
+    farm_deploy::{FarmDeployArgs, FarmDeployModule},
+    ProxyDeployer,
+};
+
+static REWARD_TOKEN_ID: &[u8] = b"REW-123456";
+static FARMING_TOKEN_ID: &[u8] = b"LPTOK-123456";
+const FARM_WASM_PATH: &str = "farm/output/farm.wasm";
+const PROXY_DEPLOYER_WASM_PATH: &str = "proxy-deployer/output/proxy-deployer.wasm";
+
+#[test]
+fn set_deployed_farm_info_test() {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let deployer = b_mock.create_user_account(&rust_zero);
+    let other_user = b_mock.create_user_account(&rust_zero);
+
+    let template_wrapper =
+        b_mock.create_sc_account(&rust_zero, Some(&owner), farm::contract_obj, FARM_WASM_PATH);
+    let proxy_deployer_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        proxy_deployer::contract_obj,
+        PROXY_DEPLOYER_WASM_PATH,
+    );
+    let pair_address = b_mock.create_user_account(&rust_zero);
+
+    // farm deployed before the deploy arguments were stored
+    let farm_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(proxy_deployer_wrapper.address_ref()),
+        farm::contract_obj,
+        FARM_WASM_PATH,
+    );
+    let farm_address = farm_wrapper.address_ref().clone();
+
+    b_mock
+        .execute_tx(&owner, &proxy_deployer_wrapper, &rust_zero, |sc| {
+            sc.init(managed_address!(template_wrapper.address_ref()));
+
+            let mut deployer_farms = ManagedVec::new();
+            deployer_farms.push(managed_address!(&farm_address));
+            sc.deployer_farm_addresses(&managed_address!(&deployer))
+                .set(&deployer_farms);
+            sc.deployers_list().insert(managed_address!(&deployer));
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &proxy_deployer_wrapper, &rust_zero, |sc| {
+            sc.upgrade_farm(managed_address!(&farm_address));
+        })
+        .assert_user_error("Unknown farm address");
+
+    let deploy_args = || FarmDeployArgs::<DebugApi>::SimpleFarm {
+        reward_token_id: managed_token_id!(REWARD_TOKEN_ID),
+        farming_token_id: managed_token_id!(FARMING_TOKEN_ID),
+        pair_contract_address: managed_address!(&pair_address),
+    };
+
+    b_mock
+        .execute_tx(&owner, &proxy_deployer_wrapper, &rust_zero, |sc| {
+            sc.set_deployed_farm_info(
+                managed_address!(&farm_address),
+                managed_address!(&other_user),
+                deploy_args(),
+            );
+        })
+        .assert_user_error("Farm not deployed by the given deployer");
+
+    b_mock
+        .execute_tx(&deployer, &proxy_deployer_wrapper, &rust_zero, |sc| {
+            sc.set_deployed_farm_info(
+                managed_address!(&farm_address),
+                managed_address!(&deployer),
+                deploy_args(),
+            );
+        })
+        .assert_user_error("Endpoint can only be called by owner");
+
+    b_mock
+        .execute_tx(&owner, &proxy_deployer_wrapper, &rust_zero, |sc| {
+            sc.set_deployed_farm_info(
+                managed_address!(&farm_address),
+                managed_address!(&deployer),
+                deploy_args(),
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&proxy_deployer_wrapper, |sc| {
+            let farm_info = sc
+                .deployed_farm_info(&managed_address!(&farm_address))
+                .get();
+            assert_eq!(farm_info.deployer, managed_address!(&deployer));
+            assert_eq!(farm_info.deploy_args, deploy_args());
+
+            let farms_mapper = sc.farms_by_farming_token(&managed_token_id!(FARMING_TOKEN_ID));
+            assert_eq!(farms_mapper.len(), 1);
+            assert!(farms_mapper.contains(&managed_address!(&farm_address)));
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &proxy_deployer_wrapper, &rust_zero, |sc| {
+            sc.set_deployed_farm_info(
+                managed_address!(&farm_address),
+                managed_address!(&deployer),
+                deploy_args(),
+            );
+        })
+        .assert_user_error("Farm info already set");
+}
+
+#[test]
+fn deploy_and_upgrade_farm_test() {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let deployer = b_mock.create_user_account(&rust_zero);
+
+    let template_wrapper =
+        b_mock.create_sc_account(&rust_zero, Some(&owner), farm::contract_obj, FARM_WASM_PATH);
+    let proxy_deployer_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        proxy_deployer::contract_obj,
+        PROXY_DEPLOYER_WASM_PATH,
+    );
+    let pair_address = b_mock.create_user_account(&rust_zero);
+
+    b_mock
+        .execute_tx(&owner, &proxy_deployer_wrapper, &rust_zero, |sc| {
+            sc.init(managed_address!(template_wrapper.address_ref()));
+        })
+        .assert_ok();
+
+    let farm_wrapper =
+        b_mock.prepare_deploy_from_sc(proxy_deployer_wrapper.address_ref(), farm::contract_obj);
+    b_mock
+        .execute_tx(&deployer, &proxy_deployer_wrapper, &rust_zero, |sc| {
+            let farm_address = sc.deploy_farm(
+                managed_token_id!(REWARD_TOKEN_ID),
+                managed_token_id!(FARMING_TOKEN_ID),
+                managed_address!(&pair_address),
+            );
+            assert_eq!(farm_address, managed_address!(farm_wrapper.address_ref()));
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&farm_wrapper, |sc| {
+            assert_eq!(sc.state().get(), State::Inactive);
+            assert_eq!(
+                sc.permissions(managed_address!(&deployer)).get(),
+                Permissions::ADMIN
+            );
+        })
+        .assert_ok();
+
+    // the owner activates the farm and revokes the deployer's ADMIN role
+    b_mock
+        .execute_tx(&owner, &farm_wrapper, &rust_zero, |sc| {
+            sc.resume();
+            sc.remove_admin_endpoint(managed_address!(&deployer));
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&deployer, &proxy_deployer_wrapper, &rust_zero, |sc| {
+            sc.upgrade_farm(managed_address!(farm_wrapper.address_ref()));
+        })
+        .assert_user_error("Endpoint can only be called by owner");
+
+    b_mock
+        .execute_tx(&owner, &proxy_deployer_wrapper, &rust_zero, |sc| {
+            sc.upgrade_farm(managed_address!(farm_wrapper.address_ref()));
+        })
+        .assert_ok();
+
+    // the upgrade keeps the farm active and doesn't grant ADMIN again
+    b_mock
+        .execute_query(&farm_wrapper, |sc| {
+            assert_eq!(sc.state().get(), State::Active);
+            assert_eq!(
+                sc.permissions(managed_address!(&deployer)).get(),
+                Permissions::empty()
+            );
+            assert_eq!(
+                sc.reward_token_id().get(),
+                managed_token_id!(REWARD_TOKEN_ID)
+            );
+            assert_eq!(
+                sc.farming_token_id().get(),
+                managed_token_id!(FARMING_TOKEN_ID)
+            );
+        })
+        .assert_ok();
+}
@@ -9,8 +9,18 @@ elrond_wasm_node::wasm_endpoints! {
     (
         callFarmEndpoint
         deployFarm
+        deployFarmStaking
+        deployFarmWithLockedRewards
         getAllDeployedFarms
+        getDeployedFarmInfo
         getDeployerFarmAddresses
+        getFarmTemplateAddress
+        getFarmsByFarmingToken
+        getMaxFarmsPerDeployer
+        setDeployedFarmInfo
+        setFarmTemplateAddress
+        setMaxFarmsPerDeployer
+        upgradeFarm
     )
 }
 
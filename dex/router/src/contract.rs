@@ -6,6 +6,7 @@ elrond_wasm::derive_imports!();
 pub mod enable_swap_by_user;
 mod events;
 pub mod factory;
+pub mod farm_creation;
 pub mod multi_pair_swap;
 
 use factory::PairTokens;
@@ -29,6 +30,7 @@ pub trait Router:
     + multi_pair_swap::MultiPairSwap
     + token_send::TokenSendModule
     + enable_swap_by_user::EnableSwapByUserModule
+    + farm_creation::FarmCreationModule
 {
     #[init]
     fn init(&self, pair_template_address_opt: OptionalValue<ManagedAddress>) {
@@ -26,7 +26,9 @@ pub struct SafePriceResult<M: ManagedTypeApi> {
 
 #[elrond_wasm::module]
 pub trait EnableSwapByUserModule:
-    crate::factory::FactoryModule + crate::events::EventsModule
+    crate::factory::FactoryModule
+    + crate::events::EventsModule
+    + crate::farm_creation::FarmCreationModule
 {
     #[only_owner]
     #[endpoint(configEnableByUserParameters)]
@@ -123,6 +125,7 @@ pub trait EnableSwapByUserModule:
 
         self.set_fee_percents(pair_address.clone());
         self.pair_resume(pair_address.clone());
+        self.queue_farm_creation(&pair_address, pair_lp_token_id);
 
         self.send().direct_esdt(
             &caller,
@@ -22,6 +22,13 @@ pub struct UserPairSwapEnabledEvent<M: ManagedTypeApi> {
     pair_address: ManagedAddress<M>,
 }
 
+#[derive(TypeAbi, TopEncode)]
+pub struct FarmCreatedEvent<M: ManagedTypeApi> {
+    pair_address: ManagedAddress<M>,
+    lp_token_id: TokenIdentifier<M>,
+    farm_address: ManagedAddress<M>,
+}
+
 #[elrond_wasm::module]
 pub trait EventsModule {
     fn emit_create_pair_event(
@@ -75,6 +82,25 @@ pub trait EventsModule {
         )
     }
 
+    fn emit_farm_created_event(
+        &self,
+        pair_address: ManagedAddress,
+        lp_token_id: TokenIdentifier,
+        farm_address: ManagedAddress,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.farm_created_event(
+            pair_address.clone(),
+            lp_token_id.clone(),
+            epoch,
+            FarmCreatedEvent {
+                pair_address,
+                lp_token_id,
+                farm_address,
+            },
+        )
+    }
+
     #[event("create_pair")]
     fn create_pair_event(
         self,
@@ -94,4 +120,13 @@ pub trait EventsModule {
         #[indexed] epoch: u64,
         swap_enabled_event: UserPairSwapEnabledEvent<Self::Api>,
     );
+
+    #[event("farmCreated")]
+    fn farm_created_event(
+        &self,
+        #[indexed] pair_address: ManagedAddress,
+        #[indexed] lp_token_id: TokenIdentifier,
+        #[indexed] epoch: u64,
+        farm_created_event: FarmCreatedEvent<Self::Api>,
+    );
 }
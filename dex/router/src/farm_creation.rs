@@ -0,0 +1,162 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use energy_query::ProxyTrait as _;
+use farm::ProxyTrait as _;
+
+const FARM_DIVISION_SAFETY_CONST: u64 = 1_000_000_000_000_000_000;
+
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct FarmCreationConfig<M: ManagedTypeApi> {
+    pub farm_template_address: ManagedAddress<M>,
+    pub reward_token_id: TokenIdentifier<M>,
+    pub energy_factory_address: ManagedAddress<M>,
+}
+
+#[elrond_wasm::module]
+pub trait FarmCreationModule: crate::events::EventsModule {
+    /// Once set, every pair enabled through setSwapEnabledByUser is queued for farm creation.
+    /// The farms are then deployed by the owner through createFarmForPair.
+    #[only_owner]
+    #[endpoint(setFarmCreationConfig)]
+    fn set_farm_creation_config(
+        &self,
+        farm_template_address: ManagedAddress,
+        reward_token_id: TokenIdentifier,
+        energy_factory_address: ManagedAddress,
+    ) {
+        require!(
+            self.blockchain().is_smart_contract(&farm_template_address),
+            "Invalid farm template address"
+        );
+        require!(
+            self.blockchain().is_smart_contract(&energy_factory_address),
+            "Invalid energy factory address"
+        );
+        require!(
+            reward_token_id.is_valid_esdt_identifier(),
+            "Invalid reward token ID"
+        );
+
+        self.farm_creation_config().set(&FarmCreationConfig {
+            farm_template_address,
+            reward_token_id,
+            energy_factory_address,
+        });
+    }
+
+    #[only_owner]
+    #[endpoint(disableFarmCreation)]
+    fn disable_farm_creation(&self) {
+        self.farm_creation_config().clear();
+    }
+
+    /// Deploys the farm for a pair queued by setSwapEnabledByUser.
+    /// Done as a separate step, so users enabling swaps don't pay for the deploy,
+    /// and a failed deploy doesn't revert the swap enable.
+    #[only_owner]
+    #[endpoint(createFarmForPair)]
+    fn create_farm_for_pair(&self, pair_address: ManagedAddress) -> ManagedAddress {
+        let config_mapper = self.farm_creation_config();
+        require!(!config_mapper.is_empty(), "Farm creation disabled");
+
+        let lp_token_id = match self.pending_farm_creations().remove(&pair_address) {
+            Some(token_id) => token_id,
+            None => sc_panic!("No farm creation pending for pair"),
+        };
+
+        let config = config_mapper.get();
+        let owner = self.blockchain().get_owner_address();
+        let (farm_address, ()) = self
+            .farm_deploy_proxy()
+            .init(
+                config.reward_token_id,
+                lp_token_id.clone(),
+                FARM_DIVISION_SAFETY_CONST,
+                pair_address.clone(),
+                owner,
+                MultiValueEncoded::new(),
+            )
+            .deploy_from_source(
+                &config.farm_template_address,
+                CodeMetadata::UPGRADEABLE | CodeMetadata::READABLE | CodeMetadata::PAYABLE_BY_SC,
+            );
+
+        let _: IgnoreValue = self
+            .farm_contract_proxy(farm_address.clone())
+            .set_energy_factory_address(config.energy_factory_address)
+            .execute_on_dest_context();
+
+        self.pair_farm_map()
+            .insert(pair_address.clone(), farm_address.clone());
+        self.emit_farm_created_event(pair_address, lp_token_id, farm_address.clone());
+
+        farm_address
+    }
+
+    fn queue_farm_creation(&self, pair_address: &ManagedAddress, lp_token_id: TokenIdentifier) {
+        if self.farm_creation_config().is_empty() || self.pair_farm_map().contains_key(pair_address)
+        {
+            return;
+        }
+
+        let _ = self
+            .pending_farm_creations()
+            .insert(pair_address.clone(), lp_token_id);
+    }
+
+    #[view(getPendingFarmCreations)]
+    fn get_pending_farm_creations(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, TokenIdentifier>> {
+        let mut result = MultiValueEncoded::new();
+        for (pair_address, lp_token_id) in self.pending_farm_creations().iter() {
+            result.push((pair_address, lp_token_id).into());
+        }
+
+        result
+    }
+
+    #[view(getFarmCreationConfig)]
+    fn get_farm_creation_config(&self) -> OptionalValue<FarmCreationConfig<Self::Api>> {
+        let mapper = self.farm_creation_config();
+        if mapper.is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(mapper.get())
+        }
+    }
+
+    #[view(getFarmForPair)]
+    fn get_farm_for_pair(&self, pair_address: ManagedAddress) -> OptionalValue<ManagedAddress> {
+        match self.pair_farm_map().get(&pair_address) {
+            Some(farm_address) => OptionalValue::Some(farm_address),
+            None => OptionalValue::None,
+        }
+    }
+
+    #[view(getAllPairFarms)]
+    fn get_all_pair_farms(&self) -> MultiValueEncoded<MultiValue2<ManagedAddress, ManagedAddress>> {
+        let mut result = MultiValueEncoded::new();
+        for (pair_address, farm_address) in self.pair_farm_map().iter() {
+            result.push((pair_address, farm_address).into());
+        }
+
+        result
+    }
+
+    #[proxy]
+    fn farm_deploy_proxy(&self) -> farm::Proxy<Self::Api>;
+
+    #[proxy]
+    fn farm_contract_proxy(&self, to: ManagedAddress) -> farm::Proxy<Self::Api>;
+
+    #[storage_mapper("farmCreationConfig")]
+    fn farm_creation_config(&self) -> SingleValueMapper<FarmCreationConfig<Self::Api>>;
+
+    #[storage_mapper("pendingFarmCreations")]
+    fn pending_farm_creations(&self) -> MapMapper<ManagedAddress, TokenIdentifier>;
+
+    #[storage_mapper("pairFarmMap")]
+    fn pair_farm_map(&self) -> MapMapper<ManagedAddress, ManagedAddress>;
+}
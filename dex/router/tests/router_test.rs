@@ -4,11 +4,13 @@ use elrond_wasm::{
     storage::mappers::StorageTokenWrapper,
     types::{EsdtLocalRole, ManagedAddress, ManagedVec, MultiValueEncoded},
 };
+use energy_query::EnergyQueryModule;
 use pair::{config::ConfigModule, Pair};
 use pausable::{PausableModule, State};
 use router::{
     enable_swap_by_user::EnableSwapByUserModule,
     factory::{FactoryModule, PairTokens},
+    farm_creation::FarmCreationModule,
     multi_pair_swap::SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
     Router,
 };
@@ -120,6 +122,15 @@ fn user_enable_pair_swaps_through_router_test() {
         "simple-lock.wasm",
     );
 
+    let farm_template_wrapper =
+        b_mock.create_sc_account(&rust_zero, Some(&owner), farm::contract_obj, FARM_WASM_PATH);
+    let energy_factory_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        simple_lock::contract_obj,
+        "energy-factory.wasm",
+    );
+
     // setup simple-lock
     b_mock
         .execute_tx(&owner, &simple_lock_wrapper, &rust_zero, |sc| {
@@ -156,7 +167,13 @@ fn user_enable_pair_swaps_through_router_test() {
                 managed_biguint!(MIN_LOCKED_TOKEN_VALUE),
                 MIN_LOCKED_PERIOD_EPOCHS,
                 ManagedVec::from_single_item(managed_token_id!(USDC_TOKEN_ID)).into(),
-            )
+            );
+
+            sc.set_farm_creation_config(
+                managed_address!(farm_template_wrapper.address_ref()),
+                managed_token_id!(MEX_TOKEN_ID),
+                managed_address!(energy_factory_wrapper.address_ref()),
+            );
         })
         .assert_ok();
 
@@ -287,4 +304,58 @@ fn user_enable_pair_swaps_through_router_test() {
             unlock_epoch: current_epoch + MIN_LOCKED_PERIOD_EPOCHS,
         }),
     );
+
+    // the farm is not deployed on swap enable, only queued for the owner
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            let pair_address = managed_address!(pair_wrapper.address_ref());
+            assert!(sc.pair_farm_map().is_empty());
+            assert_eq!(
+                sc.pending_farm_creations().get(&pair_address),
+                Some(managed_token_id!(LPUSDC_TOKEN_ID))
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            let _ = sc.create_farm_for_pair(managed_address!(pair_wrapper.address_ref()));
+        })
+        .assert_user_error("Endpoint can only be called by owner");
+
+    let farm_wrapper =
+        b_mock.prepare_deploy_from_sc(router_wrapper.address_ref(), farm::contract_obj);
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            let farm_address =
+                sc.create_farm_for_pair(managed_address!(pair_wrapper.address_ref()));
+            assert_eq!(farm_address, managed_address!(farm_wrapper.address_ref()));
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            let pair_address = managed_address!(pair_wrapper.address_ref());
+            assert!(sc.pending_farm_creations().is_empty());
+            assert_eq!(
+                sc.pair_farm_map().get(&pair_address),
+                Some(managed_address!(farm_wrapper.address_ref()))
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&farm_wrapper, |sc| {
+            assert_eq!(
+                sc.energy_factory_address().get(),
+                managed_address!(energy_factory_wrapper.address_ref())
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            let _ = sc.create_farm_for_pair(managed_address!(pair_wrapper.address_ref()));
+        })
+        .assert_user_error("No farm creation pending for pair");
 }
@@ -11,16 +11,22 @@ elrond_wasm_node::wasm_endpoints! {
         addCommonTokensForUserPairs
         clearPairTemporaryOwnerStorage
         configEnableByUserParameters
+        createFarmForPair
         createPair
+        disableFarmCreation
         getAllPairContractMetadata
+        getAllPairFarms
         getAllPairTokens
         getAllPairsManagedAddresses
         getCommonTokensForUserPairs
         getEnableSwapByUserConfig
+        getFarmCreationConfig
+        getFarmForPair
         getOwner
         getPair
         getPairCreationEnabled
         getPairTemplateAddress
+        getPendingFarmCreations
         getState
         getTemporaryOwnerPeriod
         issueLpToken
@@ -29,6 +35,7 @@ elrond_wasm_node::wasm_endpoints! {
         removeCommonTokensForUserPairs
         removePair
         resume
+        setFarmCreationConfig
         setFeeOff
         setFeeOn
         setLocalRoles
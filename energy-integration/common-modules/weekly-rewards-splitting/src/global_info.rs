@@ -13,6 +13,7 @@ pub trait WeeklyRewardsGlobalInfo:
 {
     fn update_global_amounts_for_current_week(
         &self,
+        user: &ManagedAddress,
         current_week: Week,
         user_last_active_week: Week,
         prev_user_energy: &Energy<Self::Api>,
@@ -41,6 +42,13 @@ pub trait WeeklyRewardsGlobalInfo:
             &prev_energy_for_update,
             current_user_energy,
         );
+        if self.sqrt_energy_tracking_enabled().get() {
+            self.update_total_sqrt_energy_after_user_energy_update(
+                user,
+                current_week,
+                current_user_energy,
+            );
+        }
 
         self.emit_update_global_amounts_event(
             current_week,
@@ -78,6 +86,12 @@ pub trait WeeklyRewardsGlobalInfo:
             .set(&total_tokens);
         last_week_tokens_mapper.clear();
 
+        let total_sqrt_energy = self
+            .total_sqrt_energy_for_week(last_global_update_week)
+            .get();
+        self.total_sqrt_energy_for_week(current_week)
+            .set(&total_sqrt_energy);
+
         // clear entries that are not accessible anymore
         // users can claim only for weeks of
         // (current_week - 1), (current_week - 2), ... (current_week - USER_MAX_CLAIM_WEEKS)
@@ -85,6 +99,7 @@ pub trait WeeklyRewardsGlobalInfo:
             let inaccessible_week = current_week - USER_MAX_CLAIM_WEEKS - 1;
             self.total_rewards_for_week(inaccessible_week).clear();
             self.total_energy_for_week(inaccessible_week).clear();
+            self.total_sqrt_energy_for_week(inaccessible_week).clear();
         }
     }
 
@@ -141,6 +156,32 @@ pub trait WeeklyRewardsGlobalInfo:
             })
     }
 
+    /// The sum is not depleted, as the square root can't be depleted for all users at once.
+    /// Each user contributes the square root of their energy at their last update,
+    /// which is never lower than the energy they claim with for that week.
+    /// The contribution is stored per user, so only what was actually added is removed.
+    /// Only maintained while enabled, i.e. by farms using a square root energy formula.
+    fn update_total_sqrt_energy_after_user_energy_update(
+        &self,
+        user: &ManagedAddress,
+        current_week: Week,
+        current_user_energy: &Energy<Self::Api>,
+    ) {
+        let user_sqrt_energy_mapper = self.user_sqrt_energy(user);
+        let prev_sqrt_energy = user_sqrt_energy_mapper.get();
+        let current_sqrt_energy = current_user_energy.get_energy_amount().sqrt();
+        if prev_sqrt_energy == current_sqrt_energy {
+            return;
+        }
+
+        self.total_sqrt_energy_for_week(current_week)
+            .update(|total_sqrt_energy| {
+                *total_sqrt_energy -= &prev_sqrt_energy;
+                *total_sqrt_energy += &current_sqrt_energy;
+            });
+        user_sqrt_energy_mapper.set(&current_sqrt_energy);
+    }
+
     #[view(getLastGlobalUpdateWeek)]
     #[storage_mapper("lastGlobalUpdateWeek")]
     fn last_global_update_week(&self) -> SingleValueMapper<Week>;
@@ -156,6 +197,15 @@ pub trait WeeklyRewardsGlobalInfo:
     #[storage_mapper("totalEnergyForWeek")]
     fn total_energy_for_week(&self, week: Week) -> SingleValueMapper<BigUint>;
 
+    #[storage_mapper("totalSqrtEnergyForWeek")]
+    fn total_sqrt_energy_for_week(&self, week: Week) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("userSqrtEnergy")]
+    fn user_sqrt_energy(&self, user: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("sqrtEnergyTrackingEnabled")]
+    fn sqrt_energy_tracking_enabled(&self) -> SingleValueMapper<bool>;
+
     #[view(getTotalLockedTokensForWeek)]
     #[storage_mapper("totalLockedTokensForWeek")]
     fn total_locked_tokens_for_week(&self, week: Week) -> SingleValueMapper<BigUint>;
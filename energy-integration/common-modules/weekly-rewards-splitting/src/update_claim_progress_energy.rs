@@ -71,6 +71,7 @@ pub trait UpdateClaimProgressEnergyModule:
         };
 
         self.update_global_amounts_for_current_week(
+            user,
             current_week,
             last_active_week,
             &prev_energy,
@@ -0,0 +1,184 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use core::cmp;
+
+use crate::boosted_yields_factors::BoostedYieldsFactors;
+
+pub const MAX_PERCENT: u64 = 10_000;
+pub const MAX_ENERGY_TIERS: usize = 10;
+
+#[derive(
+    ManagedVecItem,
+    TypeAbi,
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    Clone,
+    PartialEq,
+    Debug,
+)]
+pub struct EnergyTier<M: ManagedTypeApi> {
+    pub min_energy: BigUint<M>,
+    pub boost_percentage: u64,
+}
+
+/// The formula used to split the weekly boosted rewards between users
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub enum BoostedYieldsFormula<M: ManagedTypeApi> {
+    /// weighted average of the user's energy share and farm share
+    Default,
+    /// the user's farm share, multiplied by the boost of the highest energy tier reached.
+    /// Boosts can't exceed 100%, so the users never receive more than the weekly rewards.
+    TieredEnergy { tiers: ManagedVec<M, EnergyTier<M>> },
+    /// same as default, but the energy share is the square root of the user's energy,
+    /// divided by the sum of the square roots of all the users' energy.
+    /// The sum is only tracked while this formula is set, so users who did not
+    /// interact with the farm since then have no energy share.
+    SqrtEnergy,
+    /// same as default, but the user may never receive more than their farm share
+    LpShareCapped,
+}
+
+pub struct UserBoostedRewardsInput<'a, M: ManagedTypeApi> {
+    pub weekly_rewards: &'a BigUint<M>,
+    pub energy_amount: &'a BigUint<M>,
+    pub total_energy: &'a BigUint<M>,
+    pub user_sqrt_energy: &'a BigUint<M>,
+    pub total_sqrt_energy: &'a BigUint<M>,
+    pub user_farm_amount: &'a BigUint<M>,
+    pub farm_supply: &'a BigUint<M>,
+    pub factors: &'a BoostedYieldsFactors<M>,
+}
+
+impl<'a, M: ManagedTypeApi> UserBoostedRewardsInput<'a, M> {
+    fn farm_share(&self) -> BigUint<M> {
+        self.weekly_rewards * self.user_farm_amount / self.farm_supply
+    }
+
+    fn max_rewards(&self) -> BigUint<M> {
+        &self.factors.max_rewards_factor * self.weekly_rewards * self.user_farm_amount
+            / self.farm_supply
+    }
+
+    // computed user rewards = total_boosted_rewards *
+    // (energy_const * user_energy / total_energy + farm_const * user_farm / total_farm) /
+    // (energy_const + farm_const)
+    fn weighted_rewards(
+        &self,
+        energy_amount: &BigUint<M>,
+        total_energy: &BigUint<M>,
+    ) -> BigUint<M> {
+        let boosted_rewards_by_energy =
+            self.weekly_rewards * &self.factors.user_rewards_energy_const * energy_amount
+                / total_energy;
+        let boosted_rewards_by_tokens =
+            self.weekly_rewards * &self.factors.user_rewards_farm_const * self.user_farm_amount
+                / self.farm_supply;
+        let constants_base =
+            &self.factors.user_rewards_energy_const + &self.factors.user_rewards_farm_const;
+
+        (boosted_rewards_by_energy + boosted_rewards_by_tokens) / constants_base
+    }
+}
+
+impl<M: ManagedTypeApi> BoostedYieldsFormula<M> {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            BoostedYieldsFormula::TieredEnergy { tiers } => {
+                if tiers.is_empty() || tiers.len() > MAX_ENERGY_TIERS {
+                    return false;
+                }
+
+                let mut opt_prev_tier: Option<EnergyTier<M>> = None;
+                for tier in tiers {
+                    if tier.boost_percentage > MAX_PERCENT {
+                        return false;
+                    }
+
+                    if let Some(prev_tier) = &opt_prev_tier {
+                        if tier.min_energy <= prev_tier.min_energy {
+                            return false;
+                        }
+                    }
+
+                    opt_prev_tier = Some(tier);
+                }
+
+                true
+            }
+            _ => true,
+        }
+    }
+
+    /// Both the total energy and the farm supply must be non-zero
+    pub fn calculate_user_rewards(&self, input: &UserBoostedRewardsInput<M>) -> BigUint<M> {
+        let max_rewards = input.max_rewards();
+        let user_rewards = match self {
+            BoostedYieldsFormula::Default => {
+                input.weighted_rewards(input.energy_amount, input.total_energy)
+            }
+            BoostedYieldsFormula::TieredEnergy { tiers } => {
+                let mut boost_percentage = 0;
+                for tier in tiers {
+                    if input.energy_amount < &tier.min_energy {
+                        break;
+                    }
+
+                    boost_percentage = tier.boost_percentage;
+                }
+
+                input.farm_share() * boost_percentage / MAX_PERCENT
+            }
+            BoostedYieldsFormula::SqrtEnergy => {
+                if input.total_sqrt_energy == &0 {
+                    return BigUint::zero();
+                }
+
+                // users only have the amount they added to the sum as energy share
+                let sqrt_energy =
+                    cmp::min(input.energy_amount.sqrt(), input.user_sqrt_energy.clone());
+                input.weighted_rewards(&sqrt_energy, input.total_sqrt_energy)
+            }
+            BoostedYieldsFormula::LpShareCapped => {
+                let rewards = input.weighted_rewards(input.energy_amount, input.total_energy);
+                cmp::min(rewards, input.farm_share())
+            }
+        };
+
+        // min between base rewards per week and computed rewards
+        cmp::min(max_rewards, user_rewards)
+    }
+}
+
+#[elrond_wasm::module]
+pub trait BoostedYieldsFormulasModule:
+    permissions_module::PermissionsModule
+    + weekly_rewards_splitting::events::WeeklyRewardsSplittingEventsModule
+    + weekly_rewards_splitting::global_info::WeeklyRewardsGlobalInfo
+    + weekly_rewards_splitting::locked_token_buckets::WeeklyRewardsLockedTokenBucketsModule
+{
+    #[endpoint(setBoostedYieldsFormula)]
+    fn set_boosted_yields_formula(&self, formula: BoostedYieldsFormula<Self::Api>) {
+        self.require_caller_has_admin_permissions();
+        require!(formula.is_valid(), "Invalid boosted yields formula");
+
+        let is_sqrt_energy = matches!(formula, BoostedYieldsFormula::SqrtEnergy);
+        self.sqrt_energy_tracking_enabled().set(is_sqrt_energy);
+        self.boosted_yields_formula().set(&formula);
+    }
+
+    #[view(getBoostedYieldsFormula)]
+    fn get_boosted_yields_formula(&self) -> BoostedYieldsFormula<Self::Api> {
+        let mapper = self.boosted_yields_formula();
+        if mapper.is_empty() {
+            return BoostedYieldsFormula::Default;
+        }
+
+        mapper.get()
+    }
+
+    #[storage_mapper("boostedYieldsFormula")]
+    fn boosted_yields_formula(&self) -> SingleValueMapper<BoostedYieldsFormula<Self::Api>>;
+}
@@ -5,13 +5,15 @@ elrond_wasm::imports!();
 use core::cmp;
 
 use boosted_yields_factors::BoostedYieldsConfig;
+use boosted_yields_formulas::{BoostedYieldsFormula, UserBoostedRewardsInput};
 use common_types::PaymentsVec;
 use week_timekeeping::Week;
 use weekly_rewards_splitting::{
-    base_impl::WeeklyRewardsSplittingTraitsModule, USER_MAX_CLAIM_WEEKS,
+    base_impl::WeeklyRewardsSplittingTraitsModule, ClaimProgress, USER_MAX_CLAIM_WEEKS,
 };
 
 pub mod boosted_yields_factors;
+pub mod boosted_yields_formulas;
 
 const MAX_PERCENT: u64 = 10_000;
 
@@ -32,6 +34,7 @@ impl<M: ManagedTypeApi> SplitReward<M> {
 #[elrond_wasm::module]
 pub trait FarmBoostedYieldsModule:
     boosted_yields_factors::BoostedYieldsFactorsModule
+    + boosted_yields_formulas::BoostedYieldsFormulasModule
     + config::ConfigModule
     + week_timekeeping::WeekTimekeepingModule
     + pausable::PausableModule
@@ -114,7 +117,11 @@ pub trait FarmBoostedYieldsModule:
                 return BigUint::zero();
             }
         };
-        let wrapper = FarmBoostedYieldsWrapper::new(farm_token_amount, config);
+        let formula = self.get_boosted_yields_formula();
+        // the claimed weeks use the amount added before this update
+        let user_sqrt_energy = self.user_sqrt_energy(user).get();
+        let wrapper =
+            FarmBoostedYieldsWrapper::new(farm_token_amount, user_sqrt_energy, config, formula);
         let rewards = self.claim_multi(&wrapper, user);
 
         let mut total = BigUint::zero();
@@ -125,6 +132,166 @@ pub trait FarmBoostedYieldsModule:
         total
     }
 
+    /// Returns the expected boosted rewards for the current week,
+    /// for a hypothetical user joining with the given energy and farm amount.
+    /// Only the rewards accumulated so far in the current week are taken into account.
+    #[view(getBoostedRewardsSimulation)]
+    fn get_boosted_rewards_simulation(
+        &self,
+        energy_amount: BigUint,
+        farm_amount: BigUint,
+    ) -> BigUint {
+        let opt_config = self.try_get_boosted_yields_config();
+        let config = match opt_config {
+            Some(c) => c,
+            None => {
+                return BigUint::zero();
+            }
+        };
+
+        let factors = config.get_latest_factors();
+        if energy_amount < factors.min_energy_amount || farm_amount < factors.min_farm_amount {
+            return BigUint::zero();
+        }
+
+        let current_week = self.get_current_week();
+        let weekly_rewards = self.accumulated_rewards_for_week(current_week).get();
+        let total_energy = self.total_energy_for_week(current_week).get() + &energy_amount;
+        let user_sqrt_energy = energy_amount.sqrt();
+        let total_sqrt_energy =
+            self.total_sqrt_energy_for_week(current_week).get() + &user_sqrt_energy;
+        let farm_supply = self.farm_supply_for_week(current_week).get() + &farm_amount;
+
+        let formula = self.get_boosted_yields_formula();
+        formula.calculate_user_rewards(&UserBoostedRewardsInput {
+            weekly_rewards: &weekly_rewards,
+            energy_amount: &energy_amount,
+            total_energy: &total_energy,
+            user_sqrt_energy: &user_sqrt_energy,
+            total_sqrt_energy: &total_sqrt_energy,
+            user_farm_amount: &farm_amount,
+            farm_supply: &farm_supply,
+            factors: &factors,
+        })
+    }
+
+    /// Returns the boosted rewards the user would receive on their next claim,
+    /// for all the unclaimed weeks. The claim progress is not modified.
+    /// The farm token amount is the total amount of farm tokens used for claiming.
+    #[view(getUserBoostedRewardsEstimate)]
+    fn get_user_boosted_rewards_estimate(
+        &self,
+        user: ManagedAddress,
+        farm_token_amount: BigUint,
+    ) -> BigUint {
+        if self.blockchain().is_smart_contract(&user) {
+            return BigUint::zero();
+        }
+
+        let opt_config = self.try_get_boosted_yields_config();
+        let config = match opt_config {
+            Some(c) => c,
+            None => {
+                return BigUint::zero();
+            }
+        };
+
+        let claim_progress_mapper = self.current_claim_progress(&user);
+        if claim_progress_mapper.is_empty() {
+            return BigUint::zero();
+        }
+
+        let mut claim_progress = claim_progress_mapper.get();
+        let current_week = self.get_current_week();
+        let current_user_energy = self.get_energy_entry(&user);
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut calculated_energy_for_current_epoch = claim_progress.energy.clone();
+        calculated_energy_for_current_epoch.deplete(current_epoch);
+        if current_user_energy.get_energy_amount()
+            < calculated_energy_for_current_epoch.get_energy_amount()
+        {
+            return BigUint::zero();
+        }
+
+        let total_weeks_to_claim = current_week - claim_progress.week;
+        if total_weeks_to_claim > USER_MAX_CLAIM_WEEKS {
+            let extra_weeks = total_weeks_to_claim - USER_MAX_CLAIM_WEEKS;
+            claim_progress.advance_multiple_weeks(extra_weeks);
+        }
+
+        let formula = self.get_boosted_yields_formula();
+        let user_sqrt_energy = self.user_sqrt_energy(&user).get();
+        let weeks_to_claim = cmp::min(total_weeks_to_claim, USER_MAX_CLAIM_WEEKS);
+        let mut total = BigUint::zero();
+        for _ in 0..weeks_to_claim {
+            total += self.estimate_user_boosted_rewards_for_week(
+                &config,
+                &formula,
+                &claim_progress,
+                &user_sqrt_energy,
+                &farm_token_amount,
+            );
+            claim_progress.advance_week();
+        }
+
+        total
+    }
+
+    fn estimate_user_boosted_rewards_for_week(
+        &self,
+        config: &BoostedYieldsConfig<Self::Api>,
+        formula: &BoostedYieldsFormula<Self::Api>,
+        claim_progress: &ClaimProgress<Self::Api>,
+        user_sqrt_energy: &BigUint,
+        user_farm_amount: &BigUint,
+    ) -> BigUint {
+        let week = claim_progress.week;
+        let energy_amount = claim_progress.energy.get_energy_amount();
+        let total_energy = self.total_energy_for_week(week).get();
+        let total_sqrt_energy = self.total_sqrt_energy_for_week(week).get();
+        let farm_supply_for_week = self.farm_supply_for_week(week).get();
+        if total_energy == 0 || farm_supply_for_week == 0 {
+            return BigUint::zero();
+        }
+
+        let factors = config.get_factors_for_week(week);
+        if energy_amount < factors.min_energy_amount || user_farm_amount < &factors.min_farm_amount
+        {
+            return BigUint::zero();
+        }
+
+        // the weekly rewards are only collected on the first claim for that week
+        let total_rewards_mapper = self.total_rewards_for_week(week);
+        let (weekly_rewards, remaining_rewards) = if total_rewards_mapper.is_empty() {
+            let accumulated_rewards = self.accumulated_rewards_for_week(week).get();
+            (accumulated_rewards.clone(), accumulated_rewards)
+        } else {
+            let total_rewards = total_rewards_mapper.get();
+            if total_rewards.is_empty() {
+                return BigUint::zero();
+            }
+
+            let remaining_rewards = self.remaining_boosted_rewards_to_distribute(week).get();
+            (total_rewards.get(0).amount, remaining_rewards)
+        };
+        if weekly_rewards == 0 {
+            return BigUint::zero();
+        }
+
+        let computed_user_reward = formula.calculate_user_rewards(&UserBoostedRewardsInput {
+            weekly_rewards: &weekly_rewards,
+            energy_amount: &energy_amount,
+            total_energy: &total_energy,
+            user_sqrt_energy,
+            total_sqrt_energy: &total_sqrt_energy,
+            user_farm_amount,
+            farm_supply: &farm_supply_for_week,
+            factors,
+        });
+
+        cmp::min(computed_user_reward, remaining_rewards)
+    }
+
     #[view(getBoostedYieldsRewardsPercentage)]
     #[storage_mapper("boostedYieldsRewardsPercentage")]
     fn boosted_yields_rewards_percentage(&self) -> SingleValueMapper<u64>;
@@ -151,17 +318,23 @@ pub trait FarmBoostedYieldsModule:
 
 pub struct FarmBoostedYieldsWrapper<T: FarmBoostedYieldsModule> {
     pub user_farm_amount: BigUint<<T as ContractBase>::Api>,
+    pub user_sqrt_energy: BigUint<<T as ContractBase>::Api>,
     pub boosted_yields_config: BoostedYieldsConfig<<T as ContractBase>::Api>,
+    pub boosted_yields_formula: BoostedYieldsFormula<<T as ContractBase>::Api>,
 }
 
 impl<T: FarmBoostedYieldsModule> FarmBoostedYieldsWrapper<T> {
     pub fn new(
         user_farm_amount: BigUint<<T as ContractBase>::Api>,
+        user_sqrt_energy: BigUint<<T as ContractBase>::Api>,
         boosted_yields_config: BoostedYieldsConfig<<T as ContractBase>::Api>,
+        boosted_yields_formula: BoostedYieldsFormula<<T as ContractBase>::Api>,
     ) -> FarmBoostedYieldsWrapper<T> {
         FarmBoostedYieldsWrapper {
             user_farm_amount,
+            user_sqrt_energy,
             boosted_yields_config,
+            boosted_yields_formula,
         }
     }
 }
@@ -226,25 +399,24 @@ where
             return user_rewards;
         }
 
-        let max_rewards =
-            &factors.max_rewards_factor * &weekly_reward.amount * &self.user_farm_amount
-                / &farm_supply_for_week;
-
-        // computed user rewards = total_boosted_rewards *
-        // (energy_const * user_energy / total_energy + farm_const * user_farm / total_farm) /
-        // (energy_const + farm_const)
-        let boosted_rewards_by_energy =
-            &weekly_reward.amount * &factors.user_rewards_energy_const * energy_amount
-                / total_energy;
-        let boosted_rewards_by_tokens =
-            &weekly_reward.amount * &factors.user_rewards_farm_const * &self.user_farm_amount
-                / &farm_supply_for_week;
-        let constants_base = &factors.user_rewards_energy_const + &factors.user_rewards_farm_const;
-        let boosted_reward_amount =
-            (boosted_rewards_by_energy + boosted_rewards_by_tokens) / constants_base;
-
-        // min between base rewards per week and computed rewards
-        let user_reward = cmp::min(max_rewards, boosted_reward_amount);
+        let total_sqrt_energy = sc.total_sqrt_energy_for_week(week).get();
+        let computed_user_reward =
+            self.boosted_yields_formula
+                .calculate_user_rewards(&UserBoostedRewardsInput {
+                    weekly_rewards: &weekly_reward.amount,
+                    energy_amount,
+                    total_energy,
+                    user_sqrt_energy: &self.user_sqrt_energy,
+                    total_sqrt_energy: &total_sqrt_energy,
+                    user_farm_amount: &self.user_farm_amount,
+                    farm_supply: &farm_supply_for_week,
+                    factors,
+                });
+
+        // some formulas may not split the rewards exactly,
+        // so users can never receive more than what is left for the week
+        let remaining_rewards = sc.remaining_boosted_rewards_to_distribute(week).get();
+        let user_reward = cmp::min(computed_user_reward, remaining_rewards);
         if user_reward > 0 {
             sc.remaining_boosted_rewards_to_distribute(week)
                 .update(|amount| *amount -= &user_reward);
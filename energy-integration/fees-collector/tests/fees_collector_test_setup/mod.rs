@@ -8,7 +8,7 @@ use elrond_wasm_debug::{
 };
 
 use elrond_wasm_modules::pause::PauseModule;
-use energy_factory::{energy::EnergyModule, SimpleLockEnergy};
+use energy_factory::{energy::EnergyModule, virtual_lock::VirtualLockModule, SimpleLockEnergy};
 use energy_query::{Energy, EnergyQueryModule};
 use fees_collector::{config::ConfigModule, fees_accumulation::FeesAccumulationModule, *};
 use locking_module::lock_with_energy_module::LockWithEnergyModule;
@@ -140,6 +140,10 @@ where
                     .set_token_id(managed_token_id!(LOCKED_TOKEN_ID));
                 sc.set_paused(false);
                 sc.add_sc_address_to_whitelist(managed_address!(fc_wrapper.address_ref()));
+                sc.set_virtual_lock_quota(
+                    managed_address!(fc_wrapper.address_ref()),
+                    managed_biguint!(u64::MAX),
+                );
             })
             .assert_ok();
 
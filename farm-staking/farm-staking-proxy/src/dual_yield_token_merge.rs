@@ -0,0 +1,66 @@
+elrond_wasm::imports!();
+
+pub const MIN_MERGE_PAYMENTS: usize = 2;
+
+#[elrond_wasm::module]
+pub trait DualYieldTokenMergeModule:
+    crate::dual_yield_token::DualYieldTokenModule
+    + crate::external_contracts_interactions::ExternalContractsInteractionsModule
+    + crate::lp_farm_token::LpFarmTokenModule
+    + token_merge_helper::TokenMergeHelperModule
+    + elrond_wasm_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    /// Merges the underlying LP farm and staking farm positions of the received dual yield tokens,
+    /// through their respective farms, and sends a single dual yield token position back.
+    #[payable("*")]
+    #[endpoint(mergeDualYieldTokens)]
+    fn merge_dual_yield_tokens(&self) -> EsdtTokenPayment {
+        let payments = self.call_value().all_esdt_transfers();
+        require!(
+            payments.len() >= MIN_MERGE_PAYMENTS,
+            "Must send at least two dual yield tokens"
+        );
+        self.dual_yield_token().require_all_same_token(&payments);
+
+        let lp_farm_token_id = self.lp_farm_token_id().get();
+        let staking_farm_token_id = self.staking_farm_token_id().get();
+        let mut lp_farm_tokens = ManagedVec::new();
+        let mut staking_farm_tokens = ManagedVec::new();
+        for p in &payments {
+            let attributes = self.get_dual_yield_token_attributes(p.token_nonce);
+
+            lp_farm_tokens.push(EsdtTokenPayment::new(
+                lp_farm_token_id.clone(),
+                attributes.lp_farm_token_nonce,
+                self.get_lp_farm_token_amount_equivalent(&attributes, &p.amount),
+            ));
+            staking_farm_tokens.push(EsdtTokenPayment::new(
+                staking_farm_token_id.clone(),
+                attributes.staking_farm_token_nonce,
+                self.get_staking_farm_token_amount_equivalent(&p.amount),
+            ));
+
+            self.burn_dual_yield_tokens(p.token_nonce, &p.amount);
+        }
+
+        let caller = self.blockchain().get_caller();
+        let base_lp_farm_token = lp_farm_tokens.get(0);
+        let additional_lp_farm_tokens = lp_farm_tokens
+            .slice(1, lp_farm_tokens.len())
+            .unwrap_or_default();
+        let merged_lp_farm_tokens = self.merge_lp_farm_tokens(
+            caller.clone(),
+            base_lp_farm_token,
+            additional_lp_farm_tokens,
+        );
+        let merged_staking_farm_tokens = self.merge_staking_farm_tokens(staking_farm_tokens);
+
+        self.create_and_send_dual_yield_tokens(
+            &caller,
+            merged_lp_farm_tokens.token_nonce,
+            merged_lp_farm_tokens.amount,
+            merged_staking_farm_tokens.token_nonce,
+            merged_staking_farm_tokens.amount,
+        )
+    }
+}
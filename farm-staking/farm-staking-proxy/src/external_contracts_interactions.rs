@@ -4,16 +4,17 @@ use core::mem::swap;
 
 use farm::{
     base_functions::{ClaimRewardsResultType, ExitFarmResultType},
-    ExitFarmWithPartialPosResultType, ProxyTrait as _,
+    EnterFarmResultType, ExitFarmWithPartialPosResultType, ProxyTrait as _,
 };
 use farm_staking::{
-    claim_stake_farm_rewards::ProxyTrait as _, stake_farm::ProxyTrait as _,
-    unstake_farm::ProxyTrait as _,
+    claim_stake_farm_rewards::ProxyTrait as _, compound_stake_farm_rewards::ProxyTrait as _,
+    stake_farm::ProxyTrait as _, unstake_farm::ProxyTrait as _, ProxyTrait as _,
 };
+use pair::config::ProxyTrait as _;
 use pair::safe_price::ProxyTrait as _;
 
 use crate::result_types::*;
-use pair::RemoveLiquidityResultType;
+use pair::{AddLiquidityResultType, RemoveLiquidityResultType};
 
 pub type SafePriceResult<Api> = MultiValue2<EsdtTokenPayment<Api>, EsdtTokenPayment<Api>>;
 
@@ -23,6 +24,22 @@ pub trait ExternalContractsInteractionsModule:
 {
     // lp farm
 
+    fn lp_farm_enter(
+        &self,
+        lp_tokens: EsdtTokenPayment<Self::Api>,
+    ) -> LpFarmEnterResult<Self::Api> {
+        let orig_caller = self.blockchain().get_caller();
+        let lp_farm_address = self.lp_farm_address().get();
+        let enter_farm_result: EnterFarmResultType<Self::Api> = self
+            .lp_farm_proxy_obj(lp_farm_address)
+            .enter_farm_endpoint(orig_caller)
+            .add_esdt_token_transfer(lp_tokens.token_identifier, 0, lp_tokens.amount)
+            .execute_on_dest_context();
+        let (new_lp_farm_tokens, _) = enter_farm_result.into_tuple();
+
+        LpFarmEnterResult { new_lp_farm_tokens }
+    }
+
     fn lp_farm_claim_rewards(
         &self,
         lp_farm_token_id: TokenIdentifier,
@@ -125,6 +142,17 @@ pub trait ExternalContractsInteractionsModule:
         }
     }
 
+    fn merge_staking_farm_tokens(
+        &self,
+        staking_farm_tokens: PaymentsVec<Self::Api>,
+    ) -> EsdtTokenPayment<Self::Api> {
+        let staking_farm_address = self.staking_farm_address().get();
+        self.staking_farm_proxy_obj(staking_farm_address)
+            .merge_farm_tokens_endpoint()
+            .with_multi_token_transfer(staking_farm_tokens)
+            .execute_on_dest_context()
+    }
+
     fn staking_farm_claim_rewards(
         &self,
         staking_farm_token_id: TokenIdentifier,
@@ -158,6 +186,35 @@ pub trait ExternalContractsInteractionsModule:
         }
     }
 
+    fn staking_farm_compound_rewards(
+        &self,
+        staking_farm_token_nonce: u64,
+        staking_farm_token_amount: BigUint,
+        new_staking_farm_value: BigUint,
+        extra_staking_tokens: EsdtTokenPayment<Self::Api>,
+    ) -> StakingFarmCompoundRewardsResult<Self::Api> {
+        let staking_farm_token_id = self.staking_farm_token_id().get();
+        let mut payments = ManagedVec::from_single_item(EsdtTokenPayment::new(
+            staking_farm_token_id,
+            staking_farm_token_nonce,
+            staking_farm_token_amount,
+        ));
+        if extra_staking_tokens.amount > 0 {
+            payments.push(extra_staking_tokens);
+        }
+
+        let staking_farm_address = self.staking_farm_address().get();
+        let new_staking_farm_tokens: EsdtTokenPayment = self
+            .staking_farm_proxy_obj(staking_farm_address)
+            .compound_rewards_through_proxy(new_staking_farm_value)
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+
+        StakingFarmCompoundRewardsResult {
+            new_staking_farm_tokens,
+        }
+    }
+
     fn staking_farm_unstake(
         &self,
         staking_tokens: EsdtTokenPayment<Self::Api>,
@@ -230,6 +287,66 @@ pub trait ExternalContractsInteractionsModule:
         }
     }
 
+    fn pair_add_liquidity(
+        &self,
+        first_payment: EsdtTokenPayment<Self::Api>,
+        second_payment: EsdtTokenPayment<Self::Api>,
+        pair_first_token_min_amount: BigUint,
+        pair_second_token_min_amount: BigUint,
+    ) -> PairAddLiquidityResult<Self::Api> {
+        let mut payments = ManagedVec::from_single_item(first_payment.clone());
+        payments.push(second_payment.clone());
+
+        let pair_address = self.pair_address().get();
+        let add_liq_result: AddLiquidityResultType<Self::Api> = self
+            .pair_proxy_obj(pair_address)
+            .add_liquidity(pair_first_token_min_amount, pair_second_token_min_amount)
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        let (lp_tokens, first_token_used, second_token_used) = add_liq_result.into_tuple();
+
+        PairAddLiquidityResult {
+            lp_tokens,
+            first_token_leftover: EsdtTokenPayment::new(
+                first_payment.token_identifier,
+                0,
+                first_payment.amount - first_token_used.amount,
+            ),
+            second_token_leftover: EsdtTokenPayment::new(
+                second_payment.token_identifier,
+                0,
+                second_payment.amount - second_token_used.amount,
+            ),
+        }
+    }
+
+    fn pair_swap_fixed_input(
+        &self,
+        payment: EsdtTokenPayment<Self::Api>,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+    ) -> EsdtTokenPayment<Self::Api> {
+        let pair_address = self.pair_address().get();
+        self.pair_proxy_obj(pair_address)
+            .swap_tokens_fixed_input(token_out, amount_out_min)
+            .add_esdt_token_transfer(payment.token_identifier, 0, payment.amount)
+            .execute_on_dest_context()
+    }
+
+    fn get_pair_token_ids(&self) -> (TokenIdentifier, TokenIdentifier) {
+        let pair_address = self.pair_address().get();
+        let first_token_id: TokenIdentifier = self
+            .pair_proxy_obj(pair_address.clone())
+            .first_token_id()
+            .execute_on_dest_context();
+        let second_token_id: TokenIdentifier = self
+            .pair_proxy_obj(pair_address)
+            .second_token_id()
+            .execute_on_dest_context();
+
+        (first_token_id, second_token_id)
+    }
+
     fn get_lp_tokens_safe_price(&self, lp_tokens_amount: BigUint) -> BigUint {
         let pair_address = self.pair_address().get();
         let result: SafePriceResult<Self::Api> = self
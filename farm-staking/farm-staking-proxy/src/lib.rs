@@ -2,18 +2,28 @@
 
 elrond_wasm::imports!();
 
+use core::mem::swap;
+
+use result_types::PaymentsVec;
+
 pub mod dual_yield_token;
+pub mod dual_yield_token_merge;
 pub mod external_contracts_interactions;
 pub mod lp_farm_token;
 pub mod result_types;
 
 pub type StakeResult<Api> = EsdtTokenPayment<Api>;
 pub type ClaimDualYieldResult<Api> = MultiValueEncoded<Api, EsdtTokenPayment<Api>>;
+pub type CompoundDualYieldResult<Api> = EsdtTokenPayment<Api>;
 pub type UnstakeResult<Api> = MultiValueEncoded<Api, EsdtTokenPayment<Api>>;
+pub type ZapInResult<Api> = MultiValueEncoded<Api, EsdtTokenPayment<Api>>;
+
+static INVALID_ZAP_IN_PAYMENTS_ERR_MSG: &[u8] = b"Invalid payments";
 
 #[elrond_wasm::contract]
 pub trait FarmStakingProxy:
     dual_yield_token::DualYieldTokenModule
+    + dual_yield_token_merge::DualYieldTokenMergeModule
     + external_contracts_interactions::ExternalContractsInteractionsModule
     + lp_farm_token::LpFarmTokenModule
     + token_merge_helper::TokenMergeHelperModule
@@ -132,6 +142,125 @@ pub trait FarmStakingProxy:
         )
     }
 
+    /// Enters both farms directly with LP tokens, both pair tokens, or a single pair token.
+    /// For a single pair token, half of the amount is swapped for the other pair token first.
+    /// Returns the dual yield tokens, followed by any tokens left over after adding liquidity.
+    #[payable("*")]
+    #[endpoint(zapIn)]
+    fn zap_in(
+        &self,
+        pair_first_token_min_amount: BigUint,
+        pair_second_token_min_amount: BigUint,
+        opt_swap_min_amount_out: OptionalValue<BigUint>,
+    ) -> ZapInResult<Self::Api> {
+        let payments = self.call_value().all_esdt_transfers();
+        let lp_token_id = self.lp_token_id().get();
+
+        let mut output_payments = ManagedVec::new();
+        let lp_tokens = match payments.try_get(0) {
+            Some(p) if payments.len() == 1 && p.token_identifier == lp_token_id => p,
+            _ => {
+                let (first_payment, second_payment) =
+                    self.get_zap_in_pair_payments(&payments, opt_swap_min_amount_out);
+                let add_liq_result = self.pair_add_liquidity(
+                    first_payment,
+                    second_payment,
+                    pair_first_token_min_amount,
+                    pair_second_token_min_amount,
+                );
+                for leftover in [
+                    add_liq_result.first_token_leftover,
+                    add_liq_result.second_token_leftover,
+                ] {
+                    if leftover.amount > 0 {
+                        output_payments.push(leftover);
+                    }
+                }
+
+                add_liq_result.lp_tokens
+            }
+        };
+
+        let staking_token_amount = self.get_lp_tokens_safe_price(lp_tokens.amount.clone());
+        let new_lp_farm_tokens = self.lp_farm_enter(lp_tokens).new_lp_farm_tokens;
+        let received_staking_farm_token = self
+            .staking_farm_enter(staking_token_amount, ManagedVec::new())
+            .received_staking_farm_token;
+
+        let dual_yield_tokens = self.create_dual_yield_tokens(
+            new_lp_farm_tokens.token_nonce,
+            new_lp_farm_tokens.amount,
+            received_staking_farm_token.token_nonce,
+            received_staking_farm_token.amount,
+        );
+
+        let mut user_payments = ManagedVec::from_single_item(dual_yield_tokens);
+        user_payments.append_vec(output_payments);
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_multi(&caller, &user_payments);
+
+        user_payments.into()
+    }
+
+    /// Returns the payments in the pair's token order
+    fn get_zap_in_pair_payments(
+        &self,
+        payments: &PaymentsVec<Self::Api>,
+        opt_swap_min_amount_out: OptionalValue<BigUint>,
+    ) -> (EsdtTokenPayment<Self::Api>, EsdtTokenPayment<Self::Api>) {
+        let (first_token_id, second_token_id) = self.get_pair_token_ids();
+        match payments.len() {
+            1 => {
+                let payment = payments.get(0);
+                let token_out = if payment.token_identifier == first_token_id {
+                    second_token_id
+                } else if payment.token_identifier == second_token_id {
+                    first_token_id.clone()
+                } else {
+                    sc_panic!(INVALID_ZAP_IN_PAYMENTS_ERR_MSG);
+                };
+                let swap_min_amount_out = opt_swap_min_amount_out
+                    .into_option()
+                    .unwrap_or_else(|| sc_panic!("Swap min amount out required"));
+
+                let swap_amount = &payment.amount / 2u32;
+                let remaining_payment = EsdtTokenPayment::new(
+                    payment.token_identifier.clone(),
+                    0,
+                    &payment.amount - &swap_amount,
+                );
+                let swapped_payment = self.pair_swap_fixed_input(
+                    EsdtTokenPayment::new(payment.token_identifier.clone(), 0, swap_amount),
+                    token_out,
+                    swap_min_amount_out,
+                );
+
+                if payment.token_identifier == first_token_id {
+                    (remaining_payment, swapped_payment)
+                } else {
+                    (swapped_payment, remaining_payment)
+                }
+            }
+            2 => {
+                let mut first_payment = payments.get(0);
+                let mut second_payment = payments.get(1);
+                if first_payment.token_identifier == second_token_id {
+                    swap(&mut first_payment, &mut second_payment);
+                }
+
+                require!(
+                    first_payment.token_identifier == first_token_id
+                        && second_payment.token_identifier == second_token_id,
+                    INVALID_ZAP_IN_PAYMENTS_ERR_MSG
+                );
+
+                (first_payment, second_payment)
+            }
+            _ => sc_panic!(INVALID_ZAP_IN_PAYMENTS_ERR_MSG),
+        }
+    }
+
     #[payable("*")]
     #[endpoint(claimDualYield)]
     fn claim_dual_yield(&self) -> ClaimDualYieldResult<Self::Api> {
@@ -203,6 +332,59 @@ pub trait FarmStakingProxy:
         user_output_payments.into()
     }
 
+    /// Restakes the rewards of both farms into the staking farm position.
+    /// The LP farm rewards can be compounded as well, since they are the same token as the staking farm rewards.
+    #[payable("*")]
+    #[endpoint(compoundDualYield)]
+    fn compound_dual_yield(&self) -> CompoundDualYieldResult<Self::Api> {
+        let (payment_token, payment_nonce, payment_amount) =
+            self.call_value().single_esdt().into_tuple();
+        self.dual_yield_token().require_same_token(&payment_token);
+
+        let attributes = self.get_dual_yield_token_attributes(payment_nonce);
+
+        let staking_farm_token_nonce = attributes.staking_farm_token_nonce;
+        let staking_farm_token_amount =
+            self.get_staking_farm_token_amount_equivalent(&payment_amount);
+
+        let lp_farm_token_id = self.lp_farm_token_id().get();
+        let lp_farm_token_nonce = attributes.lp_farm_token_nonce;
+        let lp_farm_token_amount =
+            self.get_lp_farm_token_amount_equivalent(&attributes, &payment_amount);
+
+        let lp_tokens_in_position =
+            self.get_lp_tokens_in_farm_position(lp_farm_token_nonce, &lp_farm_token_amount);
+        let new_staking_farm_value = self.get_lp_tokens_safe_price(lp_tokens_in_position);
+
+        self.burn_dual_yield_tokens(payment_nonce, &payment_amount);
+
+        let lp_farm_claim_rewards_result =
+            self.lp_farm_claim_rewards(lp_farm_token_id, lp_farm_token_nonce, lp_farm_token_amount);
+        let lp_farm_rewards = lp_farm_claim_rewards_result.lp_farm_rewards;
+        require!(
+            lp_farm_rewards.token_identifier == self.staking_token_id().get(),
+            "LP farm rewards can't be compounded"
+        );
+
+        let staking_farm_compound_result = self.staking_farm_compound_rewards(
+            staking_farm_token_nonce,
+            staking_farm_token_amount,
+            new_staking_farm_value,
+            lp_farm_rewards,
+        );
+
+        let caller = self.blockchain().get_caller();
+        let new_lp_farm_tokens = lp_farm_claim_rewards_result.new_lp_farm_tokens;
+        let new_staking_farm_tokens = staking_farm_compound_result.new_staking_farm_tokens;
+        self.create_and_send_dual_yield_tokens(
+            &caller,
+            new_lp_farm_tokens.token_nonce,
+            new_lp_farm_tokens.amount,
+            new_staking_farm_tokens.token_nonce,
+            new_staking_farm_tokens.amount,
+        )
+    }
+
     #[payable("*")]
     #[endpoint(unstakeFarmTokens)]
     fn unstake_farm_tokens(
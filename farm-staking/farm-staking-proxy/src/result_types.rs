@@ -9,6 +9,10 @@ pub struct LpFarmClaimRewardsResult<M: ManagedTypeApi> {
     pub lp_farm_rewards: EsdtTokenPayment<M>,
 }
 
+pub struct LpFarmEnterResult<M: ManagedTypeApi> {
+    pub new_lp_farm_tokens: EsdtTokenPayment<M>,
+}
+
 pub struct LpFarmExitResult<M: ManagedTypeApi> {
     pub lp_tokens: EsdtTokenPayment<M>,
     pub lp_farm_rewards: EsdtTokenPayment<M>,
@@ -26,6 +30,10 @@ pub struct StakingFarmClaimRewardsResult<M: ManagedTypeApi> {
     pub staking_farm_rewards: EsdtTokenPayment<M>,
 }
 
+pub struct StakingFarmCompoundRewardsResult<M: ManagedTypeApi> {
+    pub new_staking_farm_tokens: EsdtTokenPayment<M>,
+}
+
 pub struct StakingFarmExitResult<M: ManagedTypeApi> {
     pub unbond_staking_farm_token: EsdtTokenPayment<M>,
     pub staking_rewards: EsdtTokenPayment<M>,
@@ -33,6 +41,12 @@ pub struct StakingFarmExitResult<M: ManagedTypeApi> {
 
 // pair
 
+pub struct PairAddLiquidityResult<M: ManagedTypeApi> {
+    pub lp_tokens: EsdtTokenPayment<M>,
+    pub first_token_leftover: EsdtTokenPayment<M>,
+    pub second_token_leftover: EsdtTokenPayment<M>,
+}
+
 pub struct PairRemoveLiquidityResult<M: ManagedTypeApi> {
     pub staking_token_payment: EsdtTokenPayment<M>,
     pub other_token_payment: EsdtTokenPayment<M>,
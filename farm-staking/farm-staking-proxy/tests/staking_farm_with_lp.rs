@@ -7,7 +7,9 @@ elrond_wasm::imports!();
 
 use constants::*;
 use elrond_wasm::elrond_codec::Empty;
-use elrond_wasm_debug::{managed_biguint, managed_token_id, rust_biguint, DebugApi};
+use elrond_wasm_debug::{
+    managed_biguint, managed_token_id, rust_biguint, tx_mock::TxInputESDT, DebugApi,
+};
 use farm_staking_proxy::dual_yield_token::DualYieldTokenAttributes;
 use farm_staking_proxy::FarmStakingProxy;
 use staking_farm_with_lp_staking_contract_interactions::*;
@@ -408,6 +410,67 @@ fn unbond_test() {
     setup.unbond_proxy(unbond_token_nonce, unbond_amount, unbond_amount);
 }
 
+#[test]
+fn compound_dual_yield_and_unbond_test() {
+    let mut setup = FarmStakingSetup::new(
+        pair::contract_obj,
+        farm::contract_obj,
+        farm_staking::contract_obj,
+        farm_staking_proxy::contract_obj,
+    );
+
+    let expected_staking_token_amount = 1_001_000_000;
+    let dual_yield_token_nonce_after_stake =
+        setup.stake_farm_lp_proxy(1, USER_TOTAL_LP_TOKENS, 1, expected_staking_token_amount);
+
+    setup
+        .b_mock
+        .set_block_nonce(BLOCK_NONCE_AFTER_PAIR_SETUP + 20);
+    setup.b_mock.set_block_epoch(20);
+
+    // same rewards as in the claim tests, 99_999 from the LP farm and 1_899 from the staking farm
+    let compounded_rewards = 99_999 + 1_899;
+    let dual_yield_token_amount = expected_staking_token_amount;
+    let dual_yield_token_nonce_after_compound = setup.compound_rewards_proxy(
+        dual_yield_token_nonce_after_stake,
+        dual_yield_token_amount,
+        dual_yield_token_amount + compounded_rewards,
+    );
+
+    setup.b_mock.execute_in_managed_environment(|| {
+        let expected_dual_yield_attributes = DualYieldTokenAttributes::<DebugApi> {
+            lp_farm_token_nonce: 2,
+            lp_farm_token_amount: managed_biguint!(USER_TOTAL_LP_TOKENS),
+            staking_farm_token_nonce: 2,
+            staking_farm_token_amount: managed_biguint!(
+                dual_yield_token_amount + compounded_rewards
+            ),
+        };
+        setup.b_mock.check_nft_balance(
+            &setup.user_addr,
+            DUAL_YIELD_TOKEN_ID,
+            dual_yield_token_nonce_after_compound,
+            &rust_biguint!(dual_yield_token_amount + compounded_rewards),
+            Some(&expected_dual_yield_attributes),
+        );
+    });
+
+    // the compounded rewards are unbonded along with the LP position's value
+    let unbond_amount = 1_001_000_000 + compounded_rewards;
+    let unbond_token_nonce = setup.unstake_proxy(
+        dual_yield_token_nonce_after_compound,
+        dual_yield_token_amount + compounded_rewards,
+        1_001_000_000,
+        0,
+        0,
+        unbond_amount,
+        30,
+    );
+
+    setup.b_mock.set_block_epoch(30);
+    setup.unbond_proxy(unbond_token_nonce, unbond_amount, unbond_amount);
+}
+
 #[test]
 fn farm_staking_compound_rewards_and_unstake_test() {
     let mut setup = FarmStakingSetup::new(
@@ -512,3 +575,192 @@ fn test_stake_farm_through_proxy_with_merging() {
         )
     });
 }
+
+#[test]
+fn test_zap_in_with_both_pair_tokens() {
+    let mut setup = FarmStakingSetup::new(
+        pair::contract_obj,
+        farm::contract_obj,
+        farm_staking::contract_obj,
+        farm_staking_proxy::contract_obj,
+    );
+
+    // payments in reverse pair order are accepted
+    let transfers = [
+        TxInputESDT {
+            token_identifier: RIDE_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(500_500_000),
+        },
+        TxInputESDT {
+            token_identifier: WEGLD_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(500_500_000),
+        },
+    ];
+    let dual_yield_tokens = setup.zap_in_proxy(&transfers, None, 1);
+
+    // same ratio as the pair reserves, so there are no leftover tokens
+    setup.b_mock.check_esdt_balance(
+        &setup.user_addr,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - USER_TOTAL_LP_TOKENS - 500_500_000),
+    );
+    setup.b_mock.check_esdt_balance(
+        &setup.user_addr,
+        RIDE_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_RIDE_TOKENS - USER_TOTAL_LP_TOKENS - 500_500_000),
+    );
+
+    let expected_lp_tokens = 500_499_750;
+    setup.b_mock.execute_in_managed_environment(|| {
+        setup.b_mock.check_nft_balance(
+            &setup.user_addr,
+            DUAL_YIELD_TOKEN_ID,
+            dual_yield_tokens.nonce,
+            &rust_biguint!(dual_yield_tokens.amount),
+            Some(&DualYieldTokenAttributes::<DebugApi> {
+                lp_farm_token_nonce: 2,
+                lp_farm_token_amount: managed_biguint!(expected_lp_tokens),
+                staking_farm_token_nonce: 1,
+                staking_farm_token_amount: managed_biguint!(dual_yield_tokens.amount),
+            }),
+        )
+    });
+
+    // check proxy SC tokens
+    setup.b_mock.execute_in_managed_environment(|| {
+        setup.b_mock.check_nft_balance::<Empty>(
+            setup.proxy_wrapper.address_ref(),
+            LP_FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(expected_lp_tokens),
+            None,
+        )
+    });
+}
+
+#[test]
+fn test_zap_in_invalid_payments() {
+    let mut setup = FarmStakingSetup::new(
+        pair::contract_obj,
+        farm::contract_obj,
+        farm_staking::contract_obj,
+        farm_staking_proxy::contract_obj,
+    );
+
+    // single pair token without swap min amount out
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_addr,
+            &setup.proxy_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000_000),
+            |sc| {
+                let _ = sc.zap_in(
+                    managed_biguint!(1),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
+            },
+        )
+        .assert_user_error("Swap min amount out required");
+
+    // same pair token twice
+    let transfers = [
+        TxInputESDT {
+            token_identifier: WEGLD_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(100_000_000),
+        },
+        TxInputESDT {
+            token_identifier: WEGLD_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(100_000_000),
+        },
+    ];
+    setup
+        .b_mock
+        .execute_esdt_multi_transfer(&setup.user_addr, &setup.proxy_wrapper, &transfers, |sc| {
+            let _ = sc.zap_in(
+                managed_biguint!(1),
+                managed_biguint!(1),
+                OptionalValue::None,
+            );
+        })
+        .assert_user_error("Invalid payments");
+}
+
+#[test]
+fn test_merge_dual_yield_tokens() {
+    let mut setup = FarmStakingSetup::new(
+        pair::contract_obj,
+        farm::contract_obj,
+        farm_staking::contract_obj,
+        farm_staking_proxy::contract_obj,
+    );
+
+    let first_dual_yield_token_nonce = setup.stake_farm_lp_proxy(1, 400_000_000, 1, 400_000_000);
+    let second_dual_yield_token_nonce = setup.stake_farm_lp_proxy(1, 600_000_000, 2, 600_000_000);
+
+    let dual_yield_token_payments = vec![
+        NonceAmountPair {
+            nonce: first_dual_yield_token_nonce,
+            amount: 400_000_000,
+        },
+        NonceAmountPair {
+            nonce: second_dual_yield_token_nonce,
+            amount: 600_000_000,
+        },
+    ];
+    let merged_dual_yield_token_nonce =
+        setup.merge_dual_yield_tokens_proxy(dual_yield_token_payments, 1_000_000_000);
+
+    // check user dual yield tokens
+    setup.b_mock.check_nft_balance::<Empty>(
+        &setup.user_addr,
+        DUAL_YIELD_TOKEN_ID,
+        first_dual_yield_token_nonce,
+        &rust_biguint!(0),
+        None,
+    );
+    setup.b_mock.check_nft_balance::<Empty>(
+        &setup.user_addr,
+        DUAL_YIELD_TOKEN_ID,
+        second_dual_yield_token_nonce,
+        &rust_biguint!(0),
+        None,
+    );
+    setup.b_mock.execute_in_managed_environment(|| {
+        setup.b_mock.check_nft_balance(
+            &setup.user_addr,
+            DUAL_YIELD_TOKEN_ID,
+            merged_dual_yield_token_nonce,
+            &rust_biguint!(1_000_000_000),
+            Some(&DualYieldTokenAttributes::<DebugApi> {
+                lp_farm_token_nonce: 2,
+                lp_farm_token_amount: managed_biguint!(1_000_000_000),
+                staking_farm_token_nonce: 3,
+                staking_farm_token_amount: managed_biguint!(1_000_000_000),
+            }),
+        )
+    });
+
+    // check proxy SC tokens
+    setup.b_mock.check_nft_balance::<Empty>(
+        setup.proxy_wrapper.address_ref(),
+        LP_FARM_TOKEN_ID,
+        2,
+        &rust_biguint!(1_000_000_000),
+        None,
+    );
+    setup.b_mock.check_nft_balance::<Empty>(
+        setup.proxy_wrapper.address_ref(),
+        STAKING_FARM_TOKEN_ID,
+        3,
+        &rust_biguint!(1_000_000_000),
+        None,
+    );
+}
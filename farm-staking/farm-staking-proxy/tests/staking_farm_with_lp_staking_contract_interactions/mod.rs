@@ -1,6 +1,6 @@
 use elrond_wasm::types::Address;
 use elrond_wasm_debug::{
-    managed_address, managed_biguint, rust_biguint,
+    managed_address, managed_biguint, managed_token_id, rust_biguint,
     testing_framework::{BlockchainStateWrapper, ContractObjWrapper},
     tx_mock::TxInputESDT,
     DebugApi,
@@ -12,6 +12,7 @@ use farm_staking::{
     unstake_farm::UnstakeFarmModule,
 };
 use farm_staking_proxy::dual_yield_token::DualYieldTokenAttributes;
+use farm_staking_proxy::dual_yield_token_merge::DualYieldTokenMergeModule;
 use farm_staking_proxy::*;
 use sc_whitelist_module::SCWhitelistModule;
 
@@ -198,6 +199,37 @@ where
         dual_yield_nonce
     }
 
+    pub fn merge_dual_yield_tokens_proxy(
+        &mut self,
+        dual_yield_tokens: Vec<NonceAmountPair>,
+        expected_dual_yield_token_amount: u64,
+    ) -> u64 {
+        let mut dual_yield_nonce = 0;
+
+        let transfers: Vec<TxInputESDT> = dual_yield_tokens
+            .iter()
+            .map(|pair| TxInputESDT {
+                token_identifier: DUAL_YIELD_TOKEN_ID.to_vec(),
+                nonce: pair.nonce,
+                value: rust_biguint!(pair.amount),
+            })
+            .collect();
+
+        self.b_mock
+            .execute_esdt_multi_transfer(&self.user_addr, &self.proxy_wrapper, &transfers, |sc| {
+                let merged_dual_yield_tokens = sc.merge_dual_yield_tokens();
+                dual_yield_nonce = merged_dual_yield_tokens.token_nonce;
+
+                assert_eq!(
+                    merged_dual_yield_tokens.amount,
+                    managed_biguint!(expected_dual_yield_token_amount)
+                );
+            })
+            .assert_ok();
+
+        dual_yield_nonce
+    }
+
     pub fn claim_rewards_proxy(
         &mut self,
         dual_yield_token_nonce: u64,
@@ -239,6 +271,75 @@ where
         dual_yield_nonce
     }
 
+    pub fn compound_rewards_proxy(
+        &mut self,
+        dual_yield_token_nonce: u64,
+        dual_yield_token_amount: u64,
+        expected_new_dual_yield_token_amount: u64,
+    ) -> u64 {
+        let mut dual_yield_nonce = 0;
+
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_addr,
+                &self.proxy_wrapper,
+                DUAL_YIELD_TOKEN_ID,
+                dual_yield_token_nonce,
+                &rust_biguint!(dual_yield_token_amount),
+                |sc| {
+                    let new_dual_yield_tokens = sc.compound_dual_yield();
+                    dual_yield_nonce = new_dual_yield_tokens.token_nonce;
+
+                    assert_eq!(
+                        new_dual_yield_tokens.amount,
+                        managed_biguint!(expected_new_dual_yield_token_amount)
+                    );
+                },
+            )
+            .assert_ok();
+
+        dual_yield_nonce
+    }
+
+    pub fn zap_in_proxy(
+        &mut self,
+        transfers: &[TxInputESDT],
+        opt_swap_min_amount_out: Option<u64>,
+        expected_output_payments_len: usize,
+    ) -> NonceAmountPair {
+        let mut dual_yield_tokens = NonceAmountPair {
+            nonce: 0,
+            amount: 0,
+        };
+
+        self.b_mock
+            .execute_esdt_multi_transfer(&self.user_addr, &self.proxy_wrapper, transfers, |sc| {
+                let output_payments = sc
+                    .zap_in(
+                        managed_biguint!(1),
+                        managed_biguint!(1),
+                        opt_swap_min_amount_out
+                            .map(|amount| managed_biguint!(amount))
+                            .into(),
+                    )
+                    .to_vec();
+                assert_eq!(output_payments.len(), expected_output_payments_len);
+
+                let new_dual_yield_tokens = output_payments.get(0);
+                assert_eq!(
+                    new_dual_yield_tokens.token_identifier,
+                    managed_token_id!(DUAL_YIELD_TOKEN_ID)
+                );
+                dual_yield_tokens = NonceAmountPair {
+                    nonce: new_dual_yield_tokens.token_nonce,
+                    amount: new_dual_yield_tokens.amount.to_u64().unwrap(),
+                };
+            })
+            .assert_ok();
+
+        dual_yield_tokens
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn unstake_proxy(
         &mut self,
@@ -301,6 +402,7 @@ where
         self.b_mock.execute_in_managed_environment(|| {
             let expected_attributes = UnbondSftAttributes {
                 unlock_epoch: expected_unbond_token_unlock_epoch,
+                unbond_epochs: UNBOND_EPOCHS,
             };
 
             self.b_mock.check_nft_balance(
@@ -9,6 +9,7 @@ elrond_wasm_node::wasm_endpoints! {
     (
         callBack
         claimDualYield
+        compoundDualYield
         getDualYieldTokenId
         getFarmTokenId
         getLpFarmAddress
@@ -17,8 +18,10 @@ elrond_wasm_node::wasm_endpoints! {
         getPairAddress
         getStakingFarmAddress
         getStakingTokenId
+        mergeDualYieldTokens
         registerDualYieldToken
         stakeFarmTokens
         unstakeFarmTokens
+        zapIn
     )
 }
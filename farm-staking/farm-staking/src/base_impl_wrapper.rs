@@ -2,9 +2,12 @@ elrond_wasm::imports!();
 
 use core::marker::PhantomData;
 
+use common_structs::Epoch;
 use contexts::storage_cache::StorageCache;
 use farm_base_impl::base_traits_impl::FarmContract;
 
+use crate::custom_rewards::MAX_PERCENT;
+use crate::staking_tiers::NO_LOCK_REWARD_MULTIPLIER;
 use crate::token_attributes::StakingFarmTokenAttributes;
 
 pub trait FarmStakingTraits =
@@ -23,6 +26,27 @@ where
     _phantom: PhantomData<T>,
 }
 
+impl<T> FarmStakingWrapper<T>
+where
+    T: FarmStakingTraits,
+{
+    /// Positions with an expired lock go back to the default tier on their next claim or compound
+    fn get_remaining_lock(
+        sc: &T,
+        token_attributes: &StakingFarmTokenAttributes<<T as ContractBase>::Api>,
+    ) -> (Epoch, u64) {
+        let current_epoch = sc.blockchain().get_block_epoch();
+        if current_epoch >= token_attributes.unlock_epoch {
+            (0, NO_LOCK_REWARD_MULTIPLIER)
+        } else {
+            (
+                token_attributes.unlock_epoch,
+                token_attributes.reward_multiplier,
+            )
+        }
+    }
+}
+
 impl<T> FarmContract for FarmStakingWrapper<T>
 where
     T: FarmStakingTraits,
@@ -62,6 +86,30 @@ where
         core::cmp::min(extra_rewards_unbounded, extra_rewards_apr_bounded)
     }
 
+    fn mint_per_second_rewards(
+        sc: &Self::FarmSc,
+        _token_id: &TokenIdentifier<<Self::FarmSc as ContractBase>::Api>,
+    ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
+        let current_timestamp = sc.blockchain().get_block_timestamp();
+        let last_reward_timestamp = sc.last_reward_timestamp().get();
+
+        if current_timestamp <= last_reward_timestamp {
+            return BigUint::zero();
+        }
+
+        let extra_rewards_unbounded =
+            Self::calculate_per_second_rewards(sc, current_timestamp, last_reward_timestamp);
+
+        let farm_token_supply = sc.farm_token_supply().get();
+        let timestamp_diff = current_timestamp - last_reward_timestamp;
+        let extra_rewards_apr_bounded =
+            sc.get_amount_apr_bounded_for_seconds(&farm_token_supply, timestamp_diff);
+
+        sc.last_reward_timestamp().set(current_timestamp);
+
+        core::cmp::min(extra_rewards_unbounded, extra_rewards_apr_bounded)
+    }
+
     fn generate_aggregated_rewards(
         sc: &Self::FarmSc,
         storage_cache: &mut StorageCache<Self::FarmSc>,
@@ -71,7 +119,7 @@ where
         let reward_capacity = sc.reward_capacity().get();
         let remaining_rewards = &reward_capacity - &accumulated_rewards;
 
-        let mut total_reward = Self::mint_per_block_rewards(sc, &storage_cache.reward_token_id);
+        let mut total_reward = Self::mint_accrued_rewards(sc, &storage_cache.reward_token_id);
         total_reward = core::cmp::min(total_reward, remaining_rewards);
         if total_reward == 0 {
             return;
@@ -88,6 +136,48 @@ where
         }
     }
 
+    /// The bonus of locked positions is taken from the remaining reward capacity.
+    /// No bonus is given once the lock expired, so rewards generated after the unlock epoch
+    /// are not boosted. Rewards still unclaimed at the unlock epoch lose their bonus as well.
+    fn calculate_rewards(
+        sc: &Self::FarmSc,
+        _caller: &ManagedAddress<<Self::FarmSc as ContractBase>::Api>,
+        farm_token_amount: &BigUint<<Self::FarmSc as ContractBase>::Api>,
+        token_attributes: &Self::AttributesType,
+        storage_cache: &mut StorageCache<Self::FarmSc>,
+    ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
+        if storage_cache.reward_per_share <= token_attributes.reward_per_share {
+            return BigUint::zero();
+        }
+
+        let rps_diff = &storage_cache.reward_per_share - &token_attributes.reward_per_share;
+        let base_reward = farm_token_amount * &rps_diff / &storage_cache.division_safety_constant;
+        let current_epoch = sc.blockchain().get_block_epoch();
+        if token_attributes.reward_multiplier <= NO_LOCK_REWARD_MULTIPLIER
+            || current_epoch >= token_attributes.unlock_epoch
+        {
+            return base_reward;
+        }
+
+        let accumulated_rewards_mapper = sc.accumulated_rewards();
+        let mut accumulated_rewards = accumulated_rewards_mapper.get();
+        let reward_capacity = sc.reward_capacity().get();
+        let remaining_rewards = &reward_capacity - &accumulated_rewards;
+
+        let bonus_multiplier = token_attributes.reward_multiplier - NO_LOCK_REWARD_MULTIPLIER;
+        let bonus_reward_unbounded = &base_reward * bonus_multiplier / MAX_PERCENT;
+        let bonus_reward = core::cmp::min(bonus_reward_unbounded, remaining_rewards);
+        if bonus_reward == 0 {
+            return base_reward;
+        }
+
+        storage_cache.reward_reserve += &bonus_reward;
+        accumulated_rewards += &bonus_reward;
+        accumulated_rewards_mapper.set(&accumulated_rewards);
+
+        base_reward + bonus_reward
+    }
+
     fn create_enter_farm_initial_attributes(
         _sc: &Self::FarmSc,
         _caller: ManagedAddress<<Self::FarmSc as ContractBase>::Api>,
@@ -98,24 +188,30 @@ where
             reward_per_share: current_reward_per_share,
             compounded_reward: BigUint::zero(),
             current_farm_amount: farming_token_amount,
+            unlock_epoch: 0,
+            reward_multiplier: NO_LOCK_REWARD_MULTIPLIER,
         }
     }
 
     fn create_claim_rewards_initial_attributes(
-        _sc: &Self::FarmSc,
+        sc: &Self::FarmSc,
         _caller: ManagedAddress<<Self::FarmSc as ContractBase>::Api>,
         first_token_attributes: Self::AttributesType,
         current_reward_per_share: BigUint<<Self::FarmSc as ContractBase>::Api>,
     ) -> Self::AttributesType {
+        let (unlock_epoch, reward_multiplier) =
+            Self::get_remaining_lock(sc, &first_token_attributes);
         StakingFarmTokenAttributes {
             reward_per_share: current_reward_per_share,
             compounded_reward: first_token_attributes.compounded_reward,
             current_farm_amount: first_token_attributes.current_farm_amount,
+            unlock_epoch,
+            reward_multiplier,
         }
     }
 
     fn create_compound_rewards_initial_attributes(
-        _sc: &Self::FarmSc,
+        sc: &Self::FarmSc,
         _caller: ManagedAddress<<Self::FarmSc as ContractBase>::Api>,
         first_token_attributes: Self::AttributesType,
         current_reward_per_share: BigUint<<Self::FarmSc as ContractBase>::Api>,
@@ -123,10 +219,14 @@ where
     ) -> Self::AttributesType {
         let new_pos_compounded_reward = first_token_attributes.compounded_reward + reward;
         let new_pos_current_farm_amount = first_token_attributes.current_farm_amount + reward;
+        let (unlock_epoch, reward_multiplier) =
+            Self::get_remaining_lock(sc, &first_token_attributes);
         StakingFarmTokenAttributes {
             reward_per_share: current_reward_per_share,
             compounded_reward: new_pos_compounded_reward,
             current_farm_amount: new_pos_current_farm_amount,
+            unlock_epoch,
+            reward_multiplier,
         }
     }
 }
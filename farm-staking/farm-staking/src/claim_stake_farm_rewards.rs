@@ -53,11 +53,13 @@ pub trait ClaimStakeFarmRewardsModule:
 
         let mut virtual_farm_token = claim_result.new_farm_token.clone();
         if let Some(new_amount) = opt_new_farming_amount {
+            // the compounded rewards are held by the staking farm, so their value does not change
+            let new_farm_amount = new_amount + &virtual_farm_token.attributes.compounded_reward;
             claim_result.storage_cache.farm_token_supply -= &virtual_farm_token.payment.amount;
-            claim_result.storage_cache.farm_token_supply += &new_amount;
+            claim_result.storage_cache.farm_token_supply += &new_farm_amount;
 
-            virtual_farm_token.payment.amount = new_amount.clone();
-            virtual_farm_token.attributes.current_farm_amount = new_amount;
+            virtual_farm_token.payment.amount = new_farm_amount.clone();
+            virtual_farm_token.attributes.current_farm_amount = new_farm_amount;
         }
 
         let new_farm_token_nonce = self.send().esdt_nft_create_compact(
@@ -16,6 +16,7 @@ pub trait CompoundStakeFarmRewardsModule:
     + elrond_wasm_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
+    + farm_base_impl::claim_rewards::BaseClaimRewardsModule
     + farm_base_impl::compound_rewards::BaseCompoundRewardsModule
     + utils::UtilsModule
 {
@@ -41,4 +42,66 @@ pub trait CompoundStakeFarmRewardsModule:
 
         new_farm_token
     }
+
+    /// The first payment is the proxy's staking farm position, and the optional second payment
+    /// consists of staking tokens, which are compounded along with the position's rewards.
+    /// As for claimRewardsWithNewValue, the position's value is updated to new_farming_amount,
+    /// while the compounded rewards are held by the staking farm.
+    #[payable("*")]
+    #[endpoint(compoundRewardsThroughProxy)]
+    fn compound_rewards_through_proxy(&self, new_farming_amount: BigUint) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        self.require_sc_address_whitelisted(&caller);
+
+        let mut payments = self.get_non_empty_payments();
+        let farm_token_payment = self.pop_first_payment(&mut payments);
+        let mut extra_compounded_amount = BigUint::zero();
+        if !payments.is_empty() {
+            require!(payments.len() == 1, "Invalid payments");
+
+            let staking_tokens = payments.get(0);
+            require!(
+                staking_tokens.token_identifier == self.farming_token_id().get(),
+                "Invalid staking token received"
+            );
+            extra_compounded_amount = staking_tokens.amount;
+        }
+
+        let mut compound_result = self
+            .claim_rewards_base_no_farm_token_mint::<FarmStakingWrapper<Self>>(
+                caller.clone(),
+                ManagedVec::from_single_item(farm_token_payment),
+            );
+
+        let compounded_rewards = &compound_result.rewards.amount + &extra_compounded_amount;
+        let mut new_farm_token = compound_result.new_farm_token.clone();
+        new_farm_token.attributes.compounded_reward += &compounded_rewards;
+
+        let new_farm_amount = new_farming_amount + &new_farm_token.attributes.compounded_reward;
+        compound_result.storage_cache.farm_token_supply -= &new_farm_token.payment.amount;
+        compound_result.storage_cache.farm_token_supply += &new_farm_amount;
+
+        new_farm_token.payment.amount = new_farm_amount.clone();
+        new_farm_token.attributes.current_farm_amount = new_farm_amount;
+
+        let new_farm_token_nonce = self.send().esdt_nft_create_compact(
+            &new_farm_token.payment.token_identifier,
+            &new_farm_token.payment.amount,
+            &new_farm_token.attributes,
+        );
+        new_farm_token.payment.token_nonce = new_farm_token_nonce;
+
+        self.send_payment_non_zero(&caller, &new_farm_token.payment);
+
+        self.emit_compound_rewards_event(
+            &caller,
+            compound_result.context,
+            new_farm_token.clone(),
+            compounded_rewards,
+            compound_result.created_with_merge,
+            compound_result.storage_cache,
+        );
+
+        new_farm_token.payment
+    }
 }
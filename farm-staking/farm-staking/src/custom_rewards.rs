@@ -8,7 +8,8 @@ use farm_base_impl::base_traits_impl::FarmContract;
 use crate::base_impl_wrapper::FarmStakingWrapper;
 
 pub const MAX_PERCENT: u64 = 10_000;
-pub const BLOCKS_IN_YEAR: u64 = 31_536_000 / 6; // seconds_in_year / 6_seconds_per_block
+pub const SECONDS_IN_YEAR: u64 = 31_536_000;
+pub const BLOCKS_IN_YEAR: u64 = SECONDS_IN_YEAR / 6; // seconds_in_year / 6_seconds_per_block
 const MAX_MIN_UNBOND_EPOCHS: u64 = 30;
 
 #[elrond_wasm::module]
@@ -52,6 +53,38 @@ pub trait CustomRewardsModule:
         self.per_block_reward_amount().set(&per_block_amount);
     }
 
+    #[endpoint(setPerSecondRewardAmount)]
+    fn set_per_second_rewards(&self, per_second_amount: BigUint) {
+        self.require_caller_has_admin_permissions();
+        require!(per_second_amount != 0, "Amount cannot be zero");
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+        self.per_second_reward_amount().set(&per_second_amount);
+    }
+
+    /// Settles the rewards accrued per block so far,
+    /// after which rewards and the APR cap are computed on the elapsed block timestamps
+    #[endpoint(switchToTimestampBasedRewards)]
+    fn switch_to_timestamp_based_rewards(&self, per_second_amount: BigUint) {
+        self.require_caller_has_admin_permissions();
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+        self.enable_timestamp_based_rewards(&per_second_amount);
+    }
+
+    /// Settles the rewards accrued per second so far,
+    /// after which rewards are accrued per block again, using the per block reward amount
+    #[endpoint(switchToBlockBasedRewards)]
+    fn switch_to_block_based_rewards(&self) {
+        self.require_caller_has_admin_permissions();
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+        self.disable_timestamp_based_rewards();
+    }
+
     #[endpoint(setMaxApr)]
     fn set_max_apr(&self, max_apr: BigUint) {
         self.require_caller_has_admin_permissions();
@@ -82,6 +115,11 @@ pub trait CustomRewardsModule:
         amount * &max_apr / MAX_PERCENT / BLOCKS_IN_YEAR
     }
 
+    fn get_amount_apr_bounded_for_seconds(&self, amount: &BigUint, seconds: u64) -> BigUint {
+        let max_apr = self.max_annual_percentage_rewards().get();
+        amount * &max_apr * seconds / MAX_PERCENT / SECONDS_IN_YEAR
+    }
+
     #[endpoint(startProduceRewards)]
     fn start_produce_rewards_endpoint(&self) {
         self.require_caller_has_admin_permissions();
@@ -18,6 +18,7 @@ pub mod claim_stake_farm_rewards;
 pub mod compound_stake_farm_rewards;
 pub mod custom_rewards;
 pub mod stake_farm;
+pub mod staking_tiers;
 pub mod token_attributes;
 pub mod unbond_farm;
 pub mod unstake_farm;
@@ -40,7 +41,9 @@ pub trait FarmStaking:
     + farm_base_impl::claim_rewards::BaseClaimRewardsModule
     + farm_base_impl::compound_rewards::BaseCompoundRewardsModule
     + farm_base_impl::exit_farm::BaseExitFarmModule
+    + farm_base_impl::emergency_exit::BaseEmergencyExitModule
     + utils::UtilsModule
+    + staking_tiers::StakingTiersModule
     + stake_farm::StakeFarmModule
     + claim_stake_farm_rewards::ClaimStakeFarmRewardsModule
     + compound_stake_farm_rewards::CompoundStakeFarmRewardsModule
@@ -104,7 +107,7 @@ pub trait FarmStaking:
             &ManagedAddress::zero(),
             &farm_token_amount,
             &attributes,
-            &storage_cache,
+            &mut storage_cache,
         )
     }
 
@@ -1,8 +1,10 @@
 elrond_wasm::imports!();
 
-use common_structs::PaymentsVec;
+use common_structs::{Epoch, PaymentsVec};
+use contexts::{enter_farm_context::EnterFarmContext, storage_cache::StorageCache};
+use farm_base_impl::base_traits_impl::FarmContract;
 
-use crate::base_impl_wrapper::FarmStakingWrapper;
+use crate::{base_impl_wrapper::FarmStakingWrapper, token_attributes::StakingFarmTokenAttributes};
 
 #[elrond_wasm::module]
 pub trait StakeFarmModule:
@@ -20,6 +22,7 @@ pub trait StakeFarmModule:
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
     + utils::UtilsModule
+    + crate::staking_tiers::StakingTiersModule
 {
     #[payable("*")]
     #[endpoint(stakeFarmThroughProxy)]
@@ -46,6 +49,59 @@ pub trait StakeFarmModule:
         self.stake_farm_common(payments)
     }
 
+    /// Stakes in the tier with the given lock period. The position may not be unstaked until the lock expires.
+    /// Additional farm tokens must have been staked in the same tier, and the merged position keeps the latest unlock epoch.
+    #[payable("*")]
+    #[endpoint(stakeFarmLocked)]
+    fn stake_farm_locked(&self, lock_epochs: Epoch) -> EsdtTokenPayment {
+        let reward_multiplier = self.get_tier_reward_multiplier(lock_epochs);
+        let payments = self.get_non_empty_payments();
+        let caller = self.blockchain().get_caller();
+
+        let mut storage_cache = StorageCache::new(self);
+        self.validate_contract_state(storage_cache.contract_state, &storage_cache.farm_token_id);
+
+        let enter_farm_context = EnterFarmContext::new(
+            payments,
+            &storage_cache.farming_token_id,
+            &storage_cache.farm_token_id,
+        );
+
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        storage_cache.farm_token_supply += &enter_farm_context.farming_token_payment.amount;
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let base_attributes = StakingFarmTokenAttributes {
+            reward_per_share: storage_cache.reward_per_share.clone(),
+            compounded_reward: BigUint::zero(),
+            current_farm_amount: enter_farm_context.farming_token_payment.amount.clone(),
+            unlock_epoch: current_epoch + lock_epochs,
+            reward_multiplier,
+        };
+        let farm_token_mapper = self.farm_token();
+        let new_farm_token = self.merge_and_create_token(
+            base_attributes,
+            &enter_farm_context.additional_farm_tokens,
+            &farm_token_mapper,
+        );
+
+        self.burn_multi_esdt(&enter_farm_context.additional_farm_tokens);
+
+        let output_payment = new_farm_token.payment.clone();
+        self.send_payment_non_zero(&caller, &output_payment);
+
+        self.emit_enter_farm_event(
+            &caller,
+            enter_farm_context.farming_token_payment,
+            new_farm_token,
+            !enter_farm_context.additional_farm_tokens.is_empty(),
+            storage_cache,
+        );
+
+        output_payment
+    }
+
     fn stake_farm_common(&self, payments: PaymentsVec<Self::Api>) -> EsdtTokenPayment {
         let caller = self.blockchain().get_caller();
         let enter_result =
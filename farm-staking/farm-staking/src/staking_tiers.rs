@@ -0,0 +1,91 @@
+elrond_wasm::imports!();
+
+use common_structs::Epoch;
+
+use crate::custom_rewards::MAX_PERCENT;
+
+pub const NO_LOCK_REWARD_MULTIPLIER: u64 = MAX_PERCENT;
+pub const MAX_REWARD_MULTIPLIER: u64 = 5 * MAX_PERCENT;
+pub const MAX_TIER_LOCK_EPOCHS: Epoch = 1_440;
+
+static UNKNOWN_STAKING_TIER_ERR_MSG: &[u8] = b"Unknown staking tier";
+
+#[elrond_wasm::module]
+pub trait StakingTiersModule:
+    crate::custom_rewards::CustomRewardsModule
+    + rewards::RewardsModule
+    + config::ConfigModule
+    + token_send::TokenSendModule
+    + farm_token::FarmTokenModule
+    + pausable::PausableModule
+    + permissions_module::PermissionsModule
+    + elrond_wasm_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    /// Positions staked in a tier are locked for lock_epochs,
+    /// and their rewards are scaled by reward_multiplier (where 10_000 = 1x).
+    /// Updating or removing a tier does not affect existing positions.
+    #[endpoint(setStakingTier)]
+    fn set_staking_tier(&self, lock_epochs: Epoch, reward_multiplier: u64) {
+        self.require_caller_has_admin_permissions();
+        require!(
+            lock_epochs > 0 && lock_epochs <= MAX_TIER_LOCK_EPOCHS,
+            "Invalid lock epochs"
+        );
+        require!(
+            (NO_LOCK_REWARD_MULTIPLIER..=MAX_REWARD_MULTIPLIER).contains(&reward_multiplier),
+            "Invalid reward multiplier"
+        );
+
+        let _ = self.staking_tiers().insert(lock_epochs, reward_multiplier);
+    }
+
+    #[endpoint(removeStakingTier)]
+    fn remove_staking_tier(&self, lock_epochs: Epoch) {
+        self.require_caller_has_admin_permissions();
+
+        let opt_removed_multiplier = self.staking_tiers().remove(&lock_epochs);
+        if opt_removed_multiplier.is_none() {
+            sc_panic!(UNKNOWN_STAKING_TIER_ERR_MSG);
+        }
+    }
+
+    fn get_tier_reward_multiplier(&self, lock_epochs: Epoch) -> u64 {
+        self.staking_tiers()
+            .get(&lock_epochs)
+            .unwrap_or_else(|| sc_panic!(UNKNOWN_STAKING_TIER_ERR_MSG))
+    }
+
+    fn get_effective_apr(&self, max_apr: &BigUint, reward_multiplier: u64) -> BigUint {
+        max_apr * reward_multiplier / MAX_PERCENT
+    }
+
+    /// Returns (lock_epochs, reward_multiplier, effective_max_apr) for each tier,
+    /// starting with the default tier, which has no lock
+    #[view(getStakingTiers)]
+    fn get_staking_tiers(&self) -> MultiValueEncoded<MultiValue3<Epoch, u64, BigUint>> {
+        let max_apr = self.max_annual_percentage_rewards().get();
+        let mut result = MultiValueEncoded::new();
+        result.push((0, NO_LOCK_REWARD_MULTIPLIER, max_apr.clone()).into());
+
+        for (lock_epochs, reward_multiplier) in self.staking_tiers().iter() {
+            let effective_apr = self.get_effective_apr(&max_apr, reward_multiplier);
+            result.push((lock_epochs, reward_multiplier, effective_apr).into());
+        }
+
+        result
+    }
+
+    #[view(getEffectiveAprForTier)]
+    fn get_effective_apr_for_tier(&self, lock_epochs: Epoch) -> BigUint {
+        let max_apr = self.max_annual_percentage_rewards().get();
+        if lock_epochs == 0 {
+            return max_apr;
+        }
+
+        let reward_multiplier = self.get_tier_reward_multiplier(lock_epochs);
+        self.get_effective_apr(&max_apr, reward_multiplier)
+    }
+
+    #[storage_mapper("stakingTiers")]
+    fn staking_tiers(&self) -> MapMapper<Epoch, u64>;
+}
@@ -1,28 +1,61 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
-use common_structs::{FarmToken, FarmTokenAttributes};
+use common_structs::{Epoch, FarmToken, FarmTokenAttributes};
+use elrond_wasm::elrond_codec::{DecodeErrorHandler, NestedDecodeInput, TopDecodeInput};
 use fixed_supply_token::FixedSupplyToken;
 use math::weighted_average_round_up;
 use mergeable::Mergeable;
 
+use crate::staking_tiers::NO_LOCK_REWARD_MULTIPLIER;
+
 static NOT_IMPLEMENTED_ERR_MSG: &[u8] = b"Conversion not implemented";
 
 #[derive(
-    ManagedVecItem,
-    TopEncode,
-    TopDecode,
-    NestedEncode,
-    NestedDecode,
-    TypeAbi,
-    Clone,
-    PartialEq,
-    Debug,
+    ManagedVecItem, TopEncode, NestedEncode, NestedDecode, TypeAbi, Clone, PartialEq, Debug,
 )]
 pub struct StakingFarmTokenAttributes<M: ManagedTypeApi> {
     pub reward_per_share: BigUint<M>,
     pub compounded_reward: BigUint<M>,
     pub current_farm_amount: BigUint<M>,
+    pub unlock_epoch: Epoch,
+    pub reward_multiplier: u64,
+}
+
+/// Tokens created before the staking tiers only have the first three fields,
+/// and are decoded as positions in the default tier, with no lock
+impl<M: ManagedTypeApi> TopDecode for StakingFarmTokenAttributes<M> {
+    fn top_decode_or_handle_err<I, H>(input: I, h: H) -> Result<Self, H::HandledErr>
+    where
+        I: TopDecodeInput,
+        H: DecodeErrorHandler,
+    {
+        let mut input_nested = input.into_nested_buffer();
+        let reward_per_share = BigUint::dep_decode_or_handle_err(&mut input_nested, h)?;
+        let compounded_reward = BigUint::dep_decode_or_handle_err(&mut input_nested, h)?;
+        let current_farm_amount = BigUint::dep_decode_or_handle_err(&mut input_nested, h)?;
+
+        let (unlock_epoch, reward_multiplier) = if input_nested.is_depleted() {
+            (0, NO_LOCK_REWARD_MULTIPLIER)
+        } else {
+            (
+                Epoch::dep_decode_or_handle_err(&mut input_nested, h)?,
+                u64::dep_decode_or_handle_err(&mut input_nested, h)?,
+            )
+        };
+
+        if !input_nested.is_depleted() {
+            return Err(h.handle_error(elrond_wasm::elrond_codec::DecodeError::INPUT_TOO_LONG));
+        }
+
+        Ok(StakingFarmTokenAttributes {
+            reward_per_share,
+            compounded_reward,
+            current_farm_amount,
+            unlock_epoch,
+            reward_multiplier,
+        })
+    }
 }
 
 #[derive(ManagedVecItem, Clone)]
@@ -74,18 +107,23 @@ impl<M: ManagedTypeApi> FixedSupplyToken<M> for StakingFarmTokenAttributes<M> {
             reward_per_share: self.reward_per_share,
             compounded_reward: new_compounded_reward,
             current_farm_amount: new_current_farm_amount,
+            unlock_epoch: self.unlock_epoch,
+            reward_multiplier: self.reward_multiplier,
         }
     }
 }
 
 impl<M: ManagedTypeApi> Mergeable<M> for StakingFarmTokenAttributes<M> {
-    /// farm staking tokens can always be merged with each other
+    /// farm staking tokens can be merged as long as they were staked in the same tier.
+    /// The merged position keeps the latest unlock epoch
     #[inline]
-    fn can_merge_with(&self, _other: &Self) -> bool {
-        true
+    fn can_merge_with(&self, other: &Self) -> bool {
+        self.reward_multiplier == other.reward_multiplier
     }
 
     fn merge_with(&mut self, other: Self) {
+        self.error_if_not_mergeable(&other);
+
         let first_supply = self.get_total_supply();
         let second_supply = other.get_total_supply();
         self.reward_per_share = weighted_average_round_up(
@@ -97,10 +135,39 @@ impl<M: ManagedTypeApi> Mergeable<M> for StakingFarmTokenAttributes<M> {
 
         self.compounded_reward += other.compounded_reward;
         self.current_farm_amount += other.current_farm_amount;
+        self.unlock_epoch = core::cmp::max(self.unlock_epoch, other.unlock_epoch);
     }
 }
 
-#[derive(TypeAbi, TopEncode, TopDecode, PartialEq, Debug)]
+#[derive(TypeAbi, TopEncode, PartialEq, Debug)]
 pub struct UnbondSftAttributes {
     pub unlock_epoch: u64,
+    pub unbond_epochs: u64,
+}
+
+/// Tokens created before the unbond period was stored only have the unlock epoch,
+/// and are decoded with 0 unbond epochs
+impl TopDecode for UnbondSftAttributes {
+    fn top_decode_or_handle_err<I, H>(input: I, h: H) -> Result<Self, H::HandledErr>
+    where
+        I: TopDecodeInput,
+        H: DecodeErrorHandler,
+    {
+        let mut input_nested = input.into_nested_buffer();
+        let unlock_epoch = u64::dep_decode_or_handle_err(&mut input_nested, h)?;
+        let unbond_epochs = if input_nested.is_depleted() {
+            0
+        } else {
+            u64::dep_decode_or_handle_err(&mut input_nested, h)?
+        };
+
+        if !input_nested.is_depleted() {
+            return Err(h.handle_error(elrond_wasm::elrond_codec::DecodeError::INPUT_TOO_LONG));
+        }
+
+        Ok(UnbondSftAttributes {
+            unlock_epoch,
+            unbond_epochs,
+        })
+    }
 }
@@ -1,8 +1,13 @@
 elrond_wasm::imports!();
 
+use common_structs::Nonce;
 use contexts::storage_cache::StorageCache;
+use farm_base_impl::base_traits_impl::FarmContract;
 
-use crate::token_attributes::UnbondSftAttributes;
+use crate::{
+    base_impl_wrapper::FarmStakingWrapper, custom_rewards::MAX_PERCENT,
+    token_attributes::UnbondSftAttributes,
+};
 
 #[elrond_wasm::module]
 pub trait UnbondFarmModule:
@@ -18,7 +23,10 @@ pub trait UnbondFarmModule:
     + elrond_wasm_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
+    + farm_base_impl::exit_farm::BaseExitFarmModule
+    + farm_base_impl::emergency_exit::BaseEmergencyExitModule
     + utils::UtilsModule
+    + crate::unstake_farm::UnstakeFarmModule
 {
     #[payable("*")]
     #[endpoint(unbondFarm)]
@@ -48,4 +56,142 @@ pub trait UnbondFarmModule:
 
         farming_tokens
     }
+
+    /// Both percentages are in basis points (10_000 = 100%).
+    /// burn_percent is the part of the penalty that is burned, the rest is redistributed to the stakers.
+    #[endpoint(setEarlyUnbondPenalty)]
+    fn set_early_unbond_penalty(&self, max_penalty_percent: u64, burn_percent: u64) {
+        self.require_caller_has_admin_permissions();
+        require!(
+            max_penalty_percent > 0 && max_penalty_percent <= MAX_PERCENT,
+            "Invalid max penalty percent"
+        );
+        require!(burn_percent <= MAX_PERCENT, "Invalid burn percent");
+
+        self.early_unbond_max_penalty_percent()
+            .set(max_penalty_percent);
+        self.early_unbond_penalty_burn_percent().set(burn_percent);
+    }
+
+    /// Withdraws the farming tokens before the unbond period is over.
+    /// The penalty decreases linearly, from the max penalty at unstake, down to zero at the unlock epoch.
+    /// The SC needs the ESDTLocalBurn role for the farming token, to burn part of the penalty.
+    #[payable("*")]
+    #[endpoint(unbondEarly)]
+    fn unbond_early(&self) -> EsdtTokenPayment {
+        let mut storage_cache = StorageCache::new(self);
+        self.validate_contract_state(storage_cache.contract_state, &storage_cache.farm_token_id);
+        require!(
+            !self.early_unbond_max_penalty_percent().is_empty(),
+            "Early unbond not enabled"
+        );
+
+        let farm_token_mapper = self.farm_token();
+        let payment = self.call_value().single_esdt();
+        farm_token_mapper.require_same_token(&payment.token_identifier);
+
+        let attributes: UnbondSftAttributes =
+            farm_token_mapper.get_token_attributes(payment.token_nonce);
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(
+            current_epoch < attributes.unlock_epoch,
+            "Unbond period already over"
+        );
+
+        farm_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+        let penalty_amount = self.get_early_unbond_penalty_amount(&payment.amount, &attributes);
+        if penalty_amount > 0 {
+            self.distribute_early_unbond_penalty(&mut storage_cache, &penalty_amount);
+        }
+
+        let caller = self.blockchain().get_caller();
+        let farming_tokens = EsdtTokenPayment::new(
+            storage_cache.farming_token_id.clone(),
+            0,
+            payment.amount - penalty_amount,
+        );
+        self.send_payment_non_zero(&caller, &farming_tokens);
+
+        farming_tokens
+    }
+
+    #[view(getEarlyUnbondPenalty)]
+    fn get_early_unbond_penalty(&self, unbond_token_nonce: Nonce, amount: BigUint) -> BigUint {
+        let attributes: UnbondSftAttributes =
+            self.farm_token().get_token_attributes(unbond_token_nonce);
+        let current_epoch = self.blockchain().get_block_epoch();
+        if current_epoch >= attributes.unlock_epoch {
+            return BigUint::zero();
+        }
+
+        self.get_early_unbond_penalty_amount(&amount, &attributes)
+    }
+
+    /// The unbond period is the one from the unstake, so later changes of the min unbond epochs
+    /// don't affect the penalty. For unbond tokens created before the period was stored
+    /// in the attributes, the current min unbond epochs are used instead.
+    fn get_early_unbond_penalty_amount(
+        &self,
+        amount: &BigUint,
+        attributes: &UnbondSftAttributes,
+    ) -> BigUint {
+        let max_penalty_percent = self.early_unbond_max_penalty_percent().get();
+        let current_epoch = self.blockchain().get_block_epoch();
+        let unbond_epochs = if attributes.unbond_epochs > 0 {
+            attributes.unbond_epochs
+        } else {
+            self.min_unbond_epochs().get()
+        };
+        let unlock_epoch = attributes.unlock_epoch;
+        let unbond_start_epoch =
+            core::cmp::min(unlock_epoch.saturating_sub(unbond_epochs), current_epoch);
+
+        let penalty_percent = math::linear_interpolation::<Self::Api, _>(
+            unbond_start_epoch,
+            unlock_epoch,
+            current_epoch,
+            max_penalty_percent,
+            0,
+        );
+
+        amount * penalty_percent / MAX_PERCENT
+    }
+
+    fn distribute_early_unbond_penalty(
+        &self,
+        storage_cache: &mut StorageCache<Self>,
+        penalty_amount: &BigUint,
+    ) {
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, storage_cache);
+
+        let burn_amount = if storage_cache.farm_token_supply > 0 {
+            let burn_percent = self.early_unbond_penalty_burn_percent().get();
+            penalty_amount * burn_percent / MAX_PERCENT
+        } else {
+            penalty_amount.clone()
+        };
+
+        let redistributed_amount = penalty_amount - &burn_amount;
+        if redistributed_amount > 0 {
+            let increase = (&redistributed_amount * &storage_cache.division_safety_constant)
+                / &storage_cache.farm_token_supply;
+            storage_cache.reward_per_share += &increase;
+            storage_cache.reward_reserve += &redistributed_amount;
+        }
+
+        if burn_amount > 0 {
+            self.send()
+                .esdt_local_burn(&storage_cache.farming_token_id, 0, &burn_amount);
+        }
+    }
+
+    #[view(getEarlyUnbondMaxPenaltyPercent)]
+    #[storage_mapper("earlyUnbondMaxPenaltyPercent")]
+    fn early_unbond_max_penalty_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(getEarlyUnbondPenaltyBurnPercent)]
+    #[storage_mapper("earlyUnbondPenaltyBurnPercent")]
+    fn early_unbond_penalty_burn_percent(&self) -> SingleValueMapper<u64>;
 }
@@ -2,7 +2,10 @@ elrond_wasm::imports!();
 
 use farm::base_functions::ExitFarmResultType;
 
-use crate::{base_impl_wrapper::FarmStakingWrapper, token_attributes::UnbondSftAttributes};
+use crate::{
+    base_impl_wrapper::FarmStakingWrapper,
+    token_attributes::{StakingFarmTokenAttributes, UnbondSftAttributes},
+};
 
 #[elrond_wasm::module]
 pub trait UnstakeFarmModule:
@@ -19,6 +22,7 @@ pub trait UnstakeFarmModule:
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::exit_farm::BaseExitFarmModule
+    + farm_base_impl::emergency_exit::BaseEmergencyExitModule
     + utils::UtilsModule
 {
     #[payable("*")]
@@ -45,7 +49,41 @@ pub trait UnstakeFarmModule:
             "Invalid staking token received"
         );
 
-        self.unstake_farm_common(second_payment, Some(first_payment.amount))
+        // the compounded rewards of the position are already held by the staking farm
+        let farm_token_mapper = self.farm_token();
+        farm_token_mapper.require_same_token(&second_payment.token_identifier);
+        let attributes: StakingFarmTokenAttributes<Self::Api> =
+            self.get_attributes_as_part_of_fixed_supply(&second_payment, &farm_token_mapper);
+        let unbond_amount = first_payment.amount + attributes.compounded_reward;
+
+        self.unstake_farm_common(second_payment, Some(unbond_amount))
+    }
+
+    /// Only available while the farm is paused.
+    /// Returns the staked tokens without an unbonding period, forfeiting all the position's rewards.
+    #[payable("*")]
+    #[endpoint(emergencyExit)]
+    fn emergency_exit(&self) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.sc_whitelist_addresses().contains(&caller),
+            "Proxy positions may not use emergency exit"
+        );
+
+        let payment = self.call_value().single_esdt();
+        let exit_result = self.emergency_exit_base::<FarmStakingWrapper<Self>>(payment);
+
+        let farming_tokens = exit_result.farming_token_payment;
+        self.send_payment_non_zero(&caller, &farming_tokens);
+
+        self.emit_emergency_exit_event(
+            &caller,
+            exit_result.context,
+            farming_tokens.clone(),
+            exit_result.storage_cache,
+        );
+
+        farming_tokens
     }
 
     fn unstake_farm_common(
@@ -53,6 +91,8 @@ pub trait UnstakeFarmModule:
         payment: EsdtTokenPayment,
         opt_unbond_amount: Option<BigUint>,
     ) -> ExitFarmResultType<Self::Api> {
+        self.require_position_unlocked(&payment);
+
         let caller = self.blockchain().get_caller();
         let exit_result = self.exit_farm_base::<FarmStakingWrapper<Self>>(caller.clone(), payment);
 
@@ -75,6 +115,19 @@ pub trait UnstakeFarmModule:
         (unbond_farm_token, exit_result.reward_payment).into()
     }
 
+    fn require_position_unlocked(&self, payment: &EsdtTokenPayment) {
+        let farm_token_mapper = self.farm_token();
+        farm_token_mapper.require_same_token(&payment.token_identifier);
+
+        let attributes: StakingFarmTokenAttributes<Self::Api> =
+            farm_token_mapper.get_token_attributes(payment.token_nonce);
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(
+            current_epoch >= attributes.unlock_epoch,
+            "Position is still locked"
+        );
+    }
+
     fn create_and_send_unbond_tokens(
         &self,
         to: &ManagedAddress,
@@ -88,6 +141,7 @@ pub trait UnstakeFarmModule:
             &amount,
             &UnbondSftAttributes {
                 unlock_epoch: current_epoch + min_unbond_epochs,
+                unbond_epochs: min_unbond_epochs,
             },
         );
         self.send()
@@ -1,5 +1,8 @@
+use elrond_wasm::elrond_codec::{NestedEncode, TopDecode, TopEncode};
 use elrond_wasm::storage::mappers::StorageTokenWrapper;
-use elrond_wasm::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
+use elrond_wasm::types::{
+    Address, EsdtLocalRole, ManagedAddress, ManagedBuffer, MultiValueEncoded,
+};
 use elrond_wasm_debug::tx_mock::{TxContextStack, TxInputESDT};
 use elrond_wasm_debug::{
     managed_biguint, managed_token_id, rust_biguint, testing_framework::*, DebugApi,
@@ -9,14 +12,18 @@ type RustBigUint = num_bigint::BigUint;
 
 use config::*;
 use farm_staking::claim_stake_farm_rewards::ClaimStakeFarmRewardsModule;
-use farm_staking::custom_rewards::{CustomRewardsModule, BLOCKS_IN_YEAR, MAX_PERCENT};
+use farm_staking::custom_rewards::{
+    CustomRewardsModule, BLOCKS_IN_YEAR, MAX_PERCENT, SECONDS_IN_YEAR,
+};
 use farm_staking::stake_farm::StakeFarmModule;
+use farm_staking::staking_tiers::{StakingTiersModule, NO_LOCK_REWARD_MULTIPLIER};
 use farm_staking::token_attributes::{StakingFarmTokenAttributes, UnbondSftAttributes};
 use farm_staking::unbond_farm::UnbondFarmModule;
 use farm_staking::unstake_farm::UnstakeFarmModule;
 use farm_staking::*;
 use farm_token::FarmTokenModule;
 use pausable::{PausableModule, State};
+use rewards::RewardsModule;
 
 const FARM_WASM_PATH: &str = "farm/output/farm-staking.wasm";
 
@@ -27,11 +34,11 @@ const DIVISION_SAFETY_CONSTANT: u64 = 1_000_000_000_000;
 const MIN_UNBOND_EPOCHS: u64 = 5;
 const MAX_APR: u64 = 2_500; // 25%
 const PER_BLOCK_REWARD_AMOUNT: u64 = 5_000;
+const PER_SECOND_REWARD_AMOUNT: u64 = 1_000;
 const TOTAL_REWARDS_AMOUNT: u64 = 1_000_000_000_000;
 
 const USER_TOTAL_RIDE_TOKENS: u64 = 5_000_000_000;
 
-#[allow(dead_code)] // owner_address is unused, at least for now
 struct FarmSetup<FarmObjBuilder>
 where
     FarmObjBuilder: 'static + Copy + Fn() -> farm_staking::ContractObj<DebugApi>,
@@ -173,6 +180,8 @@ fn stake_farm<FarmObjBuilder>(
         reward_per_share: managed_biguint!(expected_reward_per_share),
         compounded_reward: managed_biguint!(expected_compounded_reward),
         current_farm_amount: managed_biguint!(expected_total_out_amount),
+        unlock_epoch: 0,
+        reward_multiplier: NO_LOCK_REWARD_MULTIPLIER,
     };
     b_mock.check_nft_balance(
         &farm_setup.user_address,
@@ -338,6 +347,8 @@ fn claim_rewards<FarmObjBuilder>(
         reward_per_share: managed_biguint!(expected_reward_per_share),
         compounded_reward: managed_biguint!(0),
         current_farm_amount: managed_biguint!(farm_token_amount),
+        unlock_epoch: 0,
+        reward_multiplier: NO_LOCK_REWARD_MULTIPLIER,
     };
 
     b_mock.check_nft_balance(
@@ -393,6 +404,13 @@ where
     farm_setup.blockchain_wrapper.set_block_epoch(block_epoch);
 }
 
+fn set_block_timestamp<FarmObjBuilder>(farm_setup: &mut FarmSetup<FarmObjBuilder>, timestamp: u64)
+where
+    FarmObjBuilder: 'static + Copy + Fn() -> farm_staking::ContractObj<DebugApi>,
+{
+    farm_setup.blockchain_wrapper.set_block_timestamp(timestamp);
+}
+
 #[test]
 fn test_farm_setup() {
     let _ = setup_farm(farm_staking::contract_obj);
@@ -458,6 +476,7 @@ fn test_unstake_farm() {
         farm_in_amount,
         &UnbondSftAttributes {
             unlock_epoch: current_epoch + MIN_UNBOND_EPOCHS,
+            unbond_epochs: MIN_UNBOND_EPOCHS,
         },
     );
     check_farm_token_supply(&mut farm_setup, 0);
@@ -581,6 +600,7 @@ fn test_exit_farm_after_enter_twice() {
         farm_in_amount,
         &UnbondSftAttributes {
             unlock_epoch: 8 + MIN_UNBOND_EPOCHS,
+            unbond_epochs: MIN_UNBOND_EPOCHS,
         },
     );
     check_farm_token_supply(&mut farm_setup, second_farm_in_amount);
@@ -629,6 +649,7 @@ fn test_unbond() {
         farm_in_amount,
         &UnbondSftAttributes {
             unlock_epoch: current_epoch + MIN_UNBOND_EPOCHS,
+            unbond_epochs: MIN_UNBOND_EPOCHS,
         },
     );
     check_farm_token_supply(&mut farm_setup, 0);
@@ -643,3 +664,522 @@ fn test_unbond() {
         USER_TOTAL_RIDE_TOKENS + expected_rewards,
     );
 }
+
+#[test]
+fn test_timestamp_based_rewards() {
+    let mut farm_setup = setup_farm(farm_staking::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    stake_farm(
+        &mut farm_setup,
+        farm_in_amount,
+        &[],
+        expected_farm_token_nonce,
+        0,
+        0,
+    );
+    check_farm_token_supply(&mut farm_setup, farm_in_amount);
+
+    let switch_timestamp = 100;
+    set_block_timestamp(&mut farm_setup, switch_timestamp);
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.switch_to_timestamp_based_rewards(managed_biguint!(PER_SECOND_REWARD_AMOUNT));
+
+                assert!(sc.timestamp_based_rewards().get());
+                assert_eq!(sc.last_reward_timestamp().get(), switch_timestamp);
+            },
+        )
+        .assert_ok();
+
+    // block nonce is not taken into account anymore
+    set_block_nonce(&mut farm_setup, 1_000);
+    set_block_epoch(&mut farm_setup, 5);
+
+    let seconds_diff = 60;
+    set_block_timestamp(&mut farm_setup, switch_timestamp + seconds_diff);
+
+    let expected_rewards_unbounded = seconds_diff * PER_SECOND_REWARD_AMOUNT;
+    let expected_rewards_max_apr =
+        farm_in_amount * MAX_APR * seconds_diff / MAX_PERCENT / SECONDS_IN_YEAR;
+    let expected_rewards = core::cmp::min(expected_rewards_unbounded, expected_rewards_max_apr);
+    assert_eq!(expected_rewards, 47);
+
+    let expected_farming_token_balance =
+        rust_biguint!(USER_TOTAL_RIDE_TOKENS - farm_in_amount + expected_rewards);
+    let expected_reward_per_share = 470_000;
+    claim_rewards(
+        &mut farm_setup,
+        farm_in_amount,
+        expected_farm_token_nonce,
+        expected_rewards,
+        &expected_farming_token_balance,
+        &expected_farming_token_balance,
+        expected_farm_token_nonce + 1,
+        expected_reward_per_share,
+    );
+    check_farm_token_supply(&mut farm_setup, farm_in_amount);
+}
+
+#[test]
+fn test_emergency_exit() {
+    let mut farm_setup = setup_farm(farm_staking::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    stake_farm(
+        &mut farm_setup,
+        farm_in_amount,
+        &[],
+        expected_farm_token_nonce,
+        0,
+        0,
+    );
+    check_farm_token_supply(&mut farm_setup, farm_in_amount);
+
+    set_block_epoch(&mut farm_setup, 5);
+    set_block_nonce(&mut farm_setup, 10);
+
+    // farm is active - emergency exit not available
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.emergency_exit();
+            },
+        )
+        .assert_user_error("Emergency exit is only available while the farm is paused");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.pause();
+            },
+        )
+        .assert_ok();
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let payment = sc.emergency_exit();
+                assert_eq!(
+                    payment.token_identifier,
+                    managed_token_id!(FARMING_TOKEN_ID)
+                );
+                assert_eq!(payment.amount, managed_biguint!(farm_in_amount));
+            },
+        )
+        .assert_ok();
+
+    // rewards are forfeited, no unbonding period
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &farm_setup.user_address,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_RIDE_TOKENS),
+    );
+    farm_setup
+        .blockchain_wrapper
+        .check_nft_balance::<StakingFarmTokenAttributes<DebugApi>>(
+            &farm_setup.user_address,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce,
+            &rust_biguint!(0),
+            None,
+        );
+    check_farm_token_supply(&mut farm_setup, 0);
+}
+
+#[test]
+fn test_locked_staking_tier() {
+    let mut farm_setup = setup_farm(farm_staking::contract_obj);
+
+    let lock_epochs = 30;
+    let reward_multiplier = 2 * NO_LOCK_REWARD_MULTIPLIER;
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_staking_tier(lock_epochs, reward_multiplier);
+
+                assert_eq!(sc.get_effective_apr_for_tier(0), managed_biguint!(MAX_APR));
+                assert_eq!(
+                    sc.get_effective_apr_for_tier(lock_epochs),
+                    managed_biguint!(2 * MAX_APR)
+                );
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.stake_farm_locked(lock_epochs + 1);
+            },
+        )
+        .assert_user_error("Unknown staking tier");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let payment = sc.stake_farm_locked(lock_epochs);
+                assert_eq!(payment.token_nonce, 1);
+                assert_eq!(payment.amount, managed_biguint!(farm_in_amount));
+            },
+        )
+        .assert_ok();
+
+    let _ = DebugApi::dummy();
+    farm_setup.blockchain_wrapper.check_nft_balance(
+        &farm_setup.user_address,
+        FARM_TOKEN_ID,
+        1,
+        &rust_biguint!(farm_in_amount),
+        Some(&StakingFarmTokenAttributes::<DebugApi> {
+            reward_per_share: managed_biguint!(0),
+            compounded_reward: managed_biguint!(0),
+            current_farm_amount: managed_biguint!(farm_in_amount),
+            unlock_epoch: lock_epochs,
+            reward_multiplier,
+        }),
+    );
+
+    // locked positions can't be merged with unlocked ones
+    stake_farm(&mut farm_setup, farm_in_amount, &[], 2, 0, 0);
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_multi_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            &[
+                TxInputESDT {
+                    token_identifier: FARM_TOKEN_ID.to_vec(),
+                    nonce: 1,
+                    value: rust_biguint!(farm_in_amount),
+                },
+                TxInputESDT {
+                    token_identifier: FARM_TOKEN_ID.to_vec(),
+                    nonce: 2,
+                    value: rust_biguint!(farm_in_amount),
+                },
+            ],
+            |sc| {
+                let _ = sc.merge_farm_tokens_endpoint();
+            },
+        )
+        .assert_user_error("Cannot merge");
+
+    set_block_epoch(&mut farm_setup, 5);
+    set_block_nonce(&mut farm_setup, 10);
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.unstake_farm();
+            },
+        )
+        .assert_user_error("Position is still locked");
+
+    // 10 blocks * 9 per block (APR bounded) = 90, split between the two positions
+    // the locked position receives double the base reward
+    let expected_base_reward = 45;
+    let expected_bonus_reward = 45;
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (new_farm_token, rewards) = sc.claim_rewards().into_tuple();
+                assert_eq!(new_farm_token.token_nonce, 3);
+                assert_eq!(
+                    rewards.amount,
+                    managed_biguint!(expected_base_reward + expected_bonus_reward)
+                );
+            },
+        )
+        .assert_ok();
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(
+                sc.accumulated_rewards().get(),
+                managed_biguint!(2 * expected_base_reward + expected_bonus_reward)
+            );
+        })
+        .assert_ok();
+
+    set_block_epoch(&mut farm_setup, lock_epochs);
+    set_block_nonce(&mut farm_setup, 20);
+
+    // rewards claimed after the lock expired are not boosted
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            3,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (new_farm_token, rewards) = sc.claim_rewards().into_tuple();
+                assert_eq!(new_farm_token.token_nonce, 4);
+                assert_eq!(rewards.amount, managed_biguint!(expected_base_reward));
+            },
+        )
+        .assert_ok();
+
+    farm_setup.blockchain_wrapper.check_nft_balance(
+        &farm_setup.user_address,
+        FARM_TOKEN_ID,
+        4,
+        &rust_biguint!(farm_in_amount),
+        Some(&StakingFarmTokenAttributes::<DebugApi> {
+            reward_per_share: managed_biguint!(
+                2 * expected_base_reward * DIVISION_SAFETY_CONSTANT / farm_in_amount
+            ),
+            compounded_reward: managed_biguint!(0),
+            current_farm_amount: managed_biguint!(farm_in_amount),
+            unlock_epoch: 0,
+            reward_multiplier: NO_LOCK_REWARD_MULTIPLIER,
+        }),
+    );
+
+    let expected_farming_token_balance = rust_biguint!(
+        USER_TOTAL_RIDE_TOKENS - 2 * farm_in_amount
+            + 2 * expected_base_reward
+            + expected_bonus_reward
+    );
+    unstake_farm(
+        &mut farm_setup,
+        farm_in_amount,
+        4,
+        0,
+        &expected_farming_token_balance,
+        &expected_farming_token_balance,
+        5,
+        farm_in_amount,
+        &UnbondSftAttributes {
+            unlock_epoch: lock_epochs + MIN_UNBOND_EPOCHS,
+            unbond_epochs: MIN_UNBOND_EPOCHS,
+        },
+    );
+    check_farm_token_supply(&mut farm_setup, farm_in_amount);
+}
+
+#[test]
+fn test_unbond_early() {
+    let mut farm_setup = setup_farm(farm_staking::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    stake_farm(&mut farm_setup, farm_in_amount, &[], 1, 0, 0);
+    stake_farm(&mut farm_setup, farm_in_amount, &[], 2, 0, 0);
+
+    let expected_ride_token_balance = rust_biguint!(USER_TOTAL_RIDE_TOKENS - 2 * farm_in_amount);
+    unstake_farm(
+        &mut farm_setup,
+        farm_in_amount,
+        1,
+        0,
+        &expected_ride_token_balance,
+        &expected_ride_token_balance,
+        3,
+        farm_in_amount,
+        &UnbondSftAttributes {
+            unlock_epoch: MIN_UNBOND_EPOCHS,
+            unbond_epochs: MIN_UNBOND_EPOCHS,
+        },
+    );
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            3,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.unbond_early();
+            },
+        )
+        .assert_user_error("Early unbond not enabled");
+
+    // max 50% penalty, half of which is burned
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_early_unbond_penalty(5_000, 5_000);
+
+                // doesn't affect the penalty of already unstaked tokens
+                sc.set_min_unbond_epochs_endpoint(1);
+            },
+        )
+        .assert_ok();
+
+    // 2 out of 5 epochs passed => 30% penalty
+    set_block_epoch(&mut farm_setup, 2);
+    let expected_penalty = 30_000_000;
+    let expected_redistributed_amount = 15_000_000;
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &farm_setup.user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            3,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let payment = sc.unbond_early();
+                assert_eq!(
+                    payment.token_identifier,
+                    managed_token_id!(FARMING_TOKEN_ID)
+                );
+                assert_eq!(
+                    payment.amount,
+                    managed_biguint!(farm_in_amount - expected_penalty)
+                );
+
+                assert_eq!(
+                    sc.reward_reserve().get(),
+                    managed_biguint!(expected_redistributed_amount)
+                );
+            },
+        )
+        .assert_ok();
+
+    let expected_ride_token_balance =
+        rust_biguint!(USER_TOTAL_RIDE_TOKENS - farm_in_amount - expected_penalty);
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &farm_setup.user_address,
+        FARMING_TOKEN_ID,
+        &expected_ride_token_balance,
+    );
+
+    // the remaining staker receives the redistributed part of the penalty
+    let expected_ride_token_balance = &expected_ride_token_balance + expected_redistributed_amount;
+    claim_rewards(
+        &mut farm_setup,
+        farm_in_amount,
+        2,
+        expected_redistributed_amount,
+        &expected_ride_token_balance,
+        &expected_ride_token_balance,
+        4,
+        expected_redistributed_amount * DIVISION_SAFETY_CONSTANT / farm_in_amount,
+    );
+}
+
+#[test]
+fn test_decode_attributes_without_lock() {
+    let _ = DebugApi::dummy();
+
+    // layout of the farm tokens created before the staking tiers
+    let encoded_attributes = top_encode_to_vec_u8_or_panic(&(
+        managed_biguint!(1_000),
+        managed_biguint!(50),
+        managed_biguint!(5_000),
+    ));
+
+    let attributes =
+        StakingFarmTokenAttributes::<DebugApi>::top_decode(&encoded_attributes[..]).unwrap();
+    assert_eq!(
+        attributes,
+        StakingFarmTokenAttributes {
+            reward_per_share: managed_biguint!(1_000),
+            compounded_reward: managed_biguint!(50),
+            current_farm_amount: managed_biguint!(5_000),
+            unlock_epoch: 0,
+            reward_multiplier: NO_LOCK_REWARD_MULTIPLIER,
+        }
+    );
+
+    // the current layout is decoded as is
+    let locked_attributes = StakingFarmTokenAttributes::<DebugApi> {
+        unlock_epoch: 30,
+        reward_multiplier: 2 * NO_LOCK_REWARD_MULTIPLIER,
+        ..attributes
+    };
+    let encoded_locked_attributes = top_encode_to_vec_u8_or_panic(&locked_attributes);
+    assert_eq!(
+        StakingFarmTokenAttributes::<DebugApi>::top_decode(&encoded_locked_attributes[..]).unwrap(),
+        locked_attributes
+    );
+}
+
+#[test]
+fn test_decode_unbond_attributes_without_unbond_epochs() {
+    // layout of the unbond tokens created before the unbond period was stored
+    let mut encoded_attributes = Vec::new();
+    25u64.dep_encode(&mut encoded_attributes).unwrap();
+
+    let attributes = UnbondSftAttributes::top_decode(&encoded_attributes[..]).unwrap();
+    assert_eq!(
+        attributes,
+        UnbondSftAttributes {
+            unlock_epoch: 25,
+            unbond_epochs: 0,
+        }
+    );
+
+    // the current layout is decoded as is
+    let new_attributes = UnbondSftAttributes {
+        unlock_epoch: 25,
+        unbond_epochs: MIN_UNBOND_EPOCHS,
+    };
+    let encoded_new_attributes = top_encode_to_vec_u8_or_panic(&new_attributes);
+    assert_eq!(
+        UnbondSftAttributes::top_decode(&encoded_new_attributes[..]).unwrap(),
+        new_attributes
+    );
+}
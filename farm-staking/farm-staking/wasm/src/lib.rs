@@ -15,37 +15,55 @@ elrond_wasm_node::wasm_endpoints! {
         claimRewards
         claimRewardsWithNewValue
         compoundRewards
+        compoundRewardsThroughProxy
+        emergencyExit
         endProduceRewards
         getAccumulatedRewards
         getAnnualPercentageRewards
         getDivisionSafetyConstant
+        getEarlyUnbondMaxPenaltyPercent
+        getEarlyUnbondPenalty
+        getEarlyUnbondPenaltyBurnPercent
+        getEffectiveAprForTier
         getFarmTokenId
         getFarmTokenSupply
         getFarmingTokenId
         getLastRewardBlockNonce
+        getLastRewardTimestamp
         getMinUnbondEpochs
         getPerBlockRewardAmount
+        getPerSecondRewardAmount
         getPermissions
         getRewardCapacity
         getRewardPerShare
         getRewardReserve
         getRewardTokenId
+        getStakingTiers
         getState
         isSCAddressWhitelisted
+        isTimestampBasedRewards
         mergeFarmTokens
         pause
         registerFarmToken
         removeAdmin
         removeFromPauseWhitelist
         removeSCAddressFromWhitelist
+        removeStakingTier
         resume
+        setEarlyUnbondPenalty
         setMaxApr
         setMinUnbondEpochs
         setPerBlockRewardAmount
+        setPerSecondRewardAmount
+        setStakingTier
         stakeFarm
+        stakeFarmLocked
         stakeFarmThroughProxy
         startProduceRewards
+        switchToBlockBasedRewards
+        switchToTimestampBasedRewards
         topUpRewards
+        unbondEarly
         unbondFarm
         unstakeFarm
         unstakeFarmThroughProxy
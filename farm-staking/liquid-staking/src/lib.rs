@@ -0,0 +1,188 @@
+#![no_std]
+
+elrond_wasm::imports!();
+
+pub mod share_token;
+pub mod staking_farm_interactions;
+pub mod withdrawal_queue;
+
+/// Pools farm-staking positions into a single position owned by the contract.
+/// Users receive a fungible share token, whose value in staking tokens grows as rewards are compounded.
+#[elrond_wasm::contract]
+pub trait LiquidStaking:
+    share_token::ShareTokenModule
+    + staking_farm_interactions::StakingFarmInteractionsModule
+    + withdrawal_queue::WithdrawalQueueModule
+    + token_send::TokenSendModule
+    + elrond_wasm_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[init]
+    fn init(
+        &self,
+        staking_farm_address: ManagedAddress,
+        staking_token_id: TokenIdentifier,
+        staking_farm_token_id: TokenIdentifier,
+    ) {
+        require!(
+            self.blockchain().is_smart_contract(&staking_farm_address),
+            "Invalid Staking Farm address"
+        );
+        require!(
+            staking_token_id.is_valid_esdt_identifier(),
+            "Invalid Staking token ID"
+        );
+        require!(
+            staking_farm_token_id.is_valid_esdt_identifier(),
+            "Invalid Staking Farm token ID"
+        );
+
+        self.staking_farm_address().set(&staking_farm_address);
+        self.staking_token_id().set(&staking_token_id);
+        self.staking_farm_token_id().set(&staking_farm_token_id);
+    }
+
+    #[payable("*")]
+    #[endpoint(stake)]
+    fn stake(&self) -> EsdtTokenPayment {
+        let payment = self.call_value().single_esdt();
+        require!(
+            payment.token_identifier == self.staking_token_id().get(),
+            "Invalid payment token"
+        );
+
+        self.compound_pool_rewards();
+
+        let share_token_amount = self.get_share_token_amount_for_stake(payment.amount.clone());
+        require!(share_token_amount > 0, "Stake amount too low");
+
+        self.add_to_staking_position(payment);
+        self.share_token_supply()
+            .update(|supply| *supply += &share_token_amount);
+
+        let caller = self.blockchain().get_caller();
+        self.share_token()
+            .mint_and_send(&caller, share_token_amount)
+    }
+
+    /// Burns the share tokens and starts unbonding the equivalent staking tokens.
+    /// The staking tokens can be claimed through the withdraw endpoint, after the unbond period.
+    #[payable("*")]
+    #[endpoint(unstake)]
+    fn unstake(&self) -> EsdtTokenPayment {
+        let payment = self.call_value().single_esdt();
+        let share_token_mapper = self.share_token();
+        share_token_mapper.require_same_token(&payment.token_identifier);
+
+        self.compound_pool_rewards();
+
+        let staking_token_amount = self.get_staking_token_amount_for_shares(payment.amount.clone());
+        require!(staking_token_amount > 0, "Unstake amount too low");
+
+        share_token_mapper.burn(&payment.amount);
+        self.share_token_supply()
+            .update(|supply| *supply -= &payment.amount);
+
+        let position_mapper = self.staking_position();
+        let mut position = position_mapper.get();
+        let unstaked_farm_tokens = EsdtTokenPayment::new(
+            position.token_identifier.clone(),
+            position.token_nonce,
+            staking_token_amount,
+        );
+        position.amount -= &unstaked_farm_tokens.amount;
+        if position.amount > 0 {
+            position_mapper.set(&position);
+        } else {
+            position_mapper.clear();
+        }
+
+        let unstake_result = self.staking_farm_unstake(unstaked_farm_tokens);
+        if unstake_result.rewards.amount > 0 {
+            self.rewards_to_restake()
+                .update(|rewards| *rewards += unstake_result.rewards.amount);
+        }
+
+        let caller = self.blockchain().get_caller();
+        self.add_withdrawal_request(&caller, unstake_result.unbond_tokens.clone());
+
+        unstake_result.unbond_tokens
+    }
+
+    #[endpoint(compoundRewards)]
+    fn compound_rewards(&self) {
+        self.compound_pool_rewards();
+    }
+
+    fn compound_pool_rewards(&self) {
+        let position_mapper = self.staking_position();
+        if position_mapper.is_empty() {
+            return;
+        }
+
+        let position = position_mapper.get();
+        let mut new_position = self.staking_farm_compound(position);
+
+        let rewards_to_restake = self.rewards_to_restake().get();
+        if rewards_to_restake > 0 {
+            self.rewards_to_restake().clear();
+
+            let staking_token_id = self.staking_token_id().get();
+            let staking_tokens = EsdtTokenPayment::new(staking_token_id, 0, rewards_to_restake);
+            new_position = self.staking_farm_enter(staking_tokens, Some(new_position));
+        }
+
+        position_mapper.set(&new_position);
+    }
+
+    fn add_to_staking_position(&self, staking_tokens: EsdtTokenPayment) {
+        let position_mapper = self.staking_position();
+        let opt_current_position = if !position_mapper.is_empty() {
+            Some(position_mapper.get())
+        } else {
+            None
+        };
+
+        let new_position = self.staking_farm_enter(staking_tokens, opt_current_position);
+        position_mapper.set(&new_position);
+    }
+
+    #[view(getShareTokenAmountForStake)]
+    fn get_share_token_amount_for_stake(&self, staking_token_amount: BigUint) -> BigUint {
+        let total_staked = self.get_total_staked_amount();
+        let share_token_supply = self.share_token_supply().get();
+        if total_staked == 0 || share_token_supply == 0 {
+            return staking_token_amount;
+        }
+
+        staking_token_amount * share_token_supply / total_staked
+    }
+
+    #[view(getStakingTokenAmountForShares)]
+    fn get_staking_token_amount_for_shares(&self, share_token_amount: BigUint) -> BigUint {
+        let share_token_supply = self.share_token_supply().get();
+        if share_token_supply == 0 {
+            return BigUint::zero();
+        }
+
+        share_token_amount * self.get_total_staked_amount() / share_token_supply
+    }
+
+    /// The farm token amount of a staking position is equal to the staked amount, including compounded rewards
+    #[view(getTotalStakedAmount)]
+    fn get_total_staked_amount(&self) -> BigUint {
+        let position_mapper = self.staking_position();
+        if position_mapper.is_empty() {
+            return BigUint::zero();
+        }
+
+        position_mapper.get().amount
+    }
+
+    #[view(getStakingPosition)]
+    #[storage_mapper("stakingPosition")]
+    fn staking_position(&self) -> SingleValueMapper<EsdtTokenPayment>;
+
+    #[view(getRewardsToRestake)]
+    #[storage_mapper("rewardsToRestake")]
+    fn rewards_to_restake(&self) -> SingleValueMapper<BigUint>;
+}
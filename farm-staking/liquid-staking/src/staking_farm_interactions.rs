@@ -0,0 +1,93 @@
+elrond_wasm::imports!();
+
+use farm::base_functions::ExitFarmResultType;
+use farm_staking::{
+    compound_stake_farm_rewards::ProxyTrait as _, stake_farm::ProxyTrait as _,
+    unbond_farm::ProxyTrait as _, unstake_farm::ProxyTrait as _,
+};
+
+pub struct StakingFarmUnstakeResult<M: ManagedTypeApi> {
+    pub unbond_tokens: EsdtTokenPayment<M>,
+    pub rewards: EsdtTokenPayment<M>,
+}
+
+#[elrond_wasm::module]
+pub trait StakingFarmInteractionsModule {
+    fn staking_farm_enter(
+        &self,
+        staking_tokens: EsdtTokenPayment,
+        opt_current_position: Option<EsdtTokenPayment>,
+    ) -> EsdtTokenPayment {
+        let mut payments = ManagedVec::from_single_item(staking_tokens);
+        if let Some(current_position) = opt_current_position {
+            payments.push(current_position);
+        }
+
+        let staking_farm_address = self.staking_farm_address().get();
+        self.staking_farm_proxy_obj(staking_farm_address)
+            .stake_farm_endpoint()
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context()
+    }
+
+    fn staking_farm_compound(&self, position: EsdtTokenPayment) -> EsdtTokenPayment {
+        let staking_farm_address = self.staking_farm_address().get();
+        self.staking_farm_proxy_obj(staking_farm_address)
+            .compound_rewards()
+            .add_esdt_token_transfer(
+                position.token_identifier,
+                position.token_nonce,
+                position.amount,
+            )
+            .execute_on_dest_context()
+    }
+
+    fn staking_farm_unstake(
+        &self,
+        farm_tokens: EsdtTokenPayment,
+    ) -> StakingFarmUnstakeResult<Self::Api> {
+        let staking_farm_address = self.staking_farm_address().get();
+        let unstake_result: ExitFarmResultType<Self::Api> = self
+            .staking_farm_proxy_obj(staking_farm_address)
+            .unstake_farm()
+            .add_esdt_token_transfer(
+                farm_tokens.token_identifier,
+                farm_tokens.token_nonce,
+                farm_tokens.amount,
+            )
+            .execute_on_dest_context();
+        let (unbond_tokens, rewards) = unstake_result.into_tuple();
+
+        StakingFarmUnstakeResult {
+            unbond_tokens,
+            rewards,
+        }
+    }
+
+    fn staking_farm_unbond(&self, unbond_tokens: EsdtTokenPayment) -> EsdtTokenPayment {
+        let staking_farm_address = self.staking_farm_address().get();
+        self.staking_farm_proxy_obj(staking_farm_address)
+            .unbond_farm()
+            .add_esdt_token_transfer(
+                unbond_tokens.token_identifier,
+                unbond_tokens.token_nonce,
+                unbond_tokens.amount,
+            )
+            .execute_on_dest_context()
+    }
+
+    #[proxy]
+    fn staking_farm_proxy_obj(&self, sc_address: ManagedAddress) -> farm_staking::Proxy<Self::Api>;
+
+    #[view(getStakingFarmAddress)]
+    #[storage_mapper("stakingFarmAddress")]
+    fn staking_farm_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getStakingTokenId)]
+    #[storage_mapper("stakingTokenId")]
+    fn staking_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getStakingFarmTokenId)]
+    #[storage_mapper("stakingFarmTokenId")]
+    fn staking_farm_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+}
@@ -0,0 +1,92 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::Epoch;
+use farm_staking::token_attributes::UnbondSftAttributes;
+
+pub const MAX_PENDING_WITHDRAWALS: usize = 10;
+
+#[derive(
+    ManagedVecItem,
+    TypeAbi,
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    Clone,
+    PartialEq,
+    Debug,
+)]
+pub struct WithdrawalRequest<M: ManagedTypeApi> {
+    pub unbond_tokens: EsdtTokenPayment<M>,
+    pub unlock_epoch: Epoch,
+}
+
+#[elrond_wasm::module]
+pub trait WithdrawalQueueModule:
+    crate::staking_farm_interactions::StakingFarmInteractionsModule + token_send::TokenSendModule
+{
+    /// Unbonds all the caller's withdrawal requests that reached their unlock epoch,
+    /// and sends the staking tokens to the caller.
+    #[endpoint(withdraw)]
+    fn withdraw(&self) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        let requests_mapper = self.pending_withdrawals(&caller);
+        let requests = requests_mapper.get();
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut remaining_requests = ManagedVec::<Self::Api, WithdrawalRequest<Self::Api>>::new();
+        let mut total_withdrawn = BigUint::zero();
+        for request in &requests {
+            if current_epoch < request.unlock_epoch {
+                remaining_requests.push(request);
+                continue;
+            }
+
+            let staking_tokens = self.staking_farm_unbond(request.unbond_tokens);
+            total_withdrawn += staking_tokens.amount;
+        }
+        require!(total_withdrawn > 0, "Nothing to withdraw");
+
+        if remaining_requests.is_empty() {
+            requests_mapper.clear();
+        } else {
+            requests_mapper.set(&remaining_requests);
+        }
+
+        let staking_token_id = self.staking_token_id().get();
+        let withdrawn_tokens = EsdtTokenPayment::new(staking_token_id, 0, total_withdrawn);
+        self.send_payment_non_zero(&caller, &withdrawn_tokens);
+
+        withdrawn_tokens
+    }
+
+    fn add_withdrawal_request(&self, user: &ManagedAddress, unbond_tokens: EsdtTokenPayment) {
+        let own_sc_address = self.blockchain().get_sc_address();
+        let unbond_token_data = self.blockchain().get_esdt_token_data(
+            &own_sc_address,
+            &unbond_tokens.token_identifier,
+            unbond_tokens.token_nonce,
+        );
+        let attributes: UnbondSftAttributes = unbond_token_data.decode_attributes();
+
+        self.pending_withdrawals(user).update(|requests| {
+            require!(
+                requests.len() < MAX_PENDING_WITHDRAWALS,
+                "Too many pending withdrawals"
+            );
+
+            requests.push(WithdrawalRequest {
+                unbond_tokens,
+                unlock_epoch: attributes.unlock_epoch,
+            });
+        });
+    }
+
+    #[view(getPendingWithdrawals)]
+    #[storage_mapper("pendingWithdrawals")]
+    fn pending_withdrawals(
+        &self,
+        user: &ManagedAddress,
+    ) -> SingleValueMapper<ManagedVec<WithdrawalRequest<Self::Api>>>;
+}
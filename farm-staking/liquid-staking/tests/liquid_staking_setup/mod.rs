@@ -0,0 +1,200 @@
+use elrond_wasm::storage::mappers::StorageTokenWrapper;
+use elrond_wasm::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
+use elrond_wasm_debug::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint,
+    testing_framework::{BlockchainStateWrapper, ContractObjWrapper, TxResult},
+    DebugApi,
+};
+
+use ::config::ConfigModule;
+use farm_staking::custom_rewards::CustomRewardsModule;
+use farm_staking::*;
+use farm_token::FarmTokenModule;
+use liquid_staking::share_token::ShareTokenModule;
+use liquid_staking::withdrawal_queue::WithdrawalQueueModule;
+use liquid_staking::*;
+use pausable::{PausableModule, State};
+
+pub const STAKING_FARM_WASM_PATH: &str = "farm-staking/output/farm-staking.wasm";
+pub const LIQUID_STAKING_WASM_PATH: &str = "liquid-staking/output/liquid-staking.wasm";
+
+pub const STAKING_TOKEN_ID: &[u8] = b"RIDE-abcdef";
+pub const STAKING_FARM_TOKEN_ID: &[u8] = b"STKFARM-abcdef";
+pub const SHARE_TOKEN_ID: &[u8] = b"LSRIDE-abcdef";
+
+pub const DIVISION_SAFETY_CONSTANT: u64 = 1_000_000_000_000;
+pub const MAX_APR: u64 = 5_000; // 50%
+pub const UNBOND_EPOCHS: u64 = 10;
+pub const PER_BLOCK_REWARD_AMOUNT: u64 = 1_000;
+pub const REWARD_CAPACITY: u64 = 1_000_000_000_000;
+pub const USER_BALANCE: u64 = 1_000_000_000_000;
+
+pub struct LiquidStakingSetup<StakingFarmObjBuilder, LiquidStakingObjBuilder>
+where
+    StakingFarmObjBuilder: 'static + Copy + Fn() -> farm_staking::ContractObj<DebugApi>,
+    LiquidStakingObjBuilder: 'static + Copy + Fn() -> liquid_staking::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub first_user: Address,
+    pub second_user: Address,
+    pub staking_farm_wrapper:
+        ContractObjWrapper<farm_staking::ContractObj<DebugApi>, StakingFarmObjBuilder>,
+    pub liquid_staking_wrapper:
+        ContractObjWrapper<liquid_staking::ContractObj<DebugApi>, LiquidStakingObjBuilder>,
+}
+
+impl<StakingFarmObjBuilder, LiquidStakingObjBuilder>
+    LiquidStakingSetup<StakingFarmObjBuilder, LiquidStakingObjBuilder>
+where
+    StakingFarmObjBuilder: 'static + Copy + Fn() -> farm_staking::ContractObj<DebugApi>,
+    LiquidStakingObjBuilder: 'static + Copy + Fn() -> liquid_staking::ContractObj<DebugApi>,
+{
+    pub fn new(
+        staking_farm_builder: StakingFarmObjBuilder,
+        liquid_staking_builder: LiquidStakingObjBuilder,
+    ) -> Self {
+        let rust_zero = rust_biguint!(0u64);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner_address = b_mock.create_user_account(&rust_zero);
+        let first_user = b_mock.create_user_account(&rust_zero);
+        let second_user = b_mock.create_user_account(&rust_zero);
+
+        let staking_farm_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_address),
+            staking_farm_builder,
+            STAKING_FARM_WASM_PATH,
+        );
+        b_mock
+            .execute_tx(&owner_address, &staking_farm_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(STAKING_TOKEN_ID),
+                    managed_biguint!(DIVISION_SAFETY_CONSTANT),
+                    managed_biguint!(MAX_APR),
+                    UNBOND_EPOCHS,
+                    ManagedAddress::<DebugApi>::zero(),
+                    MultiValueEncoded::new(),
+                );
+
+                sc.farm_token()
+                    .set_token_id(managed_token_id!(STAKING_FARM_TOKEN_ID));
+
+                sc.state().set(State::Active);
+                sc.produce_rewards_enabled().set(true);
+                sc.per_block_reward_amount()
+                    .set(&managed_biguint!(PER_BLOCK_REWARD_AMOUNT));
+                sc.last_reward_block_nonce().set(0);
+                sc.reward_capacity().set(&managed_biguint!(REWARD_CAPACITY));
+            })
+            .assert_ok();
+
+        b_mock.set_esdt_balance(
+            staking_farm_wrapper.address_ref(),
+            STAKING_TOKEN_ID,
+            &rust_biguint!(REWARD_CAPACITY),
+        );
+        b_mock.set_esdt_local_roles(
+            staking_farm_wrapper.address_ref(),
+            STAKING_FARM_TOKEN_ID,
+            &[
+                EsdtLocalRole::NftCreate,
+                EsdtLocalRole::NftAddQuantity,
+                EsdtLocalRole::NftBurn,
+            ][..],
+        );
+
+        let liquid_staking_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_address),
+            liquid_staking_builder,
+            LIQUID_STAKING_WASM_PATH,
+        );
+        b_mock
+            .execute_tx(&owner_address, &liquid_staking_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_address!(staking_farm_wrapper.address_ref()),
+                    managed_token_id!(STAKING_TOKEN_ID),
+                    managed_token_id!(STAKING_FARM_TOKEN_ID),
+                );
+
+                sc.share_token()
+                    .set_token_id(managed_token_id!(SHARE_TOKEN_ID));
+            })
+            .assert_ok();
+
+        b_mock.set_esdt_local_roles(
+            liquid_staking_wrapper.address_ref(),
+            SHARE_TOKEN_ID,
+            &[EsdtLocalRole::Mint, EsdtLocalRole::Burn][..],
+        );
+
+        b_mock.set_esdt_balance(&first_user, STAKING_TOKEN_ID, &rust_biguint!(USER_BALANCE));
+        b_mock.set_esdt_balance(&second_user, STAKING_TOKEN_ID, &rust_biguint!(USER_BALANCE));
+
+        LiquidStakingSetup {
+            b_mock,
+            owner_address,
+            first_user,
+            second_user,
+            staking_farm_wrapper,
+            liquid_staking_wrapper,
+        }
+    }
+
+    pub fn stake(&mut self, user: &Address, amount: u64, expected_share_tokens: u64) {
+        self.b_mock
+            .execute_esdt_transfer(
+                user,
+                &self.liquid_staking_wrapper,
+                STAKING_TOKEN_ID,
+                0,
+                &rust_biguint!(amount),
+                |sc| {
+                    let share_tokens = sc.stake();
+                    assert_eq!(share_tokens.amount, managed_biguint!(expected_share_tokens));
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn unstake(&mut self, user: &Address, share_token_amount: u64) {
+        self.b_mock
+            .execute_esdt_transfer(
+                user,
+                &self.liquid_staking_wrapper,
+                SHARE_TOKEN_ID,
+                0,
+                &rust_biguint!(share_token_amount),
+                |sc| {
+                    let _ = sc.unstake();
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn withdraw(&mut self, user: &Address) -> TxResult {
+        self.b_mock.execute_tx(
+            user,
+            &self.liquid_staking_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.withdraw();
+            },
+        )
+    }
+
+    pub fn get_staking_token_amount_for_shares(&mut self, share_token_amount: u64) -> u64 {
+        let mut result = 0;
+        self.b_mock
+            .execute_query(&self.liquid_staking_wrapper, |sc| {
+                result = sc
+                    .get_staking_token_amount_for_shares(managed_biguint!(share_token_amount))
+                    .to_u64()
+                    .unwrap();
+            })
+            .assert_ok();
+
+        result
+    }
+}
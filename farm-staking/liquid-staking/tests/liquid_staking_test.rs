@@ -0,0 +1,71 @@
+pub mod liquid_staking_setup;
+
+use elrond_wasm_debug::{rust_biguint, DebugApi};
+use liquid_staking::LiquidStaking;
+use liquid_staking_setup::*;
+
+#[test]
+fn liquid_staking_setup_test() {
+    let _ = LiquidStakingSetup::new(farm_staking::contract_obj, liquid_staking::contract_obj);
+}
+
+#[test]
+fn liquid_staking_stake_compound_unstake_test() {
+    let _ = DebugApi::dummy();
+    let mut setup =
+        LiquidStakingSetup::new(farm_staking::contract_obj, liquid_staking::contract_obj);
+
+    // first user stakes at a 1:1 rate
+    let first_user = setup.first_user.clone();
+    setup.stake(&first_user, USER_BALANCE, USER_BALANCE);
+
+    // 10 blocks * 1_000 = 10_000 rewards, below the APR cap
+    setup.b_mock.set_block_nonce(10);
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.liquid_staking_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.compound_rewards();
+            },
+        )
+        .assert_ok();
+    assert_eq!(
+        setup.get_staking_token_amount_for_shares(USER_BALANCE),
+        USER_BALANCE + 10_000
+    );
+
+    // second user receives less shares for the same amount
+    // 1_000_000_000_000 * 1_000_000_000_000 / 1_000_000_010_000
+    let second_user = setup.second_user.clone();
+    let second_user_shares = 999_999_990_000;
+    setup.stake(&second_user, USER_BALANCE, second_user_shares);
+
+    // first user exits with the compounded rewards
+    setup.unstake(&first_user, USER_BALANCE);
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, SHARE_TOKEN_ID, &rust_biguint!(0));
+    assert_eq!(
+        setup.get_staking_token_amount_for_shares(second_user_shares),
+        USER_BALANCE
+    );
+
+    setup
+        .withdraw(&first_user)
+        .assert_user_error("Nothing to withdraw");
+
+    setup.b_mock.set_block_epoch(UNBOND_EPOCHS);
+    setup.withdraw(&first_user).assert_ok();
+    setup.b_mock.check_esdt_balance(
+        &first_user,
+        STAKING_TOKEN_ID,
+        &rust_biguint!(USER_BALANCE + 10_000),
+    );
+
+    setup
+        .withdraw(&first_user)
+        .assert_user_error("Nothing to withdraw");
+}
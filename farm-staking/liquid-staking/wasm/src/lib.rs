@@ -0,0 +1,28 @@
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+#![no_std]
+
+elrond_wasm_node::wasm_endpoints! {
+    liquid_staking
+    (
+        callBack
+        compoundRewards
+        getPendingWithdrawals
+        getRewardsToRestake
+        getShareTokenAmountForStake
+        getShareTokenId
+        getShareTokenSupply
+        getStakingFarmAddress
+        getStakingFarmTokenId
+        getStakingPosition
+        getStakingTokenAmountForShares
+        getStakingTokenId
+        getTotalStakedAmount
+        registerShareToken
+        stake
+        unstake
+        withdraw
+    )
+}
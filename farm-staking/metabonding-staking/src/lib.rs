@@ -4,6 +4,7 @@ elrond_wasm::imports!();
 
 pub mod events;
 pub mod locked_asset_token;
+pub mod weekly_snapshots;
 
 use locked_asset_token::UserEntry;
 
@@ -12,7 +13,10 @@ pub const UNBOND_EPOCHS: u64 = 3;
 
 #[elrond_wasm::contract]
 pub trait MetabondingStaking:
-    locked_asset_token::LockedAssetTokenModule + events::EventsModule
+    locked_asset_token::LockedAssetTokenModule
+    + events::EventsModule
+    + weekly_snapshots::WeeklySnapshotsModule
+    + week_timekeeping::WeekTimekeepingModule
 {
     #[init]
     fn init(
@@ -24,17 +28,25 @@ pub trait MetabondingStaking:
             .set_if_empty(&locked_asset_token_id);
         self.locked_asset_factory_address()
             .set_if_empty(&locked_asset_factory_address);
+
+        self.init_weekly_snapshots();
     }
 
     #[payable("*")]
     #[endpoint(stakeLockedAsset)]
     fn stake_locked_asset(&self) {
         let payments = self.call_value().all_esdt_transfers();
-        self.require_all_locked_asset_payments(&payments);
+        let token_id = self.require_all_locked_asset_payments(&payments);
 
         let caller = self.blockchain().get_caller();
         let entry_mapper = self.entry_for_user(&caller);
-        let new_entry = self.create_new_entry_by_merging_tokens(&entry_mapper, payments);
+        let prev_stake_amount = self.get_staked_amount_for_user(caller.clone());
+        let new_entry =
+            self.create_new_entry_by_merging_tokens(&caller, &entry_mapper, &token_id, payments);
+        if token_id != self.locked_asset_token_id().get() {
+            self.entry_token_id_for_user(&caller).set(&token_id);
+        }
+        self.record_stake_update(&caller, &prev_stake_amount, &new_entry.stake_amount);
 
         self.total_locked_asset_supply()
             .update(|total_supply| *total_supply += new_entry.get_total_amount());
@@ -58,9 +70,11 @@ pub trait MetabondingStaking:
         );
 
         let current_epoch = self.blockchain().get_block_epoch();
+        let prev_stake_amount = user_entry.stake_amount.clone();
         user_entry.unbond_epoch = current_epoch + UNBOND_EPOCHS;
         user_entry.stake_amount -= &amount;
         user_entry.unstake_amount += amount;
+        self.record_stake_update(&caller, &prev_stake_amount, &user_entry.stake_amount);
 
         self.unstake_event(&caller, &user_entry);
 
@@ -86,8 +100,10 @@ pub trait MetabondingStaking:
         self.total_locked_asset_supply()
             .update(|total_supply| *total_supply -= &unstake_amount);
 
+        let token_id = self.get_entry_token_id(&caller);
         let opt_entry_after_action = if user_entry.stake_amount == 0 {
             entry_mapper.clear();
+            self.entry_token_id_for_user(&caller).clear();
             self.user_list().swap_remove(&caller);
 
             None
@@ -99,15 +115,47 @@ pub trait MetabondingStaking:
             Some(&user_entry)
         };
 
+        self.send()
+            .direct_esdt(&caller, &token_id, user_entry.token_nonce, &unstake_amount);
+
+        self.unbond_event(&caller, opt_entry_after_action);
+    }
+
+    /// Withdraws the whole legacy locked asset position, including the unstaked amount, without an unbond period.
+    /// The tokens can then be migrated through the energy factory, and staked again.
+    #[endpoint(migrateLegacyStake)]
+    fn migrate_legacy_stake(&self) {
+        require!(
+            !self.energy_locked_token_id().is_empty(),
+            "Migration not enabled"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let entry_mapper = self.entry_for_user(&caller);
+        require!(!entry_mapper.is_empty(), "Must stake first");
+        require!(
+            self.entry_token_id_for_user(&caller).is_empty(),
+            "Nothing to migrate"
+        );
+
+        let user_entry: UserEntry<Self::Api> = entry_mapper.get();
+        let total_amount = user_entry.get_total_amount();
+        self.record_stake_update(&caller, &user_entry.stake_amount, &BigUint::zero());
+        self.total_locked_asset_supply()
+            .update(|total_supply| *total_supply -= &total_amount);
+
+        entry_mapper.clear();
+        self.user_list().swap_remove(&caller);
+
         let locked_asset_token_id = self.locked_asset_token_id().get();
         self.send().direct_esdt(
             &caller,
             &locked_asset_token_id,
             user_entry.token_nonce,
-            &unstake_amount,
+            &total_amount,
         );
 
-        self.unbond_event(&caller, opt_entry_after_action);
+        self.unbond_event(&caller, None);
     }
 
     #[view(getStakedAmountForUser)]
@@ -1,6 +1,7 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
+use energy_factory::token_merging::ProxyTrait as _;
 use factory::locked_asset_token_merge::ProxyTrait as _;
 
 pub type PaymentsVec<M> = ManagedVec<M, EsdtTokenPayment<M>>;
@@ -30,42 +31,98 @@ impl<M: ManagedTypeApi> UserEntry<M> {
 
 #[elrond_wasm::module]
 pub trait LockedAssetTokenModule {
-    fn require_all_locked_asset_payments(&self, payments: &PaymentsVec<Self::Api>) {
+    /// Enables staking the energy factory's locked token.
+    /// From this point on, the legacy locked asset token can no longer be staked,
+    /// and existing legacy stakes can be withdrawn through migrateLegacyStake.
+    /// This contract must be whitelisted in the energy factory, for merging tokens and for the token transfers.
+    #[only_owner]
+    #[endpoint(setEnergyFactoryLockedToken)]
+    fn set_energy_factory_locked_token(
+        &self,
+        energy_factory_address: ManagedAddress,
+        locked_token_id: TokenIdentifier,
+    ) {
+        require!(
+            self.blockchain().is_smart_contract(&energy_factory_address),
+            "Invalid energy factory address"
+        );
+        require!(
+            locked_token_id.is_valid_esdt_identifier(),
+            "Invalid locked token ID"
+        );
+
+        self.energy_factory_address().set(&energy_factory_address);
+        self.energy_locked_token_id().set(&locked_token_id);
+    }
+
+    /// Returns the token ID of the payments, which must be all of the same accepted token
+    fn require_all_locked_asset_payments(
+        &self,
+        payments: &PaymentsVec<Self::Api>,
+    ) -> TokenIdentifier {
         require!(!payments.is_empty(), "No payments");
 
-        let locked_asset_token_id = self.locked_asset_token_id().get();
+        let token_id = payments.get(0).token_identifier;
         for p in payments {
+            require!(p.token_identifier == token_id, "Invalid payment");
+        }
+
+        let energy_locked_token_mapper = self.energy_locked_token_id();
+        if energy_locked_token_mapper.is_empty() {
             require!(
-                p.token_identifier == locked_asset_token_id,
+                token_id == self.locked_asset_token_id().get(),
                 "Invalid payment"
             );
+        } else {
+            require!(
+                token_id == energy_locked_token_mapper.get(),
+                "Only the energy factory locked token can be staked"
+            );
+        }
+
+        token_id
+    }
+
+    fn get_entry_token_id(&self, user_address: &ManagedAddress) -> TokenIdentifier {
+        let entry_token_id_mapper = self.entry_token_id_for_user(user_address);
+        if entry_token_id_mapper.is_empty() {
+            self.locked_asset_token_id().get()
+        } else {
+            entry_token_id_mapper.get()
         }
     }
 
     fn create_new_entry_by_merging_tokens(
         &self,
+        user_address: &ManagedAddress,
         entry_mapper: &SingleValueMapper<UserEntry<Self::Api>>,
+        token_id: &TokenIdentifier,
         mut new_tokens: PaymentsVec<Self::Api>,
     ) -> UserEntry<Self::Api> {
         if entry_mapper.is_empty() {
-            let merged_tokens = self.merge_locked_asset_tokens(new_tokens);
+            let merged_tokens = self.merge_locked_asset_tokens(user_address, token_id, new_tokens);
 
             return UserEntry::new(merged_tokens.token_nonce, merged_tokens.amount);
         }
 
+        require!(
+            &self.get_entry_token_id(user_address) == token_id,
+            "Must migrate legacy stake first"
+        );
+
         let mut prev_entry: UserEntry<Self::Api> = entry_mapper.get();
         let prev_entry_total_tokens = prev_entry.get_total_amount();
         self.total_locked_asset_supply()
             .update(|total_supply| *total_supply -= &prev_entry_total_tokens);
 
         let prev_entry_as_payment = EsdtTokenPayment::new(
-            self.locked_asset_token_id().get(),
+            token_id.clone(),
             prev_entry.token_nonce,
             prev_entry_total_tokens,
         );
         new_tokens.push(prev_entry_as_payment);
 
-        let merged_tokens = self.merge_locked_asset_tokens(new_tokens);
+        let merged_tokens = self.merge_locked_asset_tokens(user_address, token_id, new_tokens);
         prev_entry.token_nonce = merged_tokens.token_nonce;
         prev_entry.stake_amount = &merged_tokens.amount - &prev_entry.unstake_amount;
 
@@ -74,15 +131,27 @@ pub trait LockedAssetTokenModule {
 
     fn merge_locked_asset_tokens(
         &self,
+        user_address: &ManagedAddress,
+        token_id: &TokenIdentifier,
         tokens: PaymentsVec<Self::Api>,
     ) -> EsdtTokenPayment<Self::Api> {
         if tokens.len() == 1 {
             return tokens.get(0);
         }
 
-        let locked_asset_factory_address = self.locked_asset_factory_address().get();
-        self.locked_asset_factory_proxy(locked_asset_factory_address)
-            .merge_tokens()
+        if token_id == &self.locked_asset_token_id().get() {
+            let locked_asset_factory_address = self.locked_asset_factory_address().get();
+            return self
+                .locked_asset_factory_proxy(locked_asset_factory_address)
+                .merge_tokens()
+                .with_multi_token_transfer(tokens)
+                .execute_on_dest_context();
+        }
+
+        // the energy is updated for the user, as the merge changes the unlock epoch
+        let energy_factory_address = self.energy_factory_address().get();
+        self.energy_factory_proxy(energy_factory_address)
+            .merge_tokens_endpoint(OptionalValue::Some(user_address.clone()))
             .with_multi_token_transfer(tokens)
             .execute_on_dest_context()
     }
@@ -92,6 +161,9 @@ pub trait LockedAssetTokenModule {
     #[proxy]
     fn locked_asset_factory_proxy(&self, sc_address: ManagedAddress) -> factory::Proxy<Self::Api>;
 
+    #[proxy]
+    fn energy_factory_proxy(&self, sc_address: ManagedAddress) -> energy_factory::Proxy<Self::Api>;
+
     // storage
 
     #[view(getLockedAssetTokenId)]
@@ -102,6 +174,21 @@ pub trait LockedAssetTokenModule {
     #[storage_mapper("lockedAssetFactoryAddress")]
     fn locked_asset_factory_address(&self) -> SingleValueMapper<ManagedAddress>;
 
+    #[view(getEnergyFactoryAddress)]
+    #[storage_mapper("energyFactoryAddress")]
+    fn energy_factory_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getEnergyLockedTokenId)]
+    #[storage_mapper("energyLockedTokenId")]
+    fn energy_locked_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    /// Empty for entries of the legacy locked asset token
+    #[storage_mapper("entryTokenIdForUser")]
+    fn entry_token_id_for_user(
+        &self,
+        user_address: &ManagedAddress,
+    ) -> SingleValueMapper<TokenIdentifier>;
+
     #[view(getTotalLockedAssetSupply)]
     #[storage_mapper("totalLockedAssetSupply")]
     fn total_locked_asset_supply(&self) -> SingleValueMapper<BigUint>;
@@ -0,0 +1,208 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use week_timekeeping::{Week, FIRST_WEEK};
+
+use crate::locked_asset_token::UserEntry;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Debug, PartialEq)]
+pub struct StakeSnapshot<M: ManagedTypeApi> {
+    pub week: Week,
+    pub stake_amount: BigUint<M>,
+}
+
+/// Each history holds one entry per week with stake changes, sorted by week.
+/// The entry for a week is the staked amount at the end of that week,
+/// and is valid for all the following weeks, until the next entry.
+#[elrond_wasm::module]
+pub trait WeeklySnapshotsModule:
+    crate::locked_asset_token::LockedAssetTokenModule + week_timekeeping::WeekTimekeepingModule
+{
+    /// Positions staked before the snapshots were introduced are added to the total
+    /// either by the owner, through seedTotalStake, or on the user's first action.
+    /// Until all users were processed, the total stake views are not available.
+    fn init_weekly_snapshots(&self) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        self.first_week_start_epoch().set_if_empty(current_epoch);
+
+        let total_history = self.total_stake_history();
+        if !total_history.is_empty() {
+            return;
+        }
+
+        self.push_or_update_snapshot(&total_history, FIRST_WEEK, BigUint::zero());
+        self.total_stake_seeding_index().set(self.user_list().len());
+    }
+
+    /// Processes up to max_users of the user list, starting from the end,
+    /// and adds the stake of those without a history yet to the total.
+    /// Returns the number of users left to process.
+    #[only_owner]
+    #[endpoint(seedTotalStake)]
+    fn seed_total_stake(&self, max_users: usize) -> usize {
+        let user_list = self.user_list();
+        let seeding_index_mapper = self.total_stake_seeding_index();
+        let mut index = core::cmp::min(seeding_index_mapper.get(), user_list.len());
+        let mut seeded_amount = BigUint::zero();
+        let mut processed_users = 0;
+        while index > 0 && processed_users < max_users {
+            let user_address = user_list.get_by_index(index);
+            let user_history = self.user_stake_history(&user_address);
+            if user_history.is_empty() {
+                let entry: UserEntry<Self::Api> = self.entry_for_user(&user_address).get();
+                if entry.stake_amount > 0 {
+                    self.push_or_update_snapshot(
+                        &user_history,
+                        FIRST_WEEK,
+                        entry.stake_amount.clone(),
+                    );
+                    seeded_amount += entry.stake_amount;
+                }
+            }
+
+            index -= 1;
+            processed_users += 1;
+        }
+
+        self.add_to_all_snapshots(&self.total_stake_history(), &seeded_amount);
+        seeding_index_mapper.set(index);
+
+        index
+    }
+
+    fn record_stake_update(
+        &self,
+        user_address: &ManagedAddress,
+        prev_stake_amount: &BigUint,
+        new_stake_amount: &BigUint,
+    ) {
+        let current_week = self.get_current_week();
+        let total_history = self.total_stake_history();
+
+        // stake from before the snapshots, not added to the total by seedTotalStake yet
+        let user_history = self.user_stake_history(user_address);
+        if user_history.is_empty() && *prev_stake_amount > 0 {
+            self.push_or_update_snapshot(&user_history, FIRST_WEEK, prev_stake_amount.clone());
+            self.add_to_all_snapshots(&total_history, prev_stake_amount);
+        }
+        self.push_or_update_snapshot(&user_history, current_week, new_stake_amount.clone());
+
+        let mut total_stake_amount = self.get_last_snapshot_amount(&total_history);
+        total_stake_amount -= prev_stake_amount;
+        total_stake_amount += new_stake_amount;
+        self.push_or_update_snapshot(&total_history, current_week, total_stake_amount);
+    }
+
+    /// The stake from before the snapshots counts for all the weeks
+    fn add_to_all_snapshots(
+        &self,
+        history: &VecMapper<StakeSnapshot<Self::Api>>,
+        amount: &BigUint,
+    ) {
+        if amount == &0 {
+            return;
+        }
+
+        for index in 1..=history.len() {
+            let mut snapshot = history.get(index);
+            snapshot.stake_amount += amount;
+            history.set(index, &snapshot);
+        }
+    }
+
+    fn push_or_update_snapshot(
+        &self,
+        history: &VecMapper<StakeSnapshot<Self::Api>>,
+        week: Week,
+        stake_amount: BigUint,
+    ) {
+        let snapshot = StakeSnapshot { week, stake_amount };
+        let history_len = history.len();
+        if history_len > 0 && history.get(history_len).week == week {
+            history.set(history_len, &snapshot);
+        } else {
+            history.push(&snapshot);
+        }
+    }
+
+    fn get_last_snapshot_amount(&self, history: &VecMapper<StakeSnapshot<Self::Api>>) -> BigUint {
+        let history_len = history.len();
+        if history_len > 0 {
+            history.get(history_len).stake_amount
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    /// Binary search for the last entry recorded up to the given week
+    fn get_snapshot_amount_for_week(
+        &self,
+        history: &VecMapper<StakeSnapshot<Self::Api>>,
+        week: Week,
+    ) -> BigUint {
+        let mut low = 1;
+        let mut high = history.len();
+        let mut opt_found_amount = None;
+        while low <= high {
+            let mid = (low + high) / 2;
+            let snapshot = history.get(mid);
+            if snapshot.week <= week {
+                opt_found_amount = Some(snapshot.stake_amount);
+                low = mid + 1;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        opt_found_amount.unwrap_or_else(BigUint::zero)
+    }
+
+    fn require_valid_snapshot_week(&self, week: Week) {
+        let current_week = self.get_current_week();
+        require!(week >= FIRST_WEEK && week <= current_week, "Invalid week");
+    }
+
+    /// The amount for the current week is not final until the week is over
+    #[view(getUserStakeForWeek)]
+    fn get_user_stake_for_week(&self, user_address: ManagedAddress, week: Week) -> BigUint {
+        self.require_valid_snapshot_week(week);
+
+        let user_history = self.user_stake_history(&user_address);
+        if !user_history.is_empty() {
+            return self.get_snapshot_amount_for_week(&user_history, week);
+        }
+
+        // no changes since the snapshots were introduced
+        let entry_mapper = self.entry_for_user(&user_address);
+        if entry_mapper.is_empty() {
+            BigUint::zero()
+        } else {
+            entry_mapper.get().stake_amount
+        }
+    }
+
+    /// The amount for the current week is not final until the week is over
+    #[view(getTotalStakeForWeek)]
+    fn get_total_stake_for_week(&self, week: Week) -> BigUint {
+        self.require_valid_snapshot_week(week);
+        require!(
+            self.total_stake_seeding_index().get() == 0,
+            "Total stake seeding in progress"
+        );
+
+        self.get_snapshot_amount_for_week(&self.total_stake_history(), week)
+    }
+
+    #[storage_mapper("userStakeHistory")]
+    fn user_stake_history(
+        &self,
+        user_address: &ManagedAddress,
+    ) -> VecMapper<StakeSnapshot<Self::Api>>;
+
+    #[storage_mapper("totalStakeHistory")]
+    fn total_stake_history(&self) -> VecMapper<StakeSnapshot<Self::Api>>;
+
+    #[view(getTotalStakeSeedingIndex)]
+    #[storage_mapper("totalStakeSeedingIndex")]
+    fn total_stake_seeding_index(&self) -> SingleValueMapper<usize>;
+}
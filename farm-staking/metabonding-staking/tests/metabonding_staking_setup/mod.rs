@@ -11,12 +11,15 @@ use elrond_wasm_debug::{
 use elrond_wasm_modules::pause::PauseModule;
 use factory::locked_asset::LockedAssetModule;
 use factory::*;
+use metabonding_staking::locked_asset_token::LockedAssetTokenModule;
 use metabonding_staking::MetabondingStaking;
 
 pub const METABONDING_STAKING_WASM_PATH: &str = "1.wasm";
 pub const LOCKED_ASSET_FACTORY_WASM_PATH: &str = "2.wasm";
+pub const ENERGY_FACTORY_WASM_PATH: &str = "3.wasm";
 pub const ASSET_TOKEN_ID: &[u8] = b"MEX-123456";
 pub const LOCKED_ASSET_TOKEN_ID: &[u8] = b"LKMEX-123456";
+pub const ENERGY_LOCKED_TOKEN_ID: &[u8] = b"XMEX-123456";
 
 pub struct MetabondingStakingSetup<MetabondingStakingObjBuilder, LockedAssetFactoryObjBuilder>
 where
@@ -247,4 +250,50 @@ where
             },
         )
     }
+
+    pub fn call_set_energy_factory_locked_token(&mut self) -> TxResult {
+        let energy_factory_address = self.laf_wrapper.address_ref().clone();
+        self.call_set_energy_factory_locked_token_with_address(&energy_factory_address)
+    }
+
+    pub fn call_set_energy_factory_locked_token_with_address(
+        &mut self,
+        energy_factory_address: &Address,
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_address,
+            &self.mbs_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_energy_factory_locked_token(
+                    managed_address!(energy_factory_address),
+                    managed_token_id!(ENERGY_LOCKED_TOKEN_ID),
+                );
+            },
+        )
+    }
+
+    pub fn call_stake_energy_locked_token(&mut self, token_nonce: u64, amount: u64) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            &self.user_address,
+            &self.mbs_wrapper,
+            ENERGY_LOCKED_TOKEN_ID,
+            token_nonce,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.stake_locked_asset();
+            },
+        )
+    }
+
+    pub fn call_migrate_legacy_stake(&mut self) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.user_address,
+            &self.mbs_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.migrate_legacy_stake();
+            },
+        )
+    }
 }
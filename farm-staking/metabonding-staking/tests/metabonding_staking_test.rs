@@ -1,10 +1,23 @@
 pub mod metabonding_staking_setup;
-use elrond_wasm_debug::{managed_address, managed_biguint, rust_biguint, tx_mock::TxInputESDT};
+use elrond_wasm::{
+    elrond_codec::{multi_types::OptionalValue, Empty},
+    storage::mappers::StorageTokenWrapper,
+    types::{EgldOrEsdtTokenIdentifier, EsdtLocalRole, MultiValueEncoded},
+};
+use elrond_wasm_debug::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, tx_mock::TxInputESDT,
+    DebugApi,
+};
+use elrond_wasm_modules::pause::PauseModule;
+use energy_factory::{energy::EnergyModule, SimpleLockEnergy};
 use metabonding_staking::{
     locked_asset_token::{LockedAssetTokenModule, UserEntry},
+    weekly_snapshots::WeeklySnapshotsModule,
     UNBOND_EPOCHS,
 };
 use metabonding_staking_setup::*;
+use sc_whitelist_module::SCWhitelistModule;
+use simple_lock::locked_token::{LockedTokenAttributes, LockedTokenModule};
 
 #[test]
 fn test_init() {
@@ -336,3 +349,380 @@ fn test_unbond() {
         })
         .assert_ok();
 }
+
+#[test]
+fn test_weekly_snapshots() {
+    let mut setup =
+        MetabondingStakingSetup::new(metabonding_staking::contract_obj, factory::contract_obj);
+
+    // week 1
+    setup.call_stake_locked_asset(3, 100_000_000).assert_ok();
+
+    // week 3
+    setup.b_mock.set_block_epoch(14);
+    setup.call_stake_locked_asset(4, 1_000_000).assert_ok();
+
+    // week 4
+    setup.b_mock.set_block_epoch(21);
+    setup.call_unstake(51_000_000).assert_ok();
+
+    let user_addr = setup.user_address.clone();
+    setup
+        .b_mock
+        .execute_query(&setup.mbs_wrapper, |sc| {
+            let expected_amounts = [100_000_000u64, 100_000_000, 101_000_000, 50_000_000];
+            for (week_index, expected_amount) in expected_amounts.iter().enumerate() {
+                let week = week_index + 1;
+                assert_eq!(
+                    sc.get_user_stake_for_week(managed_address!(&user_addr), week),
+                    managed_biguint!(*expected_amount)
+                );
+                assert_eq!(
+                    sc.get_total_stake_for_week(week),
+                    managed_biguint!(*expected_amount)
+                );
+            }
+        })
+        .assert_ok();
+
+    // unbonding does not change the staked amount
+    setup.b_mock.set_block_epoch(21 + UNBOND_EPOCHS);
+    setup.call_unbond().assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.mbs_wrapper, |sc| {
+            assert_eq!(sc.get_total_stake_for_week(4), managed_biguint!(50_000_000));
+        })
+        .assert_ok();
+
+    // future weeks can't be queried
+    setup
+        .b_mock
+        .execute_query(&setup.mbs_wrapper, |sc| {
+            let _ = sc.get_total_stake_for_week(5);
+        })
+        .assert_user_error("Invalid week");
+}
+
+#[test]
+fn test_seed_total_stake() {
+    let mut setup =
+        MetabondingStakingSetup::new(metabonding_staking::contract_obj, factory::contract_obj);
+
+    let rust_zero = rust_biguint!(0);
+    let owner_addr = setup.owner_address.clone();
+    let user_addr = setup.user_address.clone();
+    let first_legacy_user = setup.b_mock.create_user_account(&rust_zero);
+    let second_legacy_user = setup.b_mock.create_user_account(&rust_zero);
+
+    // positions staked before the snapshots, followed by the upgrade
+    setup
+        .b_mock
+        .execute_tx(&owner_addr, &setup.mbs_wrapper, &rust_zero, |sc| {
+            let legacy_entries = [
+                (&user_addr, 100_000_000u64),
+                (&first_legacy_user, 10_000_000),
+                (&second_legacy_user, 20_000_000),
+            ];
+            for (address, stake_amount) in legacy_entries.iter() {
+                sc.entry_for_user(&managed_address!(address))
+                    .set(&UserEntry::new(3, managed_biguint!(*stake_amount)));
+                let _ = sc.user_list().insert(managed_address!(address));
+            }
+            sc.total_stake_history().clear();
+
+            let locked_asset_token_id = sc.locked_asset_token_id().get();
+            let locked_asset_factory_address = sc.locked_asset_factory_address().get();
+            sc.init(locked_asset_token_id, locked_asset_factory_address);
+
+            assert_eq!(sc.total_stake_seeding_index().get(), 3);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.mbs_wrapper, |sc| {
+            let _ = sc.get_total_stake_for_week(1);
+        })
+        .assert_user_error("Total stake seeding in progress");
+
+    // the first action of a legacy user adds their stake to the total
+    setup.call_unstake(40_000_000).assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&user_addr, &setup.mbs_wrapper, &rust_zero, |sc| {
+            let _ = sc.seed_total_stake(1);
+        })
+        .assert_user_error("Endpoint can only be called by owner");
+
+    setup
+        .b_mock
+        .execute_tx(&owner_addr, &setup.mbs_wrapper, &rust_zero, |sc| {
+            assert_eq!(sc.seed_total_stake(1), 2);
+            assert_eq!(sc.seed_total_stake(10), 0);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.mbs_wrapper, |sc| {
+            assert_eq!(sc.get_total_stake_for_week(1), managed_biguint!(90_000_000));
+            assert_eq!(
+                sc.get_user_stake_for_week(managed_address!(&user_addr), 1),
+                managed_biguint!(60_000_000)
+            );
+            assert_eq!(
+                sc.get_user_stake_for_week(managed_address!(&second_legacy_user), 1),
+                managed_biguint!(20_000_000)
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_migrate_to_energy_locked_token() {
+    let mut setup =
+        MetabondingStakingSetup::new(metabonding_staking::contract_obj, factory::contract_obj);
+    let user_addr = setup.user_address.clone();
+    setup.b_mock.set_nft_balance(
+        &user_addr,
+        ENERGY_LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(50_000_000),
+        &Empty,
+    );
+
+    setup.call_stake_locked_asset(3, 100_000_000).assert_ok();
+    setup.call_unstake(40_000_000).assert_ok();
+
+    setup
+        .call_migrate_legacy_stake()
+        .assert_user_error("Migration not enabled");
+
+    setup.call_set_energy_factory_locked_token().assert_ok();
+
+    // legacy token can no longer be staked
+    setup
+        .call_stake_locked_asset(4, 1_000_000)
+        .assert_user_error("Only the energy factory locked token can be staked");
+    setup
+        .call_stake_energy_locked_token(1, 50_000_000)
+        .assert_user_error("Must migrate legacy stake first");
+
+    // the whole position is returned, without waiting for the unbond period
+    setup.call_migrate_legacy_stake().assert_ok();
+    setup.b_mock.check_nft_balance::<Empty>(
+        &user_addr,
+        LOCKED_ASSET_TOKEN_ID,
+        3,
+        &rust_biguint!(100_000_000),
+        None,
+    );
+    setup
+        .call_migrate_legacy_stake()
+        .assert_user_error("Must stake first");
+
+    setup
+        .call_stake_energy_locked_token(1, 50_000_000)
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_query(&setup.mbs_wrapper, |sc| {
+            let expected_entry = UserEntry::new(1, managed_biguint!(50_000_000));
+            let actual_entry = sc.entry_for_user(&managed_address!(&user_addr)).get();
+            assert_eq!(actual_entry, expected_entry);
+            assert_eq!(
+                sc.total_locked_asset_supply().get(),
+                managed_biguint!(50_000_000)
+            );
+        })
+        .assert_ok();
+
+    setup
+        .call_migrate_legacy_stake()
+        .assert_user_error("Nothing to migrate");
+
+    // unbond returns the energy factory locked token
+    setup.call_unstake(50_000_000).assert_ok();
+    setup.b_mock.set_block_epoch(UNBOND_EPOCHS);
+    setup.call_unbond().assert_ok();
+    setup.b_mock.check_nft_balance::<Empty>(
+        &user_addr,
+        ENERGY_LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(50_000_000),
+        None,
+    );
+}
+
+#[test]
+fn test_stake_energy_locked_token_multiple_payments() {
+    let mut setup =
+        MetabondingStakingSetup::new(metabonding_staking::contract_obj, factory::contract_obj);
+    let rust_zero = rust_biguint!(0u64);
+    let owner_addr = setup.owner_address.clone();
+    let user_addr = setup.user_address.clone();
+    let mbs_addr = setup.mbs_wrapper.address_ref().clone();
+    let laf_addr = setup.laf_wrapper.address_ref().clone();
+
+    let energy_factory_wrapper = setup.b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner_addr),
+        energy_factory::contract_obj,
+        ENERGY_FACTORY_WASM_PATH,
+    );
+    setup
+        .b_mock
+        .execute_tx(&owner_addr, &energy_factory_wrapper, &rust_zero, |sc| {
+            let mut lock_options = MultiValueEncoded::new();
+            lock_options.push((360u64, 4_000u64).into());
+            lock_options.push((720u64, 6_000u64).into());
+            lock_options.push((1_440u64, 8_000u64).into());
+
+            sc.init(
+                managed_token_id!(ASSET_TOKEN_ID),
+                managed_token_id!(LOCKED_ASSET_TOKEN_ID),
+                managed_address!(&laf_addr),
+                0,
+                lock_options,
+            );
+
+            sc.locked_token()
+                .set_token_id(managed_token_id!(ENERGY_LOCKED_TOKEN_ID));
+            sc.set_paused(false);
+        })
+        .assert_ok();
+    setup.b_mock.set_esdt_local_roles(
+        energy_factory_wrapper.address_ref(),
+        ASSET_TOKEN_ID,
+        &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+    );
+    setup.b_mock.set_esdt_local_roles(
+        energy_factory_wrapper.address_ref(),
+        ENERGY_LOCKED_TOKEN_ID,
+        &[
+            EsdtLocalRole::NftCreate,
+            EsdtLocalRole::NftAddQuantity,
+            EsdtLocalRole::NftBurn,
+            EsdtLocalRole::Transfer,
+        ],
+    );
+
+    // user locks three positions at epoch 0, for 1, 2 and 4 years
+    setup
+        .b_mock
+        .set_esdt_balance(&user_addr, ASSET_TOKEN_ID, &rust_biguint!(150_000_000));
+    for (amount, lock_epochs) in [
+        (60_000_000u64, 360u64),
+        (40_000_000, 720),
+        (50_000_000, 1_440),
+    ] {
+        setup
+            .b_mock
+            .execute_esdt_transfer(
+                &user_addr,
+                &energy_factory_wrapper,
+                ASSET_TOKEN_ID,
+                0,
+                &rust_biguint!(amount),
+                |sc| {
+                    let _ = sc.lock_tokens_endpoint(lock_epochs, OptionalValue::None);
+                },
+            )
+            .assert_ok();
+    }
+
+    setup
+        .call_set_energy_factory_locked_token_with_address(energy_factory_wrapper.address_ref())
+        .assert_ok();
+
+    let first_payments = [
+        TxInputESDT {
+            token_identifier: ENERGY_LOCKED_TOKEN_ID.to_vec(),
+            nonce: 1,
+            value: rust_biguint!(60_000_000),
+        },
+        TxInputESDT {
+            token_identifier: ENERGY_LOCKED_TOKEN_ID.to_vec(),
+            nonce: 2,
+            value: rust_biguint!(40_000_000),
+        },
+    ];
+
+    // merging on behalf of the user requires the whitelist
+    setup
+        .call_stake_locked_asset_multiple(&first_payments)
+        .assert_user_error("Item not whitelisted");
+
+    setup
+        .b_mock
+        .execute_tx(&owner_addr, &energy_factory_wrapper, &rust_zero, |sc| {
+            sc.add_sc_address_to_whitelist(managed_address!(&mbs_addr));
+        })
+        .assert_ok();
+
+    // (60 * 360 + 40 * 720) / 100 = 504, rounded up to the start of the next month
+    setup
+        .call_stake_locked_asset_multiple(&first_payments)
+        .assert_ok();
+    setup.b_mock.check_nft_balance(
+        &mbs_addr,
+        ENERGY_LOCKED_TOKEN_ID,
+        4,
+        &rust_biguint!(100_000_000),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(ASSET_TOKEN_ID)),
+            original_token_nonce: 0,
+            unlock_epoch: 510,
+        }),
+    );
+
+    // the new tokens are merged with the existing entry
+    // (100 * 510 + 50 * 1_440) / 150 = 820, rounded up to the start of the next month
+    setup
+        .call_stake_energy_locked_token(3, 50_000_000)
+        .assert_ok();
+    setup.b_mock.check_nft_balance(
+        &mbs_addr,
+        ENERGY_LOCKED_TOKEN_ID,
+        5,
+        &rust_biguint!(150_000_000),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(ASSET_TOKEN_ID)),
+            original_token_nonce: 0,
+            unlock_epoch: 840,
+        }),
+    );
+
+    setup
+        .b_mock
+        .execute_query(&setup.mbs_wrapper, |sc| {
+            let expected_entry = UserEntry::new(5, managed_biguint!(150_000_000));
+            let actual_entry = sc.entry_for_user(&managed_address!(&user_addr)).get();
+            assert_eq!(actual_entry, expected_entry);
+            assert_eq!(
+                sc.total_locked_asset_supply().get(),
+                managed_biguint!(150_000_000)
+            );
+        })
+        .assert_ok();
+
+    // the energy stays with the user, updated for the merged unlock epoch
+    setup
+        .b_mock
+        .execute_query(&energy_factory_wrapper, |sc| {
+            let user_energy = sc.user_energy(&managed_address!(&user_addr)).get();
+            assert_eq!(
+                user_energy.get_total_locked_tokens(),
+                &managed_biguint!(150_000_000)
+            );
+            assert_eq!(
+                sc.get_energy_amount_for_user(managed_address!(&user_addr)),
+                managed_biguint!(150_000_000u64 * 840)
+            );
+            assert!(sc.user_energy(&managed_address!(&mbs_addr)).is_empty());
+        })
+        .assert_ok();
+}
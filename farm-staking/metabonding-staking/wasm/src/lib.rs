@@ -7,13 +7,23 @@
 elrond_wasm_node::wasm_endpoints! {
     metabonding_staking
     (
+        getCurrentWeek
+        getEnergyFactoryAddress
+        getEnergyLockedTokenId
+        getFirstWeekStartEpoch
         getLockedAssetFactoryAddress
         getLockedAssetTokenId
         getSnapshot
         getStakedAmountForUser
         getTotalLockedAssetSupply
+        getTotalStakeForWeek
+        getTotalStakeSeedingIndex
         getUserEntry
         getUserList
+        getUserStakeForWeek
+        migrateLegacyStake
+        seedTotalStake
+        setEnergyFactoryLockedToken
         stakeLockedAsset
         unbond
         unstake
@@ -0,0 +1,63 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::Epoch;
+use unwrappable::Unwrappable;
+
+#[derive(TypeAbi, TopEncode, TopDecode, Clone, Copy, PartialEq, Debug)]
+pub struct ContinuousLockEpochs {
+    pub min_lock_epochs: Epoch,
+    pub max_lock_epochs: Epoch,
+}
+
+#[elrond_wasm::module]
+pub trait ContinuousLockModule: crate::lock_options::LockOptionsModule {
+    /// Enables locking for any number of epochs between min_lock_epochs and max_lock_epochs,
+    /// through the lockTokensContinuous endpoint.
+    /// The unlock epoch is not rounded to the start of the month for such locks.
+    ///
+    /// max_lock_epochs can't be greater than the longest lock option,
+    /// as the unlock penalty is interpolated between the lock options' percentages.
+    #[only_owner]
+    #[endpoint(setContinuousLockEpochs)]
+    fn set_continuous_lock_epochs(&self, min_lock_epochs: Epoch, max_lock_epochs: Epoch) {
+        let lock_options = self.get_lock_options();
+        let last_lock_option = lock_options.last().unwrap_or_panic::<Self::Api>();
+        require!(
+            min_lock_epochs > 0
+                && min_lock_epochs <= max_lock_epochs
+                && max_lock_epochs <= last_lock_option.lock_epochs,
+            "Invalid continuous lock epochs"
+        );
+
+        self.continuous_lock_epochs().set(ContinuousLockEpochs {
+            min_lock_epochs,
+            max_lock_epochs,
+        });
+    }
+
+    #[only_owner]
+    #[endpoint(disableContinuousLock)]
+    fn disable_continuous_lock(&self) {
+        self.continuous_lock_epochs().clear();
+    }
+
+    fn require_valid_continuous_lock_epochs(&self, lock_epochs: Epoch) {
+        let continuous_lock_mapper = self.continuous_lock_epochs();
+        require!(
+            !continuous_lock_mapper.is_empty(),
+            "Continuous lock not enabled"
+        );
+
+        let continuous_lock_epochs = continuous_lock_mapper.get();
+        require!(
+            lock_epochs >= continuous_lock_epochs.min_lock_epochs
+                && lock_epochs <= continuous_lock_epochs.max_lock_epochs,
+            "Invalid lock epochs"
+        );
+    }
+
+    #[view(getContinuousLockEpochs)]
+    #[storage_mapper("continuousLockEpochs")]
+    fn continuous_lock_epochs(&self) -> SingleValueMapper<ContinuousLockEpochs>;
+}
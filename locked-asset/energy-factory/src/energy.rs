@@ -1,7 +1,11 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
-use common_structs::Epoch;
+use common_structs::{Epoch, Percent};
+
+use crate::energy_delegation::MAX_DELEGATION_PERCENT;
+
+pub const MAX_USERS_WITH_ENERGY_RESULTS: usize = 100;
 
 #[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
 pub struct Energy<M: ManagedTypeApi> {
@@ -142,6 +146,27 @@ impl<M: ManagedTypeApi> Energy<M> {
         self.add_after_token_lock(token_amount, new_unlock_epoch, current_epoch);
     }
 
+    /// Both entries must be depleted up to the same epoch
+    pub fn add_energy(&mut self, other: &Energy<M>) {
+        self.amount += &other.amount;
+        self.total_locked_tokens += &other.total_locked_tokens;
+    }
+
+    /// Both entries must be depleted up to the same epoch
+    pub fn remove_energy(&mut self, other: &Energy<M>) {
+        self.amount -= &other.amount;
+        self.total_locked_tokens -= &other.total_locked_tokens;
+    }
+
+    /// Percent is in the same format as the delegation percentages (10_000 = 100%)
+    pub fn get_part(&self, percent: Percent) -> Self {
+        let amount = &self.amount * &BigInt::from(percent as i64)
+            / BigInt::from(MAX_DELEGATION_PERCENT as i64);
+        let total_locked_tokens = &self.total_locked_tokens * percent / MAX_DELEGATION_PERCENT;
+
+        Self::new(amount, self.last_update_epoch, total_locked_tokens)
+    }
+
     #[inline]
     pub fn get_last_update_epoch(&self) -> Epoch {
         self.last_update_epoch
@@ -162,45 +187,142 @@ impl<M: ManagedTypeApi> Energy<M> {
 }
 
 #[elrond_wasm::module]
-pub trait EnergyModule: crate::events::EventsModule {
+pub trait EnergyModule:
+    crate::events::EventsModule + crate::energy_checkpoints::EnergyCheckpointsModule
+{
+    /// The update function receives the user's own energy, i.e. without delegations
     fn update_energy<T, F: FnOnce(&mut Energy<Self::Api>) -> T>(
         &self,
         user: &ManagedAddress,
         update_fn: F,
     ) -> T {
-        let mut energy = self.get_updated_energy_entry_for_user(user);
+        let mut energy = self.get_own_energy_entry_for_user(user);
         let result = update_fn(&mut energy);
-        self.set_energy_entry(user, energy);
+        self.set_own_energy_entry(user, energy);
 
         result
     }
 
-    fn set_energy_entry(&self, user: &ManagedAddress, new_energy: Energy<Self::Api>) {
+    /// Sets the user's effective energy, for users not involved in delegations.
+    /// Otherwise, the difference from the previous effective energy is applied to their own energy,
+    /// so for delegators the effective energy becomes `prev + diff * (100% - delegated percent)`.
+    fn set_energy_entry(&self, user: &ManagedAddress, mut new_energy: Energy<Self::Api>) {
+        // entries received from other contracts might be depleted up to an older epoch
+        let current_epoch = self.blockchain().get_block_epoch();
+        new_energy.deplete(current_epoch);
+
+        if self.own_user_energy(user).is_empty() {
+            self.set_own_energy_entry(user, new_energy);
+            return;
+        }
+
+        let prev_energy = self.get_updated_energy_entry_for_user(user);
+        let mut own_energy = self.get_own_energy_entry_for_user(user);
+        own_energy.add_energy(&new_energy);
+        own_energy.remove_energy(&prev_energy);
+        self.set_own_energy_entry(user, own_energy);
+    }
+
+    /// Updates the user's own energy, and then the energy delegated to others, based on the new value
+    fn set_own_energy_entry(&self, user: &ManagedAddress, new_own_energy: Energy<Self::Api>) {
         require!(
             !self.blockchain().is_smart_contract(user),
             "Only user accounts can have energy"
         );
 
         let prev_energy = self.get_updated_energy_entry_for_user(user);
+        let own_energy_mapper = self.own_user_energy(user);
+        if own_energy_mapper.is_empty() {
+            // no delegations, so the own energy is the same as the effective energy
+            self.store_energy_entry(user, prev_energy, new_own_energy);
+            return;
+        }
 
-        self.user_energy(user).set(&new_energy);
-        self.emit_energy_updated_event(user, prev_energy, new_energy);
+        let prev_own_energy = self.get_own_energy_entry_for_user(user);
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut prev_delegated_energy = Energy::new_zero_energy(current_epoch);
+        let mut new_delegated_energy = Energy::new_zero_energy(current_epoch);
+        for (delegatee, percent) in self.energy_delegations(user).iter() {
+            let prev_delegation = self.get_delegated_energy_entry(user, &delegatee);
+            let new_delegation = new_own_energy.get_part(percent);
+            self.update_received_energy(&delegatee, &prev_delegation, &new_delegation);
+
+            let delegated_energy_mapper = self.delegated_energy(user, &delegatee);
+            if percent > 0 {
+                delegated_energy_mapper.set(&new_delegation);
+            } else {
+                delegated_energy_mapper.clear();
+            }
+
+            prev_delegated_energy.add_energy(&prev_delegation);
+            new_delegated_energy.add_energy(&new_delegation);
+        }
+
+        let mut new_energy = prev_energy.clone();
+        new_energy.add_energy(&new_own_energy);
+        new_energy.add_energy(&prev_delegated_energy);
+        new_energy.remove_energy(&prev_own_energy);
+        new_energy.remove_energy(&new_delegated_energy);
+
+        own_energy_mapper.set(&new_own_energy);
+        self.store_energy_entry(user, prev_energy, new_energy);
     }
 
-    #[view(getEnergyEntryForUser)]
-    fn get_updated_energy_entry_for_user(&self, user: &ManagedAddress) -> Energy<Self::Api> {
-        let current_epoch = self.blockchain().get_block_epoch();
+    fn update_received_energy(
+        &self,
+        delegatee: &ManagedAddress,
+        prev_delegation: &Energy<Self::Api>,
+        new_delegation: &Energy<Self::Api>,
+    ) {
+        let prev_energy = self.get_updated_energy_entry_for_user(delegatee);
+        let mut new_energy = prev_energy.clone();
+        new_energy.add_energy(new_delegation);
+        new_energy.remove_energy(prev_delegation);
+
+        self.store_energy_entry(delegatee, prev_energy, new_energy);
+    }
+
+    fn store_energy_entry(
+        &self,
+        user: &ManagedAddress,
+        prev_energy: Energy<Self::Api>,
+        new_energy: Energy<Self::Api>,
+    ) {
         let energy_mapper = self.user_energy(user);
         if !energy_mapper.is_empty() {
-            let mut energy = energy_mapper.get();
-            energy.deplete(current_epoch);
+            self.init_user_energy_checkpoints(user, &energy_mapper.get(), &prev_energy);
+        }
+        self.checkpoint_energy(user, &prev_energy, &new_energy);
+        self.update_users_with_energy(user, &new_energy);
 
-            energy
+        energy_mapper.set(&new_energy);
+        self.emit_energy_updated_event(user, prev_energy, new_energy);
+    }
+
+    fn update_users_with_energy(&self, user: &ManagedAddress, energy: &Energy<Self::Api>) {
+        let mut users_mapper = self.users_with_energy();
+        if energy.get_energy_amount() > 0 || energy.get_total_locked_tokens() > &0 {
+            let _ = users_mapper.insert(user.clone());
         } else {
-            Energy::new_zero_energy(current_epoch)
+            let _ = users_mapper.swap_remove(user);
         }
     }
 
+    /// Before the first delegation, the own energy is stored in the effective energy entry
+    fn init_own_energy_entry(&self, user: &ManagedAddress) {
+        let own_energy_mapper = self.own_user_energy(user);
+        if own_energy_mapper.is_empty() {
+            let energy = self.get_updated_energy_entry_for_user(user);
+            own_energy_mapper.set(&energy);
+        }
+    }
+
+    /// Returns the effective energy, i.e. own energy, minus delegated, plus received energy
+    #[view(getEnergyEntryForUser)]
+    fn get_updated_energy_entry_for_user(&self, user: &ManagedAddress) -> Energy<Self::Api> {
+        self.get_updated_energy_entry(&self.user_energy(user))
+    }
+
     #[view(getEnergyAmountForUser)]
     fn get_energy_amount_for_user(&self, user: ManagedAddress) -> BigUint {
         let energy = self.get_updated_energy_entry_for_user(&user);
@@ -208,6 +330,119 @@ pub trait EnergyModule: crate::events::EventsModule {
         energy.get_energy_amount()
     }
 
+    #[view(getEnergyEntriesForUsers)]
+    fn get_energy_entries_for_users(
+        &self,
+        users: MultiValueEncoded<ManagedAddress>,
+    ) -> MultiValueEncoded<Energy<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for user in users {
+            result.push(self.get_updated_energy_entry_for_user(&user));
+        }
+
+        result
+    }
+
+    #[view(getEnergyAmountsForUsers)]
+    fn get_energy_amounts_for_users(
+        &self,
+        users: MultiValueEncoded<ManagedAddress>,
+    ) -> MultiValueEncoded<BigUint> {
+        let mut result = MultiValueEncoded::new();
+        for user in users {
+            result.push(self.get_energy_amount_for_user(user));
+        }
+
+        result
+    }
+
+    /// Returns pairs of (user address, energy amount), starting from the given index.
+    /// Indexes start from 1. Use getNrUsersWithEnergy to find out the number of users.
+    /// Users whose energy was not updated since this list was introduced are not included.
+    #[view(getUsersWithEnergy)]
+    fn get_users_with_energy(
+        &self,
+        start_index: usize,
+        max_results: usize,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, BigUint>> {
+        require!(start_index > 0, "Invalid start index");
+        require!(
+            max_results <= MAX_USERS_WITH_ENERGY_RESULTS,
+            "Too many results requested"
+        );
+
+        let users_mapper = self.users_with_energy();
+        let nr_users = users_mapper.len();
+        let mut result = MultiValueEncoded::new();
+        let mut index = start_index;
+        while index <= nr_users && index < start_index + max_results {
+            let user = users_mapper.get_by_index(index);
+            let energy_amount = self.get_energy_amount_for_user(user.clone());
+            result.push((user, energy_amount).into());
+
+            index += 1;
+        }
+
+        result
+    }
+
+    #[view(getNrUsersWithEnergy)]
+    fn get_nr_users_with_energy(&self) -> usize {
+        self.users_with_energy().len()
+    }
+
+    #[view(getOwnEnergyEntryForUser)]
+    fn get_own_energy_entry_for_user(&self, user: &ManagedAddress) -> Energy<Self::Api> {
+        let own_energy_mapper = self.own_user_energy(user);
+        if !own_energy_mapper.is_empty() {
+            self.get_updated_energy_entry(&own_energy_mapper)
+        } else {
+            self.get_updated_energy_entry_for_user(user)
+        }
+    }
+
+    fn get_delegated_energy_entry(
+        &self,
+        delegator: &ManagedAddress,
+        delegatee: &ManagedAddress,
+    ) -> Energy<Self::Api> {
+        self.get_updated_energy_entry(&self.delegated_energy(delegator, delegatee))
+    }
+
+    fn get_updated_energy_entry(
+        &self,
+        energy_mapper: &SingleValueMapper<Energy<Self::Api>>,
+    ) -> Energy<Self::Api> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        if !energy_mapper.is_empty() {
+            let mut energy = energy_mapper.get();
+            energy.deplete(current_epoch);
+
+            energy
+        } else {
+            Energy::new_zero_energy(current_epoch)
+        }
+    }
+
+    /// Effective energy, which is read by the other contracts
     #[storage_mapper("userEnergy")]
     fn user_energy(&self, user: &ManagedAddress) -> SingleValueMapper<Energy<Self::Api>>;
+
+    #[storage_mapper("usersWithEnergy")]
+    fn users_with_energy(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    /// Only set for users involved in delegations
+    #[storage_mapper("ownUserEnergy")]
+    fn own_user_energy(&self, user: &ManagedAddress) -> SingleValueMapper<Energy<Self::Api>>;
+
+    /// Delegatee -> percent of the delegator's own energy
+    #[storage_mapper("energyDelegations")]
+    fn energy_delegations(&self, delegator: &ManagedAddress) -> MapMapper<ManagedAddress, Percent>;
+
+    #[storage_mapper("delegatedEnergy")]
+    fn delegated_energy(
+        &self,
+        delegator: &ManagedAddress,
+        delegatee: &ManagedAddress,
+    ) -> SingleValueMapper<Energy<Self::Api>>;
 }
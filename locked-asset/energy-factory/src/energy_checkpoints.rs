@@ -0,0 +1,156 @@
+elrond_wasm::imports!();
+
+use common_structs::Epoch;
+
+use crate::energy::Energy;
+
+/// Keeps the history of the users' energy and of the total energy.
+/// At most one checkpoint is kept per epoch, holding the last entry of that epoch.
+#[elrond_wasm::module]
+pub trait EnergyCheckpointsModule {
+    /// Saves the new effective energy of the user and updates the total energy accordingly.
+    /// Both entries must be depleted up to the current epoch.
+    fn checkpoint_energy(
+        &self,
+        user: &ManagedAddress,
+        prev_energy: &Energy<Self::Api>,
+        new_energy: &Energy<Self::Api>,
+    ) {
+        let mut total_energy = self.get_current_total_energy(new_energy.get_last_update_epoch());
+        total_energy.add_energy(new_energy);
+        total_energy.remove_energy(prev_energy);
+
+        self.add_checkpoint(&mut self.user_energy_checkpoints(user), new_energy);
+        self.add_checkpoint(&mut self.total_energy_checkpoints(), &total_energy);
+    }
+
+    /// Used for energy entries that were saved before checkpoints were introduced.
+    /// Adds the stored entry as the first checkpoint, and includes it in the total energy.
+    fn init_user_energy_checkpoints(
+        &self,
+        user: &ManagedAddress,
+        stored_energy: &Energy<Self::Api>,
+        current_energy: &Energy<Self::Api>,
+    ) {
+        let mut user_checkpoints_mapper = self.user_energy_checkpoints(user);
+        if !user_checkpoints_mapper.is_empty() {
+            return;
+        }
+
+        let mut total_energy =
+            self.get_current_total_energy(current_energy.get_last_update_epoch());
+        total_energy.add_energy(current_energy);
+
+        let _ = user_checkpoints_mapper.push(stored_energy);
+        self.add_checkpoint(&mut self.total_energy_checkpoints(), &total_energy);
+    }
+
+    fn get_current_total_energy(&self, current_epoch: Epoch) -> Energy<Self::Api> {
+        let total_checkpoints_mapper = self.total_energy_checkpoints();
+        let nr_checkpoints = total_checkpoints_mapper.len();
+        if nr_checkpoints == 0 {
+            return Energy::new_zero_energy(current_epoch);
+        }
+
+        let mut total_energy = total_checkpoints_mapper.get(nr_checkpoints);
+        total_energy.deplete(current_epoch);
+
+        total_energy
+    }
+
+    fn add_checkpoint(
+        &self,
+        checkpoints_mapper: &mut VecMapper<Energy<Self::Api>>,
+        energy: &Energy<Self::Api>,
+    ) {
+        let nr_checkpoints = checkpoints_mapper.len();
+        if nr_checkpoints > 0 {
+            let last_checkpoint = checkpoints_mapper.get(nr_checkpoints);
+            if last_checkpoint.get_last_update_epoch() == energy.get_last_update_epoch() {
+                checkpoints_mapper.set(nr_checkpoints, energy);
+                return;
+            }
+        }
+
+        let _ = checkpoints_mapper.push(energy);
+    }
+
+    /// Returns the energy of the user at the end of the given epoch,
+    /// which must be a past epoch, as the current epoch's checkpoint can still change.
+    /// Energy entries saved before checkpoints were introduced are only available
+    /// after the user's energy is updated, or after `initEnergyCheckpoints` is called.
+    #[view(getEnergyAtEpoch)]
+    fn get_energy_at_epoch(&self, user: ManagedAddress, epoch: Epoch) -> BigUint {
+        self.get_energy_amount_at_epoch(&self.user_energy_checkpoints(&user), epoch)
+    }
+
+    /// Returns the sum of the energy of all the users included in the checkpoints,
+    /// at the end of the given epoch, which must be a past epoch.
+    /// NOTE: The sum uses the raw energy amounts, which become negative once
+    /// the user's tokens can be unlocked, while getEnergyAtEpoch returns 0 for such users.
+    /// As such, the total can be lower than the sum of the users' values
+    /// until the users with expired tokens update their energy.
+    #[view(getTotalEnergyAtEpoch)]
+    fn get_total_energy_at_epoch(&self, epoch: Epoch) -> BigUint {
+        self.get_energy_amount_at_epoch(&self.total_energy_checkpoints(), epoch)
+    }
+
+    /// Returns the total locked tokens and the total energy, as of the current epoch.
+    #[view(getTotalLockedTokensAndEnergy)]
+    fn get_total_locked_tokens_and_energy(&self) -> MultiValue2<BigUint, BigUint> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let total_energy = self.get_current_total_energy(current_epoch);
+
+        (
+            total_energy.get_total_locked_tokens().clone(),
+            total_energy.get_energy_amount(),
+        )
+            .into()
+    }
+
+    fn get_energy_amount_at_epoch(
+        &self,
+        checkpoints_mapper: &VecMapper<Energy<Self::Api>>,
+        epoch: Epoch,
+    ) -> BigUint {
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(epoch < current_epoch, "Can only query past epochs");
+
+        match self.find_checkpoint(checkpoints_mapper, epoch) {
+            Some(mut energy) => {
+                energy.deplete(epoch);
+                energy.get_energy_amount()
+            }
+            None => BigUint::zero(),
+        }
+    }
+
+    /// Binary search for the last checkpoint saved at or before the given epoch
+    fn find_checkpoint(
+        &self,
+        checkpoints_mapper: &VecMapper<Energy<Self::Api>>,
+        epoch: Epoch,
+    ) -> Option<Energy<Self::Api>> {
+        let mut low = 1;
+        let mut high = checkpoints_mapper.len();
+        let mut result = None;
+        while low <= high {
+            let mid = low + (high - low) / 2;
+            let checkpoint = checkpoints_mapper.get(mid);
+            if checkpoint.get_last_update_epoch() <= epoch {
+                result = Some(checkpoint);
+                low = mid + 1;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        result
+    }
+
+    #[storage_mapper("userEnergyCheckpoints")]
+    fn user_energy_checkpoints(&self, user: &ManagedAddress) -> VecMapper<Energy<Self::Api>>;
+
+    #[storage_mapper("totalEnergyCheckpoints")]
+    fn total_energy_checkpoints(&self) -> VecMapper<Energy<Self::Api>>;
+}
@@ -0,0 +1,167 @@
+elrond_wasm::imports!();
+
+use common_structs::Percent;
+use week_timekeeping::Week;
+
+use crate::energy::Energy;
+
+pub const MAX_ENERGY_DELEGATIONS: usize = 10;
+pub const MAX_DELEGATION_PERCENT: Percent = 10_000; // 100%
+
+static DELEGATION_NOT_FOUND_ERR_MSG: &[u8] = b"Delegation not found";
+
+#[elrond_wasm::module]
+pub trait EnergyDelegationModule:
+    crate::energy::EnergyModule
+    + crate::events::EventsModule
+    + elrond_wasm_modules::pause::PauseModule
+    + week_timekeeping::WeekTimekeepingModule
+{
+    /// Delegates a part of the caller's own energy to another user account,
+    /// e.g. so that a hot wallet can use the energy of tokens kept in cold storage.
+    /// The delegated part follows the caller's energy changes, until it is revoked.
+    /// Calling this for an existing delegatee updates the percent.
+    ///
+    /// To prevent counting the same energy for several users in the same week,
+    /// a delegation can only be lowered or revoked starting with the week after it was set,
+    /// and no delegation can be added or raised in the same week as a lowering or revocation.
+    ///
+    /// Arguments:
+    /// - delegatee - The address that receives the energy
+    /// - percent - Part of the caller's own energy, where 10_000 = 100%.
+    ///     The sum of all the caller's delegations can't be over 100%.
+    #[endpoint(delegateEnergy)]
+    fn delegate_energy(&self, delegatee: ManagedAddress, percent: Percent) {
+        self.require_not_paused();
+
+        let caller = self.blockchain().get_caller();
+        require!(caller != delegatee, "Cannot delegate to self");
+        require!(
+            !self.blockchain().is_smart_contract(&delegatee),
+            "Only user accounts can have energy"
+        );
+        require!(
+            percent > 0 && percent <= MAX_DELEGATION_PERCENT,
+            "Invalid percent"
+        );
+
+        let current_week = self.get_current_week();
+        let delegations_mapper = self.energy_delegations(&caller);
+        let prev_percent = delegations_mapper.get(&delegatee).unwrap_or_default();
+        if percent < prev_percent {
+            self.require_delegation_can_be_lowered(&caller, &delegatee, current_week);
+            self.last_delegation_decrease_week(&caller)
+                .set(current_week);
+        } else {
+            require!(
+                self.last_delegation_decrease_week(&caller).get() != current_week,
+                "Cannot delegate in the same week as a revocation"
+            );
+        }
+
+        let mut total_percent = percent;
+        for (other_delegatee, other_percent) in delegations_mapper.iter() {
+            if other_delegatee != delegatee {
+                total_percent += other_percent;
+            }
+        }
+        require!(
+            total_percent <= MAX_DELEGATION_PERCENT,
+            "Cannot delegate more than 100%"
+        );
+        require!(
+            delegations_mapper.contains_key(&delegatee)
+                || delegations_mapper.len() < MAX_ENERGY_DELEGATIONS,
+            "Too many delegations"
+        );
+
+        self.init_own_energy_entry(&caller);
+        self.init_own_energy_entry(&delegatee);
+
+        self.delegation_week(&caller, &delegatee).set(current_week);
+        let _ = self.energy_delegations(&caller).insert(delegatee, percent);
+        self.refresh_delegated_energy(&caller);
+    }
+
+    /// Revokes the delegation towards the given address.
+    /// The delegated energy is returned to the caller.
+    /// Only possible starting with the week after the delegation was last set.
+    #[endpoint(revokeEnergyDelegation)]
+    fn revoke_energy_delegation(&self, delegatee: ManagedAddress) {
+        self.require_not_paused();
+
+        let caller = self.blockchain().get_caller();
+        let mut delegations_mapper = self.energy_delegations(&caller);
+        require!(
+            delegations_mapper.contains_key(&delegatee),
+            DELEGATION_NOT_FOUND_ERR_MSG
+        );
+
+        let current_week = self.get_current_week();
+        self.require_delegation_can_be_lowered(&caller, &delegatee, current_week);
+        self.last_delegation_decrease_week(&caller)
+            .set(current_week);
+        self.delegation_week(&caller, &delegatee).clear();
+
+        // a zero percent delegation removes the delegated energy
+        let _ = delegations_mapper.insert(delegatee.clone(), 0);
+        self.refresh_delegated_energy(&caller);
+
+        let _ = delegations_mapper.remove(&delegatee);
+    }
+
+    fn require_delegation_can_be_lowered(
+        &self,
+        delegator: &ManagedAddress,
+        delegatee: &ManagedAddress,
+        current_week: Week,
+    ) {
+        require!(
+            self.delegation_week(delegator, delegatee).get() < current_week,
+            "Delegation can only be lowered starting next week"
+        );
+    }
+
+    fn refresh_delegated_energy(&self, delegator: &ManagedAddress) {
+        self.update_energy(delegator, |_: &mut Energy<Self::Api>| {});
+    }
+
+    #[view(getEnergyDelegations)]
+    fn get_energy_delegations(
+        &self,
+        delegator: ManagedAddress,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, Percent>> {
+        let mut result = MultiValueEncoded::new();
+        for (delegatee, percent) in self.energy_delegations(&delegator).iter() {
+            result.push((delegatee, percent).into());
+        }
+
+        result
+    }
+
+    #[view(getDelegatedEnergyAmount)]
+    fn get_delegated_energy_amount(
+        &self,
+        delegator: ManagedAddress,
+        delegatee: ManagedAddress,
+    ) -> BigUint {
+        require!(
+            self.energy_delegations(&delegator).contains_key(&delegatee),
+            DELEGATION_NOT_FOUND_ERR_MSG
+        );
+
+        let delegated_energy = self.get_delegated_energy_entry(&delegator, &delegatee);
+        delegated_energy.get_energy_amount()
+    }
+
+    /// The week in which the delegation towards the delegatee was last set
+    #[storage_mapper("delegationWeek")]
+    fn delegation_week(
+        &self,
+        delegator: &ManagedAddress,
+        delegatee: &ManagedAddress,
+    ) -> SingleValueMapper<Week>;
+
+    #[storage_mapper("lastDelegationDecreaseWeek")]
+    fn last_delegation_decrease_week(&self, delegator: &ManagedAddress) -> SingleValueMapper<Week>;
+}
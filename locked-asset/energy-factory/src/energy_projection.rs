@@ -0,0 +1,122 @@
+elrond_wasm::imports!();
+
+use common_structs::{Epoch, Nonce};
+use simple_lock::locked_token::LockedTokenAttributes;
+
+use crate::energy_delegation::MAX_DELEGATION_PERCENT;
+
+#[elrond_wasm::module]
+pub trait EnergyProjectionModule:
+    simple_lock::basic_lock_unlock::BasicLockUnlock
+    + simple_lock::locked_token::LockedTokenModule
+    + simple_lock::token_attributes::TokenAttributesModule
+    + elrond_wasm_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + crate::energy::EnergyModule
+    + crate::lock_options::LockOptionsModule
+    + crate::events::EventsModule
+    + crate::migration::SimpleLockMigrationModule
+    + elrond_wasm_modules::pause::PauseModule
+    + utils::UtilsModule
+    + crate::extend_lock::ExtendLockModule
+    + crate::perpetual_lock::PerpetualLockModule
+    + crate::multi_asset_lock::MultiAssetLockModule
+    + legacy_token_decode_module::LegacyTokenDecodeModule
+{
+    /// Projects the user's energy at the given epoch, based on the unlock epoch of each position.
+    /// Unlike the energy entry, which depletes linearly, a position stops depleting
+    /// once its unlock epoch is reached. The user's locked asset positions are included
+    /// with their base asset equivalent. The user's perpetual lock is included with its full energy,
+    /// as it is expected to be refreshed.
+    ///
+    /// The parts of the user's own energy delegated to others are removed from the result.
+    /// Energy received through delegations is added, depleting linearly,
+    /// as the delegators' positions are not known.
+    ///
+    /// Arguments:
+    /// - user - The address whose locked asset positions, perpetual lock and delegations
+    ///     are taken into account
+    /// - epoch - The epoch for the projection. Must not be in the past.
+    /// - extend_lock_option - Simulates extending each LOCKED position
+    ///     through lockTokens with this lock option, i.e. its unlock epoch becomes
+    ///     the start of the month of current epoch + lock option, if that's later than the current one.
+    ///     Pass 0 for the current unlock epochs.
+    /// - positions - pairs of (LOCKED token nonce, amount) held by the user
+    #[view(getProjectedEnergy)]
+    fn get_projected_energy(
+        &self,
+        user: ManagedAddress,
+        epoch: Epoch,
+        extend_lock_option: Epoch,
+        positions: MultiValueEncoded<MultiValue2<Nonce, BigUint>>,
+    ) -> BigUint {
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(epoch >= current_epoch, "Cannot project past epochs");
+
+        let opt_extended_unlock_epoch = if extend_lock_option > 0 {
+            self.require_is_listed_lock_option(extend_lock_option);
+            Some(self.unlock_epoch_to_start_of_month(current_epoch + extend_lock_option))
+        } else {
+            None
+        };
+
+        let locked_token_mapper = self.locked_token();
+        let mut own_projected_energy = BigUint::zero();
+        for position in positions {
+            let (token_nonce, amount) = position.into_tuple();
+            let attributes: LockedTokenAttributes<Self::Api> =
+                locked_token_mapper.get_token_attributes(token_nonce);
+            let unlock_epoch = match opt_extended_unlock_epoch {
+                Some(extended_unlock_epoch) => {
+                    core::cmp::max(attributes.unlock_epoch, extended_unlock_epoch)
+                }
+                None => attributes.unlock_epoch,
+            };
+            if unlock_epoch > epoch {
+                own_projected_energy += amount * (unlock_epoch - epoch);
+            }
+        }
+
+        let locked_asset_token_mapper = self.locked_asset_token();
+        for token_nonce in self.user_locked_asset_nonces(&user).iter() {
+            let attributes: LockedTokenAttributes<Self::Api> =
+                locked_asset_token_mapper.get_token_attributes(token_nonce);
+            if attributes.unlock_epoch > epoch {
+                let position = self.locked_asset_position(token_nonce).get();
+                own_projected_energy +=
+                    position.base_asset_amount * (attributes.unlock_epoch - epoch);
+            }
+        }
+
+        let perpetual_lock_mapper = self.perpetual_lock(&user);
+        if !perpetual_lock_mapper.is_empty() {
+            let perpetual_lock = perpetual_lock_mapper.get();
+            own_projected_energy += perpetual_lock.token_amount * perpetual_lock.lock_epochs;
+        }
+
+        let mut delegated_percent = 0;
+        for (_, percent) in self.energy_delegations(&user).iter() {
+            delegated_percent += percent;
+        }
+        let kept_percent = MAX_DELEGATION_PERCENT - delegated_percent;
+
+        own_projected_energy * kept_percent / MAX_DELEGATION_PERCENT
+            + self.get_projected_received_energy(&user, epoch)
+    }
+
+    fn get_projected_received_energy(&self, user: &ManagedAddress, epoch: Epoch) -> BigUint {
+        if self.own_user_energy(user).is_empty() {
+            return BigUint::zero();
+        }
+
+        // effective energy = own energy - delegated energy + received energy
+        let mut received_energy = self.get_updated_energy_entry_for_user(user);
+        received_energy.remove_energy(&self.get_own_energy_entry_for_user(user));
+        for (delegatee, _) in self.energy_delegations(user).iter() {
+            received_energy.add_energy(&self.get_delegated_energy_entry(user, &delegatee));
+        }
+
+        received_energy.deplete(epoch);
+        received_energy.get_energy_amount()
+    }
+}
@@ -1,6 +1,8 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
+use common_structs::Epoch;
+
 use crate::energy::Energy;
 
 #[derive(TypeAbi, TopEncode, TopDecode)]
@@ -9,6 +11,14 @@ pub struct EnergyUpdatedEvent<M: ManagedTypeApi> {
     pub new_energy_entry: Energy<M>,
 }
 
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct VirtualLockEvent<M: ManagedTypeApi> {
+    pub dest_address: ManagedAddress<M>,
+    pub energy_address: ManagedAddress<M>,
+    pub locked_tokens: EsdtTokenPayment<M>,
+    pub unlock_epoch: Epoch,
+}
+
 #[elrond_wasm::module]
 pub trait EventsModule {
     fn emit_energy_updated_event(
@@ -39,4 +49,24 @@ pub trait EventsModule {
         #[indexed] timestamp: u64,
         data: EnergyUpdatedEvent<Self::Api>,
     );
+
+    fn emit_virtual_lock_event(&self, caller: &ManagedAddress, data: VirtualLockEvent<Self::Api>) {
+        self.virtual_lock_event(
+            caller,
+            self.blockchain().get_block_nonce(),
+            self.blockchain().get_block_epoch(),
+            self.blockchain().get_block_timestamp(),
+            data,
+        );
+    }
+
+    #[event("virtualLock")]
+    fn virtual_lock_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        #[indexed] timestamp: u64,
+        data: VirtualLockEvent<Self::Api>,
+    );
 }
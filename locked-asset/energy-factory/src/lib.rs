@@ -2,7 +2,11 @@
 
 elrond_wasm::imports!();
 
+pub mod continuous_lock;
 pub mod energy;
+pub mod energy_checkpoints;
+pub mod energy_delegation;
+pub mod energy_projection;
 pub mod events;
 pub mod extend_lock;
 pub mod local_roles;
@@ -10,8 +14,11 @@ pub mod lock_options;
 pub mod lock_options_endpoints;
 pub mod locked_token_transfer;
 pub mod migration;
+pub mod multi_asset_lock;
 pub mod penalty;
+pub mod perpetual_lock;
 pub mod token_merging;
+pub mod token_splitting;
 pub mod token_whitelist;
 pub mod unlock_with_penalty;
 pub mod unstake;
@@ -32,8 +39,11 @@ pub trait SimpleLockEnergy:
     + simple_lock::token_attributes::TokenAttributesModule
     + token_whitelist::TokenWhitelistModule
     + energy::EnergyModule
+    + energy_checkpoints::EnergyCheckpointsModule
+    + energy_delegation::EnergyDelegationModule
     + lock_options::LockOptionsModule
     + lock_options_endpoints::LockOptionsEndpointsModule
+    + continuous_lock::ContinuousLockModule
     + unlock_with_penalty::UnlockWithPenaltyModule
     + unstake::UnstakeModule
     + extend_lock::ExtendLockModule
@@ -42,15 +52,21 @@ pub trait SimpleLockEnergy:
     + elrond_wasm_modules::pause::PauseModule
     + local_roles::LocalRolesModule
     + token_merging::TokenMergingModule
+    + token_splitting::TokenSplittingModule
     + penalty::LocalPenaltyModule
     + utils::UtilsModule
     + virtual_lock::VirtualLockModule
+    + perpetual_lock::PerpetualLockModule
+    + energy_projection::EnergyProjectionModule
+    + multi_asset_lock::MultiAssetLockModule
     + sc_whitelist_module::SCWhitelistModule
     + locked_token_transfer::LockedTokenTransferModule
     + legacy_token_decode_module::LegacyTokenDecodeModule
+    + week_timekeeping::WeekTimekeepingModule
 {
     /// Args:
     /// - base_asset_token_id: The only token that is accepted for the lockTokens endpoint.
+    ///     Other assets can be whitelisted for the lockAsset endpoint.
     ///     NOTE: The SC also needs the ESDTLocalMint and ESDTLocalBurn roles for this token.
     /// - legacy_token_id: The token ID of the old locked asset.
     ///     NOTE: The SC also needs the NFTBurn role for this token.
@@ -88,6 +104,9 @@ pub trait SimpleLockEnergy:
         self.old_locked_asset_factory_address()
             .set_if_empty(&old_locked_asset_factory_address);
 
+        let current_epoch = self.blockchain().get_block_epoch();
+        self.first_week_start_epoch().set_if_empty(current_epoch);
+
         self.set_paused(true);
     }
 
@@ -114,11 +133,39 @@ pub trait SimpleLockEnergy:
         self.require_not_paused();
         self.require_is_listed_lock_option(lock_epochs);
 
-        let payment = self.call_value().single_esdt();
-        let dest_address = self.dest_from_optional(opt_destination);
         let current_epoch = self.blockchain().get_block_epoch();
         let unlock_epoch = self.unlock_epoch_to_start_of_month(current_epoch + lock_epochs);
 
+        self.lock_and_send_to_destination(unlock_epoch, current_epoch, opt_destination)
+    }
+
+    /// Same as lockTokens, but the lock period can be any number of epochs
+    /// in the range set through setContinuousLockEpochs.
+    /// The tokens unlock exactly at current_epoch + lock_epochs.
+    #[payable("*")]
+    #[endpoint(lockTokensContinuous)]
+    fn lock_tokens_continuous_endpoint(
+        &self,
+        lock_epochs: Epoch,
+        opt_destination: OptionalValue<ManagedAddress>,
+    ) -> EsdtTokenPayment {
+        self.require_not_paused();
+        self.require_valid_continuous_lock_epochs(lock_epochs);
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let unlock_epoch = current_epoch + lock_epochs;
+
+        self.lock_and_send_to_destination(unlock_epoch, current_epoch, opt_destination)
+    }
+
+    fn lock_and_send_to_destination(
+        &self,
+        unlock_epoch: Epoch,
+        current_epoch: Epoch,
+        opt_destination: OptionalValue<ManagedAddress>,
+    ) -> EsdtTokenPayment {
+        let payment = self.call_value().single_esdt();
+        let dest_address = self.dest_from_optional(opt_destination);
         let output_tokens =
             self.lock_by_token_type(&dest_address, payment, unlock_epoch, current_epoch);
 
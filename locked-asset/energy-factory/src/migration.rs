@@ -42,6 +42,25 @@ pub trait SimpleLockMigrationModule:
         }
     }
 
+    /// Adds the first energy checkpoint for users whose energy was saved
+    /// before checkpoints were introduced, and includes them in the total energy
+    /// and in the list of users with energy.
+    /// Users that already have checkpoints are ignored.
+    #[only_owner]
+    #[endpoint(initEnergyCheckpoints)]
+    fn init_energy_checkpoints(&self, users: MultiValueEncoded<ManagedAddress>) {
+        for user in users {
+            let energy_mapper = self.user_energy(&user);
+            if energy_mapper.is_empty() {
+                continue;
+            }
+
+            let current_energy = self.get_updated_energy_entry_for_user(&user);
+            self.init_user_energy_checkpoints(&user, &energy_mapper.get(), &current_energy);
+            self.update_users_with_energy(&user, &current_energy);
+        }
+    }
+
     #[endpoint(updateEnergyAfterOldTokenUnlock)]
     fn update_energy_after_old_token_unlock(
         &self,
@@ -0,0 +1,303 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::{Epoch, Nonce, PaymentsVec};
+use simple_lock::{error_messages::CANNOT_UNLOCK_YET_ERR_MSG, locked_token::LockedTokenAttributes};
+
+use crate::energy::Energy;
+
+pub mod price_provider_proxy {
+    elrond_wasm::imports!();
+
+    #[elrond_wasm::proxy]
+    pub trait PriceProvider {
+        #[endpoint(updateAndGetTokensForGivenPositionWithSafePrice)]
+        fn update_and_get_tokens_for_given_position_with_safe_price(
+            &self,
+            liquidity: BigUint,
+        ) -> MultiValue2<EsdtTokenPayment<Self::Api>, EsdtTokenPayment<Self::Api>>;
+    }
+}
+
+/// A single lockAsset call, with the remaining locked tokens and their base asset equivalent,
+/// fixed at lock time. Partial unlocks remove the same share of both amounts,
+/// and the last unlock removes whatever is left, so no energy residue remains.
+#[derive(TypeAbi, TopEncode, TopDecode, Clone, PartialEq, Debug)]
+pub struct LockedAssetPosition<M: ManagedTypeApi> {
+    pub token_amount: BigUint<M>,
+    pub base_asset_amount: BigUint<M>,
+}
+
+impl<M: ManagedTypeApi> LockedAssetPosition<M> {
+    pub fn get_base_asset_amount(&self, token_amount: &BigUint<M>) -> BigUint<M> {
+        if token_amount == &self.token_amount {
+            return self.base_asset_amount.clone();
+        }
+
+        token_amount * &self.base_asset_amount / &self.token_amount
+    }
+}
+
+/// Whitelisted assets other than the base asset (i.e. LP tokens of base asset pairs)
+/// can be locked as well. They are kept by the SC instead of being burned,
+/// and their energy is given by the base asset side of the position, at the pair's safe price.
+///
+/// Such positions use a separate locked asset token instead of the LOCKED token,
+/// as every contract consuming LOCKED tokens assumes they are backed 1:1 by the base asset.
+/// Each lock creates a new nonce, which can only be unlocked by the address it was created for,
+/// through unlockAsset, after its unlock epoch.
+#[elrond_wasm::module]
+pub trait MultiAssetLockModule:
+    simple_lock::basic_lock_unlock::BasicLockUnlock
+    + simple_lock::locked_token::LockedTokenModule
+    + simple_lock::token_attributes::TokenAttributesModule
+    + elrond_wasm_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + crate::energy::EnergyModule
+    + crate::lock_options::LockOptionsModule
+    + crate::events::EventsModule
+    + elrond_wasm_modules::pause::PauseModule
+    + utils::UtilsModule
+{
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(issueLockedAssetToken)]
+    fn issue_locked_asset_token(
+        &self,
+        token_display_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+        num_decimals: usize,
+    ) {
+        let payment_amount = self.call_value().egld_value();
+
+        self.locked_asset_token().issue_and_set_all_roles(
+            EsdtTokenType::Meta,
+            payment_amount,
+            token_display_name,
+            token_ticker,
+            num_decimals,
+            None,
+        );
+    }
+
+    /// Arguments:
+    /// - token_id - The asset that can be locked
+    /// - pair_address - The base asset pair that provides the safe price for the asset
+    #[only_owner]
+    #[endpoint(addLockableAsset)]
+    fn add_lockable_asset(&self, token_id: TokenIdentifier, pair_address: ManagedAddress) {
+        self.require_valid_token_id(&token_id);
+        self.require_sc_address(&pair_address);
+        require!(
+            !self.is_base_asset_token(&token_id),
+            "Base asset is always lockable"
+        );
+
+        let _ = self.lockable_assets().insert(token_id, pair_address);
+    }
+
+    /// Already locked tokens of the asset can still be unlocked after removal
+    #[only_owner]
+    #[endpoint(removeLockableAsset)]
+    fn remove_lockable_asset(&self, token_id: TokenIdentifier) {
+        let _ = self.lockable_assets().remove(&token_id);
+    }
+
+    /// Locks a whitelisted additional asset, similar to lockTokens.
+    ///
+    /// Arguments:
+    /// - lock_epochs - One of the lock options
+    /// - opt_destination - OPTIONAL: destination address for the locked asset tokens. Default is caller.
+    ///
+    /// Expected payment: a lockable asset
+    ///
+    /// Output payment: locked asset tokens
+    #[payable("*")]
+    #[endpoint(lockAsset)]
+    fn lock_asset(
+        &self,
+        lock_epochs: Epoch,
+        opt_destination: OptionalValue<ManagedAddress>,
+    ) -> EsdtTokenPayment {
+        self.require_not_paused();
+        self.require_is_listed_lock_option(lock_epochs);
+
+        let payment = self.call_value().single_esdt();
+        let pair_address = match self.lockable_assets().get(&payment.token_identifier) {
+            Some(address) => address,
+            None => sc_panic!("Asset not lockable"),
+        };
+
+        let base_asset_amount =
+            self.get_base_asset_amount_safe_price(pair_address, payment.amount.clone());
+        require!(base_asset_amount > 0, "Invalid asset price");
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let unlock_epoch = self.unlock_epoch_to_start_of_month(current_epoch + lock_epochs);
+        let attributes = LockedTokenAttributes {
+            original_token_id: EgldOrEsdtTokenIdentifier::esdt(payment.token_identifier),
+            original_token_nonce: payment.token_nonce,
+            unlock_epoch,
+        };
+        let output_tokens = self
+            .locked_asset_token()
+            .nft_create(payment.amount.clone(), &attributes);
+
+        let dest_address = self.dest_from_optional(opt_destination);
+        self.locked_asset_position(output_tokens.token_nonce)
+            .set(&LockedAssetPosition {
+                token_amount: payment.amount,
+                base_asset_amount: base_asset_amount.clone(),
+            });
+        let _ = self
+            .user_locked_asset_nonces(&dest_address)
+            .insert(output_tokens.token_nonce);
+
+        self.update_energy(&dest_address, |energy: &mut Energy<Self::Api>| {
+            energy.add_after_token_lock(&base_asset_amount, unlock_epoch, current_epoch);
+        });
+
+        self.send().direct_esdt(
+            &dest_address,
+            &output_tokens.token_identifier,
+            output_tokens.token_nonce,
+            &output_tokens.amount,
+        );
+
+        output_tokens
+    }
+
+    /// Unlock tokens, previously locked with the `lockAsset` endpoint
+    ///
+    /// Expected payments: locked asset tokens, created for the caller
+    ///
+    /// Output payments: the originally locked assets
+    #[payable("*")]
+    #[endpoint(unlockAsset)]
+    fn unlock_asset(&self) -> MultiValueEncoded<EsdtTokenPayment> {
+        self.require_not_paused();
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        let locked_asset_token_mapper = self.locked_asset_token();
+
+        let mut output_payments = PaymentsVec::new();
+        self.update_energy(&caller, |energy: &mut Energy<Self::Api>| {
+            let payments = self.get_non_empty_payments();
+            locked_asset_token_mapper.require_all_same_token(&payments);
+
+            for payment in &payments {
+                require!(
+                    self.user_locked_asset_nonces(&caller)
+                        .contains(&payment.token_nonce),
+                    "Position was not created for caller"
+                );
+
+                let attributes: LockedTokenAttributes<Self::Api> =
+                    locked_asset_token_mapper.get_token_attributes(payment.token_nonce);
+                require!(
+                    current_epoch >= attributes.unlock_epoch,
+                    CANNOT_UNLOCK_YET_ERR_MSG
+                );
+
+                let energy_token_amount = self.remove_from_locked_asset_position(
+                    &caller,
+                    payment.token_nonce,
+                    &payment.amount,
+                );
+                energy.refund_after_token_unlock(
+                    &energy_token_amount,
+                    attributes.unlock_epoch,
+                    current_epoch,
+                );
+
+                locked_asset_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+                let unlocked_tokens = self.unlock_tokens_unchecked(payment, &attributes);
+                output_payments.push(self.to_esdt_payment(unlocked_tokens));
+            }
+        });
+
+        self.send().direct_multi(&caller, &output_payments);
+
+        output_payments.into()
+    }
+
+    /// Returns the base asset equivalent of the removed tokens
+    fn remove_from_locked_asset_position(
+        &self,
+        user: &ManagedAddress,
+        token_nonce: Nonce,
+        token_amount: &BigUint,
+    ) -> BigUint {
+        let position_mapper = self.locked_asset_position(token_nonce);
+        let mut position = position_mapper.get();
+        let base_asset_amount = position.get_base_asset_amount(token_amount);
+        position.token_amount -= token_amount;
+        position.base_asset_amount -= &base_asset_amount;
+
+        if position.token_amount == 0 {
+            position_mapper.clear();
+            let _ = self
+                .user_locked_asset_nonces(user)
+                .swap_remove(&token_nonce);
+        } else {
+            position_mapper.set(&position);
+        }
+
+        base_asset_amount
+    }
+
+    fn get_base_asset_amount_safe_price(
+        &self,
+        pair_address: ManagedAddress,
+        liquidity: BigUint,
+    ) -> BigUint {
+        let base_asset = self.base_asset_token_id().get();
+        let call_result: MultiValue2<EsdtTokenPayment, EsdtTokenPayment> = self
+            .price_provider_proxy(pair_address)
+            .update_and_get_tokens_for_given_position_with_safe_price(liquidity)
+            .execute_on_dest_context();
+        let (first_token, second_token) = call_result.into_tuple();
+
+        if first_token.token_identifier == base_asset {
+            first_token.amount
+        } else if second_token.token_identifier == base_asset {
+            second_token.amount
+        } else {
+            sc_panic!("Pair does not contain the base asset");
+        }
+    }
+
+    #[view(getLockableAssets)]
+    fn get_lockable_assets(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<TokenIdentifier, ManagedAddress>> {
+        let mut result = MultiValueEncoded::new();
+        for (token_id, pair_address) in self.lockable_assets().iter() {
+            result.push((token_id, pair_address).into());
+        }
+
+        result
+    }
+
+    #[proxy]
+    fn price_provider_proxy(&self, to: ManagedAddress) -> price_provider_proxy::Proxy<Self::Api>;
+
+    #[storage_mapper("lockableAssets")]
+    fn lockable_assets(&self) -> MapMapper<TokenIdentifier, ManagedAddress>;
+
+    #[view(getLockedAssetTokenId)]
+    #[storage_mapper("lockedAssetTokenId")]
+    fn locked_asset_token(&self) -> NonFungibleTokenMapper<Self::Api>;
+
+    #[view(getLockedAssetPosition)]
+    #[storage_mapper("lockedAssetPosition")]
+    fn locked_asset_position(
+        &self,
+        token_nonce: Nonce,
+    ) -> SingleValueMapper<LockedAssetPosition<Self::Api>>;
+
+    #[view(getUserLockedAssetNonces)]
+    #[storage_mapper("userLockedAssetNonces")]
+    fn user_locked_asset_nonces(&self, user: &ManagedAddress) -> UnorderedSetMapper<Nonce>;
+}
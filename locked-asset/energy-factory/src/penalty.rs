@@ -4,12 +4,156 @@ elrond_wasm::derive_imports!();
 use common_structs::{Epoch, Percent};
 use math::linear_interpolation;
 
-use crate::lock_options::LockOption;
+use crate::lock_options::{AllLockOptions, LockOption, MAX_PENALTY_PERCENTAGE};
+
+pub const MAX_PENALTY_STEPS: usize = 20;
+const MAX_HALVINGS: u64 = 64;
+
+/// Storage keys read by the token unstake SC
+pub static PENALTY_CURVE_STORAGE_KEY: &[u8] = b"penaltyCurve";
+pub static PENALTY_FEES_COLLECTOR_PERCENTAGE_STORAGE_KEY: &[u8] = b"penaltyFeesCollectorPercentage";
+
+#[derive(
+    ManagedVecItem,
+    TypeAbi,
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    Clone,
+    Copy,
+    PartialEq,
+    Debug,
+)]
+pub struct PenaltyStep {
+    pub max_lock_epochs_remaining: Epoch,
+    pub penalty_percentage: Percent,
+}
+
+/// The formula used to compute the penalty for unlocking early,
+/// based on the number of epochs remaining until the unlock epoch
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub enum PenaltyCurve<M: ManagedTypeApi> {
+    /// linear interpolation between the lock options' penalty percentages
+    Linear,
+    /// starts from the longest lock option's penalty percentage,
+    /// and halves every `half_life_epochs` as the unlock epoch gets closer
+    ExponentialDecay { half_life_epochs: Epoch },
+    /// the percentage of the first step that has at least the remaining lock epochs.
+    /// Lock epochs above the last step, e.g. for lock options added later, use the last step.
+    StepTable { steps: ManagedVec<M, PenaltyStep> },
+}
+
+#[derive(
+    TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Debug,
+)]
+pub enum PenaltyCurveType {
+    Linear,
+    ExponentialDecay,
+    StepTable,
+}
+
+impl<M: ManagedTypeApi> PenaltyCurve<M> {
+    pub fn get_type(&self) -> PenaltyCurveType {
+        match self {
+            PenaltyCurve::Linear => PenaltyCurveType::Linear,
+            PenaltyCurve::ExponentialDecay { .. } => PenaltyCurveType::ExponentialDecay,
+            PenaltyCurve::StepTable { .. } => PenaltyCurveType::StepTable,
+        }
+    }
+
+    pub fn is_valid(&self, last_lock_option: &LockOption) -> bool {
+        match self {
+            PenaltyCurve::Linear => true,
+            PenaltyCurve::ExponentialDecay { half_life_epochs } => *half_life_epochs > 0,
+            PenaltyCurve::StepTable { steps } => {
+                if steps.is_empty() || steps.len() > MAX_PENALTY_STEPS {
+                    return false;
+                }
+
+                let mut opt_prev_step: Option<PenaltyStep> = None;
+                for step in steps {
+                    if step.penalty_percentage >= MAX_PENALTY_PERCENTAGE {
+                        return false;
+                    }
+
+                    if let Some(prev_step) = &opt_prev_step {
+                        if step.max_lock_epochs_remaining <= prev_step.max_lock_epochs_remaining
+                            || step.penalty_percentage < prev_step.penalty_percentage
+                        {
+                            return false;
+                        }
+                    }
+
+                    opt_prev_step = Some(step);
+                }
+
+                let last_step = steps.get(steps.len() - 1);
+                last_step.max_lock_epochs_remaining >= last_lock_option.lock_epochs
+            }
+        }
+    }
+}
 
 #[elrond_wasm::module]
 pub trait LocalPenaltyModule: crate::lock_options::LockOptionsModule {
+    /// Sets the penalty curve used for early unlocks and lock period reductions.
+    ///
+    /// Arguments:
+    /// - curve - See `PenaltyCurve` for the available types
+    #[only_owner]
+    #[endpoint(setPenaltyCurve)]
+    fn set_penalty_curve(&self, curve: PenaltyCurve<Self::Api>) {
+        let lock_options = self.get_lock_options();
+        let last_lock_option = unsafe { lock_options.get_unchecked(lock_options.len() - 1) };
+        require!(curve.is_valid(last_lock_option), "Invalid penalty curve");
+
+        self.penalty_curve().set(&curve);
+    }
+
+    /// Sets the part of the penalty that is sent to the fees collector instead of being burned,
+    /// while the given curve type is active. 10_000 = 100%.
+    /// It is applied by the token unstake SC, with the curve active when it handles the penalty,
+    /// before splitting the rest of the penalty based on its own burn percentage.
+    #[only_owner]
+    #[endpoint(setPenaltyFeesCollectorPercentage)]
+    fn set_penalty_fees_collector_percentage(
+        &self,
+        curve_type: PenaltyCurveType,
+        percentage: Percent,
+    ) {
+        require!(
+            percentage <= MAX_PENALTY_PERCENTAGE,
+            "Invalid fees collector percentage"
+        );
+
+        self.penalty_fees_collector_percentage(curve_type)
+            .set(percentage);
+    }
+
+    #[view(getPenaltyCurve)]
+    fn get_penalty_curve(&self) -> PenaltyCurve<Self::Api> {
+        let mapper = self.penalty_curve();
+        if mapper.is_empty() {
+            return PenaltyCurve::Linear;
+        }
+
+        mapper.get()
+    }
+
     fn calculate_penalty_percentage_full_unlock(&self, lock_epochs_remaining: Epoch) -> Percent {
         let lock_options = self.get_lock_options();
+        let curve = self.get_penalty_curve();
+
+        self.calculate_penalty_percentage_for_curve(&lock_options, &curve, lock_epochs_remaining)
+    }
+
+    fn calculate_penalty_percentage_for_curve(
+        &self,
+        lock_options: &AllLockOptions,
+        curve: &PenaltyCurve<Self::Api>,
+        lock_epochs_remaining: Epoch,
+    ) -> Percent {
         let last_index = lock_options.len() - 1;
         let last_lock_option = unsafe { lock_options.get_unchecked(last_index) };
         require!(
@@ -17,6 +161,44 @@ pub trait LocalPenaltyModule: crate::lock_options::LockOptionsModule {
             "Invalid lock epochs"
         );
 
+        match curve {
+            PenaltyCurve::Linear => {
+                self.calculate_linear_penalty_percentage(lock_options, lock_epochs_remaining)
+            }
+            PenaltyCurve::ExponentialDecay { half_life_epochs } => {
+                let epochs_since_max = last_lock_option.lock_epochs - lock_epochs_remaining;
+                let nr_halvings = epochs_since_max / half_life_epochs;
+                if nr_halvings >= MAX_HALVINGS {
+                    return 0;
+                }
+
+                // linear interpolation between two consecutive halvings
+                let upper_percentage = last_lock_option.penalty_start_percentage >> nr_halvings;
+                let lower_percentage = upper_percentage / 2;
+                let epochs_since_halving = epochs_since_max % half_life_epochs;
+
+                upper_percentage
+                    - (upper_percentage - lower_percentage) * epochs_since_halving
+                        / half_life_epochs
+            }
+            PenaltyCurve::StepTable { steps } => {
+                for step in steps {
+                    if lock_epochs_remaining <= step.max_lock_epochs_remaining {
+                        return step.penalty_percentage;
+                    }
+                }
+
+                steps.get(steps.len() - 1).penalty_percentage
+            }
+        }
+    }
+
+    fn calculate_linear_penalty_percentage(
+        &self,
+        lock_options: &AllLockOptions,
+        lock_epochs_remaining: Epoch,
+    ) -> Percent {
+        let last_index = lock_options.len() - 1;
         let mut prev_option = LockOption::default();
         let mut next_option = LockOption::default();
 
@@ -47,4 +229,14 @@ pub trait LocalPenaltyModule: crate::lock_options::LockOptionsModule {
             next_option.penalty_start_percentage,
         )
     }
+
+    #[storage_mapper("penaltyCurve")]
+    fn penalty_curve(&self) -> SingleValueMapper<PenaltyCurve<Self::Api>>;
+
+    #[view(getPenaltyFeesCollectorPercentage)]
+    #[storage_mapper("penaltyFeesCollectorPercentage")]
+    fn penalty_fees_collector_percentage(
+        &self,
+        curve_type: PenaltyCurveType,
+    ) -> SingleValueMapper<Percent>;
 }
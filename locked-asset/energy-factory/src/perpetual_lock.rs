@@ -0,0 +1,206 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::Epoch;
+use simple_lock::locked_token::LockedTokenAttributes;
+
+use crate::{energy::Energy, unlock_with_penalty::TOKEN_CAN_BE_UNLOCKED_ALREADY_ERR_MSG};
+
+#[derive(TypeAbi, TopEncode, TopDecode, Clone, PartialEq, Debug)]
+pub struct PerpetualLock<M: ManagedTypeApi> {
+    pub token_amount: BigUint<M>,
+    pub lock_epochs: Epoch,
+    pub last_refresh_epoch: Epoch,
+}
+
+impl<M: ManagedTypeApi> PerpetualLock<M> {
+    #[inline]
+    pub fn get_unlock_epoch(&self) -> Epoch {
+        self.last_refresh_epoch + self.lock_epochs
+    }
+}
+
+/// Tokens in a perpetual lock are accounted in the energy entry like regular locked tokens,
+/// which unlock `lock_epochs` after the last refresh of the perpetual lock.
+/// This way, the energy keeps being `total_locked_tokens * remaining epochs`,
+/// which is what the contracts reading the energy entries rely on.
+/// Each refresh brings the remaining lock period back to `lock_epochs`.
+#[elrond_wasm::module]
+pub trait PerpetualLockModule:
+    simple_lock::basic_lock_unlock::BasicLockUnlock
+    + simple_lock::locked_token::LockedTokenModule
+    + simple_lock::token_attributes::TokenAttributesModule
+    + elrond_wasm_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + crate::energy::EnergyModule
+    + crate::lock_options::LockOptionsModule
+    + crate::events::EventsModule
+    + crate::migration::SimpleLockMigrationModule
+    + elrond_wasm_modules::pause::PauseModule
+    + utils::UtilsModule
+    + crate::extend_lock::ExtendLockModule
+    + legacy_token_decode_module::LegacyTokenDecodeModule
+{
+    /// Moves the LOCKED tokens into the caller's perpetual lock.
+    /// The tokens are burned, and their energy is kept constant at token_amount * lock_epochs.
+    ///
+    /// Arguments:
+    /// - lock_epochs - One of the lock options. It must be the same for all the
+    ///     tokens in the perpetual lock, and can't be lower than the tokens' remaining lock period.
+    ///
+    /// Expected payments: LOCKED tokens
+    #[payable("*")]
+    #[endpoint(enablePerpetualLock)]
+    fn enable_perpetual_lock(&self, lock_epochs: Epoch) {
+        self.require_not_paused();
+        self.require_is_listed_lock_option(lock_epochs);
+
+        let caller = self.blockchain().get_caller();
+        let payments = self.get_non_empty_payments();
+        let locked_token_mapper = self.locked_token();
+        locked_token_mapper.require_all_same_token(&payments);
+
+        let perpetual_lock_mapper = self.perpetual_lock(&caller);
+        let mut perpetual_lock = if !perpetual_lock_mapper.is_empty() {
+            let existing_lock = perpetual_lock_mapper.get();
+            require!(
+                existing_lock.lock_epochs == lock_epochs,
+                "Perpetual lock has a different lock period"
+            );
+
+            existing_lock
+        } else {
+            PerpetualLock {
+                token_amount: BigUint::zero(),
+                lock_epochs,
+                last_refresh_epoch: 0,
+            }
+        };
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut added_amount = BigUint::zero();
+        self.update_energy(&caller, |energy: &mut Energy<Self::Api>| {
+            self.refresh_perpetual_lock_energy(&mut perpetual_lock, energy, current_epoch);
+
+            for payment in &payments {
+                let attributes: LockedTokenAttributes<Self::Api> =
+                    locked_token_mapper.get_token_attributes(payment.token_nonce);
+                require!(
+                    attributes.unlock_epoch > current_epoch,
+                    TOKEN_CAN_BE_UNLOCKED_ALREADY_ERR_MSG
+                );
+                require!(
+                    attributes.unlock_epoch - current_epoch <= lock_epochs,
+                    "Lock period cannot be reduced"
+                );
+
+                energy.update_after_unlock_any(
+                    &payment.amount,
+                    attributes.unlock_epoch,
+                    current_epoch,
+                );
+                locked_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+                added_amount += &payment.amount;
+            }
+
+            energy.add_after_token_lock(
+                &added_amount,
+                perpetual_lock.get_unlock_epoch(),
+                current_epoch,
+            );
+        });
+
+        perpetual_lock.token_amount += added_amount;
+        perpetual_lock_mapper.set(&perpetual_lock);
+    }
+
+    /// Removes the caller's perpetual lock. The tokens are locked again
+    /// for the perpetual lock's period, after which their energy depletes as usual.
+    ///
+    /// Output payment: LOCKED tokens
+    #[endpoint(disablePerpetualLock)]
+    fn disable_perpetual_lock(&self) -> EsdtTokenPayment {
+        self.require_not_paused();
+
+        let caller = self.blockchain().get_caller();
+        let perpetual_lock_mapper = self.perpetual_lock(&caller);
+        require!(!perpetual_lock_mapper.is_empty(), "No perpetual lock");
+
+        let perpetual_lock = perpetual_lock_mapper.get();
+        perpetual_lock_mapper.clear();
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let unlock_epoch =
+            self.unlock_epoch_to_start_of_month(current_epoch + perpetual_lock.lock_epochs);
+        let base_asset = self.base_asset_token_id().get();
+
+        let output_tokens = self.update_energy(&caller, |energy: &mut Energy<Self::Api>| {
+            energy.update_after_unlock_any(
+                &perpetual_lock.token_amount,
+                perpetual_lock.get_unlock_epoch(),
+                current_epoch,
+            );
+
+            self.lock_base_asset(
+                EsdtTokenPayment::new(base_asset, 0, perpetual_lock.token_amount),
+                unlock_epoch,
+                current_epoch,
+                energy,
+            )
+        });
+
+        self.send().direct_esdt(
+            &caller,
+            &output_tokens.token_identifier,
+            output_tokens.token_nonce,
+            &output_tokens.amount,
+        );
+
+        output_tokens
+    }
+
+    /// Brings the remaining lock period of the users' perpetual locks back to their lock_epochs.
+    /// Can be called by anyone, e.g. once per week, so that the energy of the perpetual locks
+    /// stays close to `token_amount * lock_epochs`.
+    #[endpoint(refreshPerpetualLocks)]
+    fn refresh_perpetual_locks(&self, users: MultiValueEncoded<ManagedAddress>) {
+        self.require_not_paused();
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        for user in users {
+            let perpetual_lock_mapper = self.perpetual_lock(&user);
+            if perpetual_lock_mapper.is_empty() {
+                continue;
+            }
+
+            let mut perpetual_lock = perpetual_lock_mapper.get();
+            self.update_energy(&user, |energy: &mut Energy<Self::Api>| {
+                self.refresh_perpetual_lock_energy(&mut perpetual_lock, energy, current_epoch);
+            });
+            perpetual_lock_mapper.set(&perpetual_lock);
+        }
+    }
+
+    fn refresh_perpetual_lock_energy(
+        &self,
+        perpetual_lock: &mut PerpetualLock<Self::Api>,
+        energy: &mut Energy<Self::Api>,
+        current_epoch: Epoch,
+    ) {
+        if perpetual_lock.token_amount > 0 {
+            energy.update_after_unlock_epoch_change(
+                &perpetual_lock.token_amount,
+                perpetual_lock.get_unlock_epoch(),
+                current_epoch + perpetual_lock.lock_epochs,
+                current_epoch,
+            );
+        }
+
+        perpetual_lock.last_refresh_epoch = current_epoch;
+    }
+
+    #[view(getPerpetualLock)]
+    #[storage_mapper("perpetualLock")]
+    fn perpetual_lock(&self, user: &ManagedAddress) -> SingleValueMapper<PerpetualLock<Self::Api>>;
+}
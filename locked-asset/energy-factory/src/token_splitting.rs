@@ -0,0 +1,89 @@
+elrond_wasm::imports!();
+
+use common_structs::PaymentsVec;
+use simple_lock::locked_token::LockedTokenAttributes;
+
+pub const MAX_SPLIT_OUTPUT_TOKENS: usize = 10;
+
+#[elrond_wasm::module]
+pub trait TokenSplittingModule:
+    simple_lock::locked_token::LockedTokenModule
+    + elrond_wasm_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + elrond_wasm_modules::pause::PauseModule
+    + utils::UtilsModule
+    + legacy_token_decode_module::LegacyTokenDecodeModule
+{
+    /// Splits a locked token into multiple positions of the given amounts.
+    /// For LOCKED tokens, each position is a new token nonce, with the same attributes as the input,
+    /// so the unlock epoch and the user's energy do not change.
+    ///
+    /// Legacy locked tokens can't be created by this contract, and migrating them
+    /// would change their unlock schedule and the user's energy.
+    /// They are sent back as separate payments of the same nonce instead.
+    ///
+    /// Expected payment: LOCKED or legacy locked tokens
+    ///
+    /// Arguments:
+    /// - amounts - The amounts of the output tokens. They must add up to the payment amount.
+    ///
+    /// Output payments: the new tokens, in the order of the given amounts
+    #[payable("*")]
+    #[endpoint(splitTokens)]
+    fn split_tokens(
+        &self,
+        amounts: MultiValueEncoded<BigUint>,
+    ) -> MultiValueEncoded<EsdtTokenPayment> {
+        self.require_not_paused();
+
+        let payment = self.call_value().single_esdt();
+        let locked_token_mapper = self.locked_token();
+        let is_locked_token = payment.token_identifier == locked_token_mapper.get_token_id();
+        let is_legacy_token = payment.token_identifier == self.legacy_locked_token_id().get();
+        require!(is_locked_token || is_legacy_token, "Invalid payment token");
+
+        let nr_output_tokens = amounts.len();
+        require!(
+            nr_output_tokens > 1 && nr_output_tokens <= MAX_SPLIT_OUTPUT_TOKENS,
+            "Invalid number of output tokens"
+        );
+
+        let opt_attributes: Option<LockedTokenAttributes<Self::Api>> = if is_locked_token {
+            Some(locked_token_mapper.get_token_attributes(payment.token_nonce))
+        } else {
+            // only tokens with valid legacy attributes are accepted
+            let _ = self.decode_legacy_token(&payment.token_identifier, payment.token_nonce);
+            None
+        };
+
+        let mut output_payments = PaymentsVec::new();
+        let mut total_amount = BigUint::zero();
+        for amount in amounts {
+            require!(amount > 0, "Amount cannot be 0");
+
+            total_amount += &amount;
+            let new_tokens = match &opt_attributes {
+                Some(attributes) => locked_token_mapper.nft_create(amount, attributes),
+                None => EsdtTokenPayment::new(
+                    payment.token_identifier.clone(),
+                    payment.token_nonce,
+                    amount,
+                ),
+            };
+            output_payments.push(new_tokens);
+        }
+        require!(
+            total_amount == payment.amount,
+            "Amounts must add up to the payment amount"
+        );
+
+        if is_locked_token {
+            locked_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
+        }
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_multi(&caller, &output_payments);
+
+        output_payments.into()
+    }
+}
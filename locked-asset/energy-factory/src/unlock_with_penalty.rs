@@ -1,7 +1,7 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
-use common_structs::Epoch;
+use common_structs::{Epoch, Percent};
 
 use simple_lock::locked_token::LockedTokenAttributes;
 
@@ -49,7 +49,7 @@ pub trait UnlockWithPenaltyModule:
             &unlocked_tokens.amount,
         );
 
-        self.set_energy_entry(&caller, reduce_result.energy);
+        self.set_own_energy_entry(&caller, reduce_result.energy);
         self.unstake_tokens(caller, payment, unlocked_tokens);
     }
 
@@ -90,7 +90,7 @@ pub trait UnlockWithPenaltyModule:
 
         let mut energy = reduce_result.energy;
         energy.add_after_token_lock(&new_locked_tokens.amount, new_unlock_epoch, current_epoch);
-        self.set_energy_entry(&caller, energy);
+        self.set_own_energy_entry(&caller, energy);
 
         self.send().direct(
             &caller,
@@ -138,7 +138,7 @@ pub trait UnlockWithPenaltyModule:
         let prev_lock_epochs = attributes.unlock_epoch - current_epoch;
         require!(new_lock_epochs < prev_lock_epochs, "Invalid reduce choice");
 
-        let mut energy = self.get_updated_energy_entry_for_user(caller);
+        let mut energy = self.get_own_energy_entry_for_user(caller);
         energy.deplete_after_early_unlock(&payment.amount, attributes.unlock_epoch, current_epoch);
 
         let penalty_amount =
@@ -171,6 +171,33 @@ pub trait UnlockWithPenaltyModule:
             / (MAX_PENALTY_PERCENTAGE - new_penalty_percentage)
     }
 
+    /// Returns pairs of (epoch, penalty percentage) for every epoch until the token's unlock epoch,
+    /// where the percentage is the full unlock penalty if unlocking in that epoch.
+    /// 10_000 = 100%
+    #[view(getPenaltyScheduleForToken)]
+    fn get_penalty_schedule_for_token(
+        &self,
+        token_nonce: u64,
+    ) -> MultiValueEncoded<MultiValue2<Epoch, Percent>> {
+        let attributes: LockedTokenAttributes<Self::Api> =
+            self.locked_token().get_token_attributes(token_nonce);
+        let lock_options = self.get_lock_options();
+        let curve = self.get_penalty_curve();
+
+        let mut schedule = MultiValueEncoded::new();
+        let current_epoch = self.blockchain().get_block_epoch();
+        for epoch in current_epoch..attributes.unlock_epoch {
+            let penalty_percentage = self.calculate_penalty_percentage_for_curve(
+                &lock_options,
+                &curve,
+                attributes.unlock_epoch - epoch,
+            );
+            schedule.push((epoch, penalty_percentage).into());
+        }
+
+        schedule
+    }
+
     /// Calculates the penalty that would be incurred if `token_amount` tokens
     /// were to have their lock epochs reduced from `prev_lock_epochs` to
     /// `new_lock_epochs`. For full unlock, `new_lock_epochs` should be 0.
@@ -1,7 +1,26 @@
 elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
 
-use crate::energy::Energy;
+use crate::{energy::Energy, events::VirtualLockEvent};
 use common_structs::Epoch;
+use week_timekeeping::Week;
+
+pub const MAX_VIRTUAL_LOCK_HISTORY_RESULTS: usize = 100;
+
+#[derive(TypeAbi, TopEncode, TopDecode, Clone, PartialEq, Debug)]
+pub struct VirtualLockUsage<M: ManagedTypeApi> {
+    pub week: Week,
+    pub amount: BigUint<M>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct VirtualLockEntry<M: ManagedTypeApi> {
+    pub dest_address: ManagedAddress<M>,
+    pub energy_address: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+    pub unlock_epoch: Epoch,
+    pub lock_epoch: Epoch,
+}
 
 #[elrond_wasm::module]
 pub trait VirtualLockModule:
@@ -19,7 +38,53 @@ pub trait VirtualLockModule:
     + crate::extend_lock::ExtendLockModule
     + sc_whitelist_module::SCWhitelistModule
     + legacy_token_decode_module::LegacyTokenDecodeModule
+    + week_timekeeping::WeekTimekeepingModule
 {
+    /// Sets the amount the whitelisted SC can lock through lockVirtual each week.
+    /// Whitelisted SCs without a quota cannot use lockVirtual,
+    /// see setVirtualLockQuotas for the SCs whitelisted before the quotas were introduced.
+    #[only_owner]
+    #[endpoint(setVirtualLockQuota)]
+    fn set_virtual_lock_quota(&self, sc_address: ManagedAddress, amount_per_week: BigUint) {
+        self.require_sc_address(&sc_address);
+        require!(amount_per_week > 0, "Invalid quota");
+
+        self.virtual_lock_quota(&sc_address).set(&amount_per_week);
+    }
+
+    /// Sets the weekly quotas of SCs that are already whitelisted,
+    /// as pairs of (sc_address, amount_per_week).
+    ///
+    /// Before quotas were introduced, whitelisted SCs could use lockVirtual without limit.
+    /// Their lockVirtual calls fail until a quota is set, so this must be called
+    /// in the same batch as the upgrade that introduces the quotas,
+    /// for every whitelisted SC that uses lockVirtual (e.g. farms with locked rewards,
+    /// the fees collector).
+    #[only_owner]
+    #[endpoint(setVirtualLockQuotas)]
+    fn set_virtual_lock_quotas(
+        &self,
+        quotas: MultiValueEncoded<MultiValue2<ManagedAddress, BigUint>>,
+    ) {
+        for quota in quotas {
+            let (sc_address, amount_per_week) = quota.into_tuple();
+            self.require_sc_address_whitelisted(&sc_address);
+            require!(amount_per_week > 0, "Invalid quota");
+
+            self.virtual_lock_quota(&sc_address).set(&amount_per_week);
+        }
+    }
+
+    #[only_owner]
+    #[endpoint(removeVirtualLockQuota)]
+    fn remove_virtual_lock_quota(&self, sc_address: ManagedAddress) {
+        self.virtual_lock_quota(&sc_address).clear();
+    }
+
+    /// Mints LOCKED tokens to `dest_address` and adds their energy to `energy_address`,
+    /// without receiving the base asset. Only callable by whitelisted SCs,
+    /// within their weekly quota.
+    /// Each call is recorded in the caller's virtual lock history.
     #[endpoint(lockVirtual)]
     fn lock_virtual(
         &self,
@@ -39,13 +104,19 @@ pub trait VirtualLockModule:
         let caller = self.blockchain().get_caller();
         self.require_sc_address_whitelisted(&caller);
 
+        self.use_virtual_lock_quota(&caller, &amount);
+        self.total_virtual_locked_amount()
+            .update(|total| *total += &amount);
+        self.virtual_locked_amount(&caller)
+            .update(|total| *total += &amount);
+
         let current_epoch = self.blockchain().get_block_epoch();
         let unlock_epoch = self.unlock_epoch_to_start_of_month(current_epoch + lock_epochs);
 
         let locked_tokens =
             self.update_energy(&energy_address, |energy: &mut Energy<Self::Api>| {
                 self.lock_base_asset(
-                    EsdtTokenPayment::new(token_id, 0, amount),
+                    EsdtTokenPayment::new(token_id, 0, amount.clone()),
                     unlock_epoch,
                     current_epoch,
                     energy,
@@ -58,6 +129,109 @@ pub trait VirtualLockModule:
             &locked_tokens.amount,
         );
 
+        self.virtual_lock_history(&caller).push(&VirtualLockEntry {
+            dest_address: dest_address.clone(),
+            energy_address: energy_address.clone(),
+            amount,
+            unlock_epoch,
+            lock_epoch: current_epoch,
+        });
+        self.emit_virtual_lock_event(
+            &caller,
+            VirtualLockEvent {
+                dest_address,
+                energy_address,
+                locked_tokens: locked_tokens.clone(),
+                unlock_epoch,
+            },
+        );
+
         locked_tokens
     }
+
+    fn use_virtual_lock_quota(&self, caller: &ManagedAddress, amount: &BigUint) {
+        let quota_mapper = self.virtual_lock_quota(caller);
+        require!(!quota_mapper.is_empty(), "No virtual lock quota");
+
+        let current_week = self.get_current_week();
+        let usage_mapper = self.virtual_lock_usage(caller);
+        let mut usage = if !usage_mapper.is_empty() {
+            usage_mapper.get()
+        } else {
+            VirtualLockUsage {
+                week: current_week,
+                amount: BigUint::zero(),
+            }
+        };
+        if usage.week != current_week {
+            usage.week = current_week;
+            usage.amount = BigUint::zero();
+        }
+
+        usage.amount += amount;
+        require!(
+            usage.amount <= quota_mapper.get(),
+            "Virtual lock quota exceeded"
+        );
+
+        usage_mapper.set(&usage);
+    }
+
+    /// Returns the virtual locks of the given caller, oldest first.
+    /// start_index is 1-based.
+    #[view(getVirtualLockHistory)]
+    fn get_virtual_lock_history(
+        &self,
+        caller: ManagedAddress,
+        start_index: usize,
+        max_results: usize,
+    ) -> MultiValueEncoded<VirtualLockEntry<Self::Api>> {
+        require!(start_index > 0, "Invalid start index");
+        require!(
+            max_results <= MAX_VIRTUAL_LOCK_HISTORY_RESULTS,
+            "Too many results requested"
+        );
+
+        let history_mapper = self.virtual_lock_history(&caller);
+        let history_len = history_mapper.len();
+        let mut result = MultiValueEncoded::new();
+        let mut index = start_index;
+        while index <= history_len && index < start_index + max_results {
+            result.push(history_mapper.get(index));
+
+            index += 1;
+        }
+
+        result
+    }
+
+    #[view(getVirtualLockHistoryLength)]
+    fn get_virtual_lock_history_length(&self, caller: ManagedAddress) -> usize {
+        self.virtual_lock_history(&caller).len()
+    }
+
+    #[view(getVirtualLockQuota)]
+    #[storage_mapper("virtualLockQuota")]
+    fn virtual_lock_quota(&self, sc_address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getVirtualLockUsage)]
+    #[storage_mapper("virtualLockUsage")]
+    fn virtual_lock_usage(
+        &self,
+        sc_address: &ManagedAddress,
+    ) -> SingleValueMapper<VirtualLockUsage<Self::Api>>;
+
+    #[view(getVirtualLockedAmount)]
+    #[storage_mapper("virtualLockedAmount")]
+    fn virtual_locked_amount(&self, sc_address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalVirtualLockedAmount)]
+    #[storage_mapper("totalVirtualLockedAmount")]
+    fn total_virtual_locked_amount(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("virtualLockHistory")]
+    fn virtual_lock_history(
+        &self,
+        sc_address: &ManagedAddress,
+    ) -> VecMapper<VirtualLockEntry<Self::Api>>;
 }
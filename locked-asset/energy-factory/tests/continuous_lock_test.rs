@@ -0,0 +1,74 @@
+mod energy_factory_setup;
+
+use energy_factory::continuous_lock::ContinuousLockModule;
+use energy_factory_setup::*;
+use simple_lock::locked_token::LockedTokenAttributes;
+
+use elrond_wasm_debug::{managed_token_id_wrapped, rust_biguint, DebugApi};
+
+#[test]
+fn continuous_lock_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let owner = setup.owner.clone();
+    let first_user = setup.first_user.clone();
+    let half_balance = USER_BALANCE / 2;
+    let lock_epochs = 400;
+
+    let current_epoch = 5;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    // not enabled yet
+    setup
+        .lock_continuous(&first_user, BASE_ASSET_TOKEN_ID, half_balance, lock_epochs)
+        .assert_user_error("Continuous lock not enabled");
+
+    // max lock epochs over the longest lock option
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_continuous_lock_epochs(LOCK_OPTIONS[0], LOCK_OPTIONS[2] + 1);
+        })
+        .assert_user_error("Invalid continuous lock epochs");
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_continuous_lock_epochs(LOCK_OPTIONS[0], LOCK_OPTIONS[2]);
+        })
+        .assert_ok();
+
+    setup
+        .lock_continuous(&first_user, BASE_ASSET_TOKEN_ID, half_balance, 100)
+        .assert_user_error("Invalid lock epochs");
+
+    setup
+        .lock_continuous(&first_user, BASE_ASSET_TOKEN_ID, half_balance, lock_epochs)
+        .assert_ok();
+
+    // unlock epoch is not rounded to the start of the month
+    let unlock_epoch = current_epoch + lock_epochs;
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(half_balance),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch,
+        }),
+    );
+
+    let expected_energy = rust_biguint!(half_balance) * lock_epochs;
+    assert_eq!(setup.get_user_energy(&first_user), expected_energy);
+
+    // penalty is interpolated between the first and second lock options
+    let penalty_amount = setup.get_penalty_amount(half_balance, lock_epochs, 0);
+    let penalty_percentage = PENALTY_PERCENTAGES[0]
+        + (PENALTY_PERCENTAGES[1] - PENALTY_PERCENTAGES[0]) * (lock_epochs - LOCK_OPTIONS[0])
+            / (LOCK_OPTIONS[1] - LOCK_OPTIONS[0]);
+    assert_eq!(
+        penalty_amount,
+        rust_biguint!(half_balance) * penalty_percentage / 10_000u64
+    );
+}
@@ -0,0 +1,88 @@
+mod energy_factory_setup;
+
+use energy_factory::energy_checkpoints::EnergyCheckpointsModule;
+use energy_factory_setup::*;
+
+use elrond_wasm_debug::{managed_address, rust_biguint};
+
+#[test]
+fn energy_checkpoints_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    setup.b_mock.set_block_epoch(1);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    setup.b_mock.set_block_epoch(10);
+    setup
+        .lock(
+            &second_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    let unlock_epoch = to_start_of_month(1 + LOCK_OPTIONS[0]);
+    assert_eq!(unlock_epoch, to_start_of_month(10 + LOCK_OPTIONS[0]));
+
+    setup.b_mock.set_block_epoch(20);
+    setup.unlock_early(&first_user, 1, half_balance).assert_ok();
+
+    // the current epoch can't be queried, as it can still change
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let _ = sc.get_energy_at_epoch(managed_address!(&first_user), 20);
+        })
+        .assert_user_error("Can only query past epochs");
+
+    setup.b_mock.set_block_epoch(21);
+
+    // no energy before the first lock
+    assert_eq!(setup.get_energy_at_epoch(&first_user, 0), rust_biguint!(0));
+    assert_eq!(setup.get_energy_at_epoch(&second_user, 5), rust_biguint!(0));
+
+    // historical energy is kept after the early unlock
+    let expected_energy_at_15 = rust_biguint!(half_balance) * (unlock_epoch - 15);
+    assert_eq!(
+        setup.get_energy_at_epoch(&first_user, 15),
+        expected_energy_at_15
+    );
+    assert_eq!(
+        setup.get_energy_at_epoch(&second_user, 15),
+        expected_energy_at_15
+    );
+    assert_eq!(setup.get_energy_at_epoch(&first_user, 20), rust_biguint!(0));
+
+    // total energy
+    assert_eq!(
+        setup.get_total_energy_at_epoch(5),
+        rust_biguint!(half_balance) * (unlock_epoch - 5)
+    );
+    assert_eq!(
+        setup.get_total_energy_at_epoch(15),
+        expected_energy_at_15 * 2u32
+    );
+    assert_eq!(
+        setup.get_total_energy_at_epoch(20),
+        rust_biguint!(half_balance) * (unlock_epoch - 20)
+    );
+
+    // future epochs can't be queried
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let _ = sc.get_energy_at_epoch(managed_address!(&first_user), 22);
+        })
+        .assert_user_error("Can only query past epochs");
+}
@@ -0,0 +1,121 @@
+mod energy_factory_setup;
+
+use energy_factory_setup::*;
+
+use elrond_wasm_debug::rust_biguint;
+use elrond_wasm_modules::pause::PauseModule;
+
+#[test]
+fn delegate_and_revoke_energy_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+
+    let current_epoch = 1;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            USER_BALANCE,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    let unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[0]);
+    let full_energy = rust_biguint!(USER_BALANCE) * (unlock_epoch - current_epoch);
+    let half_energy = &full_energy / 2u32;
+    assert_eq!(setup.get_user_energy(&first_user), full_energy);
+    assert_eq!(setup.get_user_energy(&second_user), rust_biguint!(0));
+
+    // delegate half
+    setup
+        .delegate_energy(&first_user, &second_user, 5_000)
+        .assert_ok();
+    assert_eq!(setup.get_user_energy(&first_user), half_energy);
+    assert_eq!(setup.get_user_energy(&second_user), half_energy);
+
+    // update to 100%
+    setup
+        .delegate_energy(&first_user, &second_user, 10_000)
+        .assert_ok();
+    assert_eq!(setup.get_user_energy(&first_user), rust_biguint!(0));
+    assert_eq!(setup.get_user_energy(&second_user), full_energy);
+
+    // can't revoke in the same week
+    setup
+        .revoke_energy_delegation(&first_user, &second_user)
+        .assert_user_error("Delegation can only be lowered starting next week");
+    setup
+        .delegate_energy(&first_user, &second_user, 5_000)
+        .assert_user_error("Delegation can only be lowered starting next week");
+
+    // revoke next week
+    let current_epoch = current_epoch + EPOCHS_IN_WEEK;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    let full_energy = rust_biguint!(USER_BALANCE) * (unlock_epoch - current_epoch);
+    setup
+        .revoke_energy_delegation(&first_user, &second_user)
+        .assert_ok();
+    assert_eq!(setup.get_user_energy(&first_user), full_energy);
+    assert_eq!(setup.get_user_energy(&second_user), rust_biguint!(0));
+
+    // the returned energy can't be delegated again in the same week
+    let third_user = setup.b_mock.create_user_account(&rust_biguint!(0));
+    setup
+        .delegate_energy(&first_user, &third_user, 10_000)
+        .assert_user_error("Cannot delegate in the same week as a revocation");
+
+    let current_epoch = current_epoch + EPOCHS_IN_WEEK;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    let full_energy = rust_biguint!(USER_BALANCE) * (unlock_epoch - current_epoch);
+    setup
+        .delegate_energy(&first_user, &third_user, 10_000)
+        .assert_ok();
+    assert_eq!(setup.get_user_energy(&first_user), rust_biguint!(0));
+    assert_eq!(setup.get_user_energy(&third_user), full_energy);
+}
+
+#[test]
+fn delegate_energy_errors_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+
+    setup
+        .delegate_energy(&first_user, &first_user, 5_000)
+        .assert_user_error("Cannot delegate to self");
+
+    setup
+        .delegate_energy(&first_user, &second_user, 0)
+        .assert_user_error("Invalid percent");
+
+    setup
+        .delegate_energy(&first_user, &second_user, 10_001)
+        .assert_user_error("Invalid percent");
+
+    setup
+        .revoke_energy_delegation(&first_user, &second_user)
+        .assert_user_error("Delegation not found");
+
+    setup
+        .delegate_energy(&first_user, &second_user, 6_000)
+        .assert_ok();
+    setup
+        .delegate_energy(&second_user, &first_user, 5_000)
+        .assert_ok();
+
+    setup.b_mock.set_block_epoch(EPOCHS_IN_WEEK);
+    setup
+        .b_mock
+        .execute_tx(&setup.owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_paused(true);
+        })
+        .assert_ok();
+    setup
+        .revoke_energy_delegation(&first_user, &second_user)
+        .assert_user_error("Contract is paused");
+}
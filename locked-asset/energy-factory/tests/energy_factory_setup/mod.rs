@@ -15,8 +15,11 @@ use elrond_wasm_debug::{
 };
 use elrond_wasm_modules::pause::PauseModule;
 use energy_factory::{
-    energy::EnergyModule, unlock_with_penalty::UnlockWithPenaltyModule, unstake::UnstakeModule,
-    SimpleLockEnergy,
+    energy::EnergyModule, energy_checkpoints::EnergyCheckpointsModule,
+    energy_delegation::EnergyDelegationModule, energy_projection::EnergyProjectionModule,
+    multi_asset_lock::MultiAssetLockModule, perpetual_lock::PerpetualLockModule,
+    token_splitting::TokenSplittingModule, unlock_with_penalty::UnlockWithPenaltyModule,
+    unstake::UnstakeModule, SimpleLockEnergy,
 };
 use simple_lock::locked_token::LockedTokenModule;
 
@@ -30,6 +33,7 @@ pub const FIRST_UPDATED_BLOCK_NONCE: u64 = 2_286_815; // first nonce for updated
 pub static BASE_ASSET_TOKEN_ID: &[u8] = b"MEX-123456";
 pub static LOCKED_TOKEN_ID: &[u8] = b"LOCKED-123456";
 pub static LEGACY_LOCKED_TOKEN_ID: &[u8] = b"LEGACY-123456";
+pub static LOCKED_ASSET_TOKEN_ID: &[u8] = b"LKASSET-123456";
 
 pub static LOCK_OPTIONS: &[u64] = &[EPOCHS_IN_YEAR, 2 * EPOCHS_IN_YEAR, 4 * EPOCHS_IN_YEAR]; // 1, 2 or 4 years
 pub static PENALTY_PERCENTAGES: &[u64] = &[4_000, 6_000, 8_000];
@@ -81,6 +85,8 @@ where
 
                 sc.locked_token()
                     .set_token_id(managed_token_id!(LOCKED_TOKEN_ID));
+                sc.locked_asset_token()
+                    .set_token_id(managed_token_id!(LOCKED_ASSET_TOKEN_ID));
                 sc.set_paused(false);
                 sc.set_token_unstake_address(managed_address!(token_unstake_wrapper.address_ref()));
             })
@@ -102,6 +108,11 @@ where
                 EsdtLocalRole::Transfer,
             ],
         );
+        b_mock.set_esdt_local_roles(
+            sc_wrapper.address_ref(),
+            LOCKED_ASSET_TOKEN_ID,
+            &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftBurn],
+        );
         b_mock.set_esdt_local_roles(
             sc_wrapper.address_ref(),
             LEGACY_LOCKED_TOKEN_ID,
@@ -165,6 +176,28 @@ where
         )
     }
 
+    pub fn lock_continuous(
+        &mut self,
+        caller: &Address,
+        token_id: &[u8],
+        amount: u64,
+        lock_epochs: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.sc_wrapper,
+            token_id,
+            0,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.lock_tokens_continuous_endpoint(
+                    lock_epochs,
+                    OptionalValue::Some(managed_address!(caller)),
+                );
+            },
+        )
+    }
+
     pub fn extend_locking_period(
         &mut self,
         caller: &Address,
@@ -251,6 +284,120 @@ where
         result
     }
 
+    pub fn delegate_energy(
+        &mut self,
+        caller: &Address,
+        delegatee: &Address,
+        percent: u64,
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.sc_wrapper, &rust_biguint!(0), |sc| {
+                sc.delegate_energy(managed_address!(delegatee), percent);
+            })
+    }
+
+    pub fn revoke_energy_delegation(&mut self, caller: &Address, delegatee: &Address) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.sc_wrapper, &rust_biguint!(0), |sc| {
+                sc.revoke_energy_delegation(managed_address!(delegatee));
+            })
+    }
+
+    pub fn split_tokens(
+        &mut self,
+        caller: &Address,
+        token_id: &[u8],
+        token_nonce: u64,
+        amount: u64,
+        output_amounts: &[u64],
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.sc_wrapper,
+            token_id,
+            token_nonce,
+            &rust_biguint!(amount),
+            |sc| {
+                let mut amounts = MultiValueEncoded::new();
+                for output_amount in output_amounts {
+                    amounts.push(managed_biguint!(*output_amount));
+                }
+
+                let _ = sc.split_tokens(amounts);
+            },
+        )
+    }
+
+    pub fn enable_perpetual_lock(
+        &mut self,
+        caller: &Address,
+        token_nonce: u64,
+        amount: u64,
+        lock_epochs: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.sc_wrapper,
+            LOCKED_TOKEN_ID,
+            token_nonce,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.enable_perpetual_lock(lock_epochs);
+            },
+        )
+    }
+
+    pub fn refresh_perpetual_locks(&mut self, users: &[&Address]) -> TxResult {
+        self.b_mock
+            .execute_tx(&self.owner, &self.sc_wrapper, &rust_biguint!(0), |sc| {
+                let mut managed_users = MultiValueEncoded::new();
+                for user in users {
+                    managed_users.push(managed_address!(*user));
+                }
+
+                sc.refresh_perpetual_locks(managed_users);
+            })
+    }
+
+    pub fn disable_perpetual_lock(&mut self, caller: &Address) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.sc_wrapper, &rust_biguint!(0), |sc| {
+                let _ = sc.disable_perpetual_lock();
+            })
+    }
+
+    pub fn lock_asset(
+        &mut self,
+        caller: &Address,
+        token_id: &[u8],
+        amount: u64,
+        lock_epochs: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.sc_wrapper,
+            token_id,
+            0,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.lock_asset(lock_epochs, OptionalValue::None);
+            },
+        )
+    }
+
+    pub fn unlock_asset(&mut self, caller: &Address, token_nonce: u64, amount: u64) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.sc_wrapper,
+            LOCKED_ASSET_TOKEN_ID,
+            token_nonce,
+            &rust_biguint!(amount),
+            |sc| {
+                let _ = sc.unlock_asset();
+            },
+        )
+    }
+
     pub fn get_user_energy(&mut self, user: &Address) -> num_bigint::BigUint {
         let mut result = rust_biguint!(0);
         self.b_mock
@@ -262,6 +409,58 @@ where
 
         result
     }
+
+    pub fn get_energy_at_epoch(&mut self, user: &Address, epoch: u64) -> num_bigint::BigUint {
+        let mut result = rust_biguint!(0);
+        self.b_mock
+            .execute_query(&self.sc_wrapper, |sc| {
+                let managed_result = sc.get_energy_at_epoch(managed_address!(user), epoch);
+                result = to_rust_biguint(managed_result);
+            })
+            .assert_ok();
+
+        result
+    }
+
+    pub fn get_total_energy_at_epoch(&mut self, epoch: u64) -> num_bigint::BigUint {
+        let mut result = rust_biguint!(0);
+        self.b_mock
+            .execute_query(&self.sc_wrapper, |sc| {
+                let managed_result = sc.get_total_energy_at_epoch(epoch);
+                result = to_rust_biguint(managed_result);
+            })
+            .assert_ok();
+
+        result
+    }
+
+    pub fn get_projected_energy(
+        &mut self,
+        user: &Address,
+        epoch: u64,
+        extend_lock_option: u64,
+        positions: &[(u64, u64)],
+    ) -> num_bigint::BigUint {
+        let mut result = rust_biguint!(0);
+        self.b_mock
+            .execute_query(&self.sc_wrapper, |sc| {
+                let mut managed_positions = MultiValueEncoded::new();
+                for (token_nonce, amount) in positions {
+                    managed_positions.push((*token_nonce, managed_biguint!(*amount)).into());
+                }
+
+                let managed_result = sc.get_projected_energy(
+                    managed_address!(user),
+                    epoch,
+                    extend_lock_option,
+                    managed_positions,
+                );
+                result = to_rust_biguint(managed_result);
+            })
+            .assert_ok();
+
+        result
+    }
 }
 
 pub fn to_rust_biguint(
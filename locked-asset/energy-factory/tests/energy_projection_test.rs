@@ -0,0 +1,108 @@
+mod energy_factory_setup;
+
+use elrond_wasm::types::MultiValueEncoded;
+use energy_factory::energy_projection::EnergyProjectionModule;
+use energy_factory_setup::*;
+
+use elrond_wasm_debug::{managed_address, rust_biguint};
+
+#[test]
+fn projected_energy_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    let current_epoch = 1;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[1],
+        )
+        .assert_ok();
+
+    let first_unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[0]);
+    let second_unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[1]);
+    let positions = [(1, half_balance), (2, half_balance)];
+
+    // same as the energy entry while no position is unlockable
+    let projected_energy = setup.get_projected_energy(&first_user, current_epoch, 0, &positions);
+    assert_eq!(projected_energy, setup.get_user_energy(&first_user));
+
+    // first position does not contribute after its unlock epoch
+    let future_epoch = first_unlock_epoch + 40;
+    let projected_energy = setup.get_projected_energy(&first_user, future_epoch, 0, &positions);
+    assert_eq!(
+        projected_energy,
+        rust_biguint!(half_balance) * (second_unlock_epoch - future_epoch)
+    );
+
+    // extend all positions with the longest lock option, as through lockTokens
+    let extended_unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[2]);
+    let projected_energy =
+        setup.get_projected_energy(&first_user, future_epoch, LOCK_OPTIONS[2], &positions);
+    let expected_energy = rust_biguint!(USER_BALANCE) * (extended_unlock_epoch - future_epoch);
+    assert_eq!(projected_energy, expected_energy);
+
+    // positions with a later unlock epoch are not affected
+    let projected_energy =
+        setup.get_projected_energy(&first_user, future_epoch, LOCK_OPTIONS[0], &positions);
+    assert_eq!(
+        projected_energy,
+        rust_biguint!(half_balance) * (second_unlock_epoch - future_epoch)
+    );
+
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let _ = sc.get_projected_energy(
+                managed_address!(&first_user),
+                future_epoch,
+                42,
+                MultiValueEncoded::new(),
+            );
+        })
+        .assert_user_error("Invalid lock choice");
+
+    // delegate half of the energy
+    setup
+        .delegate_energy(&first_user, &second_user, 5_000)
+        .assert_ok();
+    let projected_energy = setup.get_projected_energy(&first_user, current_epoch, 0, &positions);
+    assert_eq!(projected_energy, setup.get_user_energy(&first_user));
+
+    // the received energy depletes linearly
+    let total_energy = rust_biguint!(half_balance) * (first_unlock_epoch - current_epoch)
+        + rust_biguint!(half_balance) * (second_unlock_epoch - current_epoch);
+    let received_energy = total_energy / 2u32
+        - rust_biguint!(USER_BALANCE / 2) * (first_unlock_epoch - current_epoch);
+    assert_eq!(
+        setup.get_projected_energy(&second_user, first_unlock_epoch, 0, &[]),
+        received_energy
+    );
+
+    // past epochs can't be projected
+    setup.b_mock.set_block_epoch(10);
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let _ = sc.get_projected_energy(
+                managed_address!(&first_user),
+                5,
+                0,
+                MultiValueEncoded::new(),
+            );
+        })
+        .assert_user_error("Cannot project past epochs");
+}
@@ -0,0 +1,90 @@
+mod energy_factory_setup;
+
+use elrond_wasm::types::{ManagedAddress, MultiValueEncoded};
+use energy_factory::{energy::EnergyModule, energy_checkpoints::EnergyCheckpointsModule};
+use energy_factory_setup::*;
+
+use elrond_wasm_debug::{managed_address, managed_biguint, DebugApi};
+
+#[test]
+fn batch_energy_views_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    let current_epoch = 1;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    setup
+        .lock(
+            &second_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[1],
+        )
+        .assert_ok();
+
+    let first_user_energy = setup.get_user_energy(&first_user);
+    let second_user_energy = setup.get_user_energy(&second_user);
+
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let mut users = MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new();
+            users.push(managed_address!(&second_user));
+            users.push(managed_address!(&first_user));
+
+            let amounts: Vec<_> = sc
+                .get_energy_amounts_for_users(users.clone())
+                .into_iter()
+                .collect();
+            assert_eq!(amounts.len(), 2);
+            assert_eq!(to_rust_biguint(amounts[0].clone()), second_user_energy);
+            assert_eq!(to_rust_biguint(amounts[1].clone()), first_user_energy);
+
+            let entries: Vec<_> = sc.get_energy_entries_for_users(users).into_iter().collect();
+            assert_eq!(
+                entries[1].get_total_locked_tokens(),
+                &managed_biguint!(half_balance)
+            );
+
+            // pagination
+            assert_eq!(sc.get_nr_users_with_energy(), 2);
+            let first_page: Vec<_> = sc.get_users_with_energy(1, 1).into_iter().collect();
+            assert_eq!(first_page.len(), 1);
+            let (user, _) = first_page[0].clone().into_tuple();
+            assert_eq!(user, managed_address!(&first_user));
+
+            let second_page: Vec<_> = sc.get_users_with_energy(2, 10).into_iter().collect();
+            assert_eq!(second_page.len(), 1);
+            let (user, _) = second_page[0].clone().into_tuple();
+            assert_eq!(user, managed_address!(&second_user));
+
+            // totals
+            let (total_locked_tokens, total_energy) =
+                sc.get_total_locked_tokens_and_energy().into_tuple();
+            assert_eq!(total_locked_tokens, managed_biguint!(USER_BALANCE));
+            assert_eq!(
+                to_rust_biguint(total_energy),
+                &first_user_energy + &second_user_energy
+            );
+        })
+        .assert_ok();
+
+    // users are removed from the list after unlocking everything
+    setup.unlock_early(&first_user, 1, half_balance).assert_ok();
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert_eq!(sc.get_nr_users_with_energy(), 1);
+        })
+        .assert_ok();
+}
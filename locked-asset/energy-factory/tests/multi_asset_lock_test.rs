@@ -0,0 +1,262 @@
+mod energy_factory_setup;
+
+use elrond_wasm::elrond_codec::multi_types::OptionalValue;
+use energy_factory::{
+    energy::EnergyModule, multi_asset_lock::MultiAssetLockModule,
+    unlock_with_penalty::UnlockWithPenaltyModule,
+};
+use energy_factory_setup::*;
+use pair_mock::PairMock;
+use simple_lock::locked_token::LockedTokenAttributes;
+
+use elrond_wasm_debug::{
+    managed_address, managed_biguint, managed_token_id, managed_token_id_wrapped, rust_biguint,
+    DebugApi,
+};
+
+static LP_TOKEN_ID: &[u8] = b"LPTOK-123456";
+static WEGLD_TOKEN_ID: &[u8] = b"WEGLD-123456";
+const PAIR_MOCK_WASM_PATH: &str = "pair-mock/output/pair-mock.wasm";
+
+#[test]
+fn lock_and_unlock_asset_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let owner = setup.owner.clone();
+    let lp_amount = 1_000_000u64;
+
+    let rust_zero = rust_biguint!(0);
+    let pair_wrapper = setup.b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        pair_mock::contract_obj,
+        PAIR_MOCK_WASM_PATH,
+    );
+    setup
+        .b_mock
+        .execute_tx(&owner, &pair_wrapper, &rust_zero, |sc| {
+            sc.init(
+                OptionalValue::Some(managed_token_id!(BASE_ASSET_TOKEN_ID)),
+                OptionalValue::Some(managed_token_id!(WEGLD_TOKEN_ID)),
+                OptionalValue::Some(managed_token_id!(LP_TOKEN_ID)),
+                OptionalValue::None,
+                OptionalValue::None,
+                OptionalValue::None,
+            );
+        })
+        .assert_ok();
+    setup
+        .b_mock
+        .set_esdt_balance(&first_user, LP_TOKEN_ID, &rust_biguint!(lp_amount));
+
+    let mut current_epoch = 1;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .lock_asset(&first_user, LP_TOKEN_ID, lp_amount, LOCK_OPTIONS[0])
+        .assert_user_error("Asset not lockable");
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_zero, |sc| {
+            sc.add_lockable_asset(
+                managed_token_id!(LP_TOKEN_ID),
+                managed_address!(pair_wrapper.address_ref()),
+            );
+        })
+        .assert_ok();
+
+    setup
+        .lock_asset(&first_user, LP_TOKEN_ID, lp_amount, LOCK_OPTIONS[0])
+        .assert_ok();
+
+    // LP tokens are kept by the SC
+    setup.b_mock.check_esdt_balance(
+        setup.sc_wrapper.address_ref(),
+        LP_TOKEN_ID,
+        &rust_biguint!(lp_amount),
+    );
+
+    // a separate token is used, so contracts consuming LOCKED tokens don't accept it
+    let unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[0]);
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LOCKED_ASSET_TOKEN_ID,
+        1,
+        &rust_biguint!(lp_amount),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(LP_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch,
+        }),
+    );
+
+    // the pair mock values half the position in the base asset
+    let base_asset_amount = lp_amount / 2;
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let position = sc.locked_asset_position(1).get();
+            assert_eq!(position.token_amount, managed_biguint!(lp_amount));
+            assert_eq!(
+                position.base_asset_amount,
+                managed_biguint!(base_asset_amount)
+            );
+            assert!(sc
+                .user_locked_asset_nonces(&managed_address!(&first_user))
+                .contains(&1));
+        })
+        .assert_ok();
+
+    let expected_energy = rust_biguint!(base_asset_amount) * (unlock_epoch - current_epoch);
+    assert_eq!(setup.get_user_energy(&first_user), expected_energy);
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &first_user,
+            &setup.sc_wrapper,
+            LOCKED_ASSET_TOKEN_ID,
+            1,
+            &rust_biguint!(lp_amount),
+            |sc| {
+                sc.unlock_early();
+            },
+        )
+        .assert_user_error("Invalid payment token");
+
+    setup
+        .unlock_asset(&first_user, 1, lp_amount)
+        .assert_user_error("Cannot unlock yet");
+
+    current_epoch = unlock_epoch;
+    setup.b_mock.set_block_epoch(current_epoch);
+
+    // only the address the position was created for can unlock it
+    let second_user = setup.second_user.clone();
+    setup.b_mock.set_nft_balance(
+        &second_user,
+        LOCKED_ASSET_TOKEN_ID,
+        1,
+        &rust_biguint!(1),
+        &LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(LP_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch,
+        },
+    );
+    setup
+        .unlock_asset(&second_user, 1, 1)
+        .assert_user_error("Position was not created for caller");
+
+    // partial unlocks remove the whole base asset equivalent in the end
+    setup
+        .unlock_asset(&first_user, 1, lp_amount / 3)
+        .assert_ok();
+    setup
+        .unlock_asset(&first_user, 1, lp_amount - lp_amount / 3)
+        .assert_ok();
+
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, LP_TOKEN_ID, &rust_biguint!(lp_amount));
+    assert_eq!(setup.get_user_energy(&first_user), rust_biguint!(0));
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let energy = sc.user_energy(&managed_address!(&first_user)).get();
+            assert_eq!(energy.get_total_locked_tokens(), &managed_biguint!(0));
+            assert!(sc.locked_asset_position(1).is_empty());
+            assert!(sc
+                .user_locked_asset_nonces(&managed_address!(&first_user))
+                .is_empty());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn lock_asset_new_position_per_lock_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let owner = setup.owner.clone();
+    let first_amount = 1_000u64;
+    let second_amount = 3_001u64;
+
+    let rust_zero = rust_biguint!(0);
+    let pair_wrapper = setup.b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        pair_mock::contract_obj,
+        PAIR_MOCK_WASM_PATH,
+    );
+    setup
+        .b_mock
+        .execute_tx(&owner, &pair_wrapper, &rust_zero, |sc| {
+            sc.init(
+                OptionalValue::Some(managed_token_id!(BASE_ASSET_TOKEN_ID)),
+                OptionalValue::Some(managed_token_id!(WEGLD_TOKEN_ID)),
+                OptionalValue::Some(managed_token_id!(LP_TOKEN_ID)),
+                OptionalValue::None,
+                OptionalValue::None,
+                OptionalValue::None,
+            );
+        })
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_zero, |sc| {
+            sc.add_lockable_asset(
+                managed_token_id!(LP_TOKEN_ID),
+                managed_address!(pair_wrapper.address_ref()),
+            );
+        })
+        .assert_ok();
+    setup
+        .b_mock
+        .set_esdt_balance(&first_user, LP_TOKEN_ID, &rust_biguint!(first_amount));
+    setup
+        .b_mock
+        .set_esdt_balance(&second_user, LP_TOKEN_ID, &rust_biguint!(second_amount));
+
+    let current_epoch = 1;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .lock_asset(&first_user, LP_TOKEN_ID, first_amount, LOCK_OPTIONS[0])
+        .assert_ok();
+    setup
+        .lock_asset(&second_user, LP_TOKEN_ID, second_amount, LOCK_OPTIONS[0])
+        .assert_ok();
+
+    // same attributes, but each lock has its own position
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let first_position = sc.locked_asset_position(1).get();
+            assert_eq!(first_position.token_amount, managed_biguint!(first_amount));
+            assert_eq!(
+                first_position.base_asset_amount,
+                managed_biguint!(first_amount / 2)
+            );
+
+            let second_position = sc.locked_asset_position(2).get();
+            assert_eq!(
+                second_position.token_amount,
+                managed_biguint!(second_amount)
+            );
+            assert_eq!(
+                second_position.base_asset_amount,
+                managed_biguint!(second_amount / 2)
+            );
+        })
+        .assert_ok();
+
+    let unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[0]);
+    assert_eq!(
+        setup.get_user_energy(&second_user),
+        rust_biguint!(second_amount / 2) * (unlock_epoch - current_epoch)
+    );
+    assert_eq!(
+        setup.get_projected_energy(&second_user, current_epoch, 0, &[]),
+        rust_biguint!(second_amount / 2) * (unlock_epoch - current_epoch)
+    );
+}
@@ -0,0 +1,186 @@
+mod energy_factory_setup;
+
+use elrond_wasm::types::{ManagedVec, MultiValueEncoded};
+use energy_factory::{
+    lock_options_endpoints::LockOptionsEndpointsModule,
+    penalty::{LocalPenaltyModule, PenaltyCurve, PenaltyCurveType, PenaltyStep},
+    unlock_with_penalty::UnlockWithPenaltyModule,
+};
+use energy_factory_setup::*;
+
+use elrond_wasm_debug::{rust_biguint, DebugApi};
+
+#[test]
+fn exponential_decay_penalty_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let owner = setup.owner.clone();
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_penalty_curve(PenaltyCurve::ExponentialDecay {
+                half_life_epochs: EPOCHS_IN_YEAR,
+            });
+        })
+        .assert_ok();
+
+    let amount = 1_000_000u64;
+    let max_penalty = PENALTY_PERCENTAGES[2];
+
+    // max lock period
+    let penalty_amount = setup.get_penalty_amount(amount, LOCK_OPTIONS[2], 0);
+    assert_eq!(penalty_amount, rust_biguint!(amount * max_penalty / 10_000));
+
+    // one half-life later
+    let penalty_amount = setup.get_penalty_amount(amount, LOCK_OPTIONS[2] - EPOCHS_IN_YEAR, 0);
+    assert_eq!(
+        penalty_amount,
+        rust_biguint!(amount * max_penalty / 2 / 10_000)
+    );
+
+    // half-way between max and the first halving
+    let penalty_amount = setup.get_penalty_amount(amount, LOCK_OPTIONS[2] - EPOCHS_IN_YEAR / 2, 0);
+    assert_eq!(
+        penalty_amount,
+        rust_biguint!(amount * (max_penalty * 3 / 4) / 10_000)
+    );
+}
+
+#[test]
+fn step_table_penalty_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let owner = setup.owner.clone();
+
+    // steps do not cover the longest lock option
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let mut steps = ManagedVec::<DebugApi, PenaltyStep>::new();
+            steps.push(PenaltyStep {
+                max_lock_epochs_remaining: LOCK_OPTIONS[0],
+                penalty_percentage: 1_000,
+            });
+            sc.set_penalty_curve(PenaltyCurve::StepTable { steps });
+        })
+        .assert_user_error("Invalid penalty curve");
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let mut steps = ManagedVec::<DebugApi, PenaltyStep>::new();
+            steps.push(PenaltyStep {
+                max_lock_epochs_remaining: LOCK_OPTIONS[0],
+                penalty_percentage: 1_000,
+            });
+            steps.push(PenaltyStep {
+                max_lock_epochs_remaining: LOCK_OPTIONS[2],
+                penalty_percentage: 5_000,
+            });
+            sc.set_penalty_curve(PenaltyCurve::StepTable { steps });
+        })
+        .assert_ok();
+
+    let amount = 1_000_000u64;
+    let penalty_amount = setup.get_penalty_amount(amount, 100, 0);
+    assert_eq!(penalty_amount, rust_biguint!(amount * 1_000 / 10_000));
+
+    let penalty_amount = setup.get_penalty_amount(amount, LOCK_OPTIONS[0] + 1, 0);
+    assert_eq!(penalty_amount, rust_biguint!(amount * 5_000 / 10_000));
+
+    // schedule for a token locked for 1 year
+    let first_user = setup.first_user.clone();
+    setup.b_mock.set_block_epoch(0);
+    setup
+        .lock(&first_user, BASE_ASSET_TOKEN_ID, amount, LOCK_OPTIONS[0])
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let schedule: Vec<_> = sc.get_penalty_schedule_for_token(1).into_iter().collect();
+            assert_eq!(schedule.len(), LOCK_OPTIONS[0] as usize);
+            for (i, entry) in schedule.into_iter().enumerate() {
+                let (epoch, penalty_percentage) = entry.into_tuple();
+                assert_eq!(epoch, i as u64);
+                assert_eq!(penalty_percentage, 1_000);
+            }
+        })
+        .assert_ok();
+}
+
+#[test]
+fn step_table_longer_lock_option_added_later_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let owner = setup.owner.clone();
+    let new_lock_option = 5 * EPOCHS_IN_YEAR;
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let mut steps = ManagedVec::<DebugApi, PenaltyStep>::new();
+            steps.push(PenaltyStep {
+                max_lock_epochs_remaining: LOCK_OPTIONS[0],
+                penalty_percentage: 1_000,
+            });
+            steps.push(PenaltyStep {
+                max_lock_epochs_remaining: LOCK_OPTIONS[2],
+                penalty_percentage: 5_000,
+            });
+            sc.set_penalty_curve(PenaltyCurve::StepTable { steps });
+
+            let mut new_lock_options = MultiValueEncoded::new();
+            new_lock_options.push((new_lock_option, 9_000).into());
+            sc.add_lock_options(new_lock_options);
+        })
+        .assert_ok();
+
+    // lock epochs above the last step use the last step's percentage
+    let amount = 1_000_000u64;
+    let penalty_amount = setup.get_penalty_amount(amount, new_lock_option, 0);
+    assert_eq!(penalty_amount, rust_biguint!(amount * 5_000 / 10_000));
+
+    let penalty_amount = setup.get_penalty_amount(amount, LOCK_OPTIONS[2] + 1, 0);
+    assert_eq!(penalty_amount, rust_biguint!(amount * 5_000 / 10_000));
+}
+
+#[test]
+fn penalty_fees_collector_percentage_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let owner = setup.owner.clone();
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_penalty_fees_collector_percentage(PenaltyCurveType::Linear, 10_001);
+        })
+        .assert_user_error("Invalid fees collector percentage");
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_penalty_fees_collector_percentage(PenaltyCurveType::Linear, 2_000);
+            sc.set_penalty_fees_collector_percentage(PenaltyCurveType::StepTable, 5_000);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert_eq!(
+                sc.penalty_fees_collector_percentage(PenaltyCurveType::Linear)
+                    .get(),
+                2_000
+            );
+            assert_eq!(
+                sc.penalty_fees_collector_percentage(PenaltyCurveType::ExponentialDecay)
+                    .get(),
+                0
+            );
+            assert_eq!(
+                sc.penalty_fees_collector_percentage(PenaltyCurveType::StepTable)
+                    .get(),
+                5_000
+            );
+        })
+        .assert_ok();
+}
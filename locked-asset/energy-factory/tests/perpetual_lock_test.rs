@@ -0,0 +1,100 @@
+mod energy_factory_setup;
+
+use energy_factory_setup::*;
+use simple_lock::locked_token::LockedTokenAttributes;
+
+use elrond_wasm_debug::{
+    managed_address, managed_biguint, managed_token_id_wrapped, rust_biguint, DebugApi,
+};
+use energy_factory::energy::EnergyModule;
+
+#[test]
+fn perpetual_lock_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    let mut current_epoch = 1;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    setup
+        .disable_perpetual_lock(&first_user)
+        .assert_user_error("No perpetual lock");
+
+    setup
+        .enable_perpetual_lock(&first_user, 1, half_balance, 42)
+        .assert_user_error("Invalid lock choice");
+
+    current_epoch = 10;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .enable_perpetual_lock(&first_user, 1, half_balance / 2, LOCK_OPTIONS[0])
+        .assert_ok();
+
+    setup
+        .enable_perpetual_lock(&first_user, 1, half_balance / 2, LOCK_OPTIONS[1])
+        .assert_user_error("Perpetual lock has a different lock period");
+
+    setup
+        .enable_perpetual_lock(&first_user, 1, half_balance / 2, LOCK_OPTIONS[0])
+        .assert_ok();
+
+    let perpetual_energy = rust_biguint!(half_balance) * LOCK_OPTIONS[0];
+    assert_eq!(setup.get_user_energy(&first_user), perpetual_energy);
+
+    // energy depletes like a regular lock until the next refresh
+    current_epoch = 200;
+    setup.b_mock.set_block_epoch(current_epoch);
+    let perpetual_unlock_epoch = 10 + LOCK_OPTIONS[0];
+    assert_eq!(
+        setup.get_user_energy(&first_user),
+        rust_biguint!(half_balance) * (perpetual_unlock_epoch - current_epoch)
+    );
+
+    setup.refresh_perpetual_locks(&[&first_user]).assert_ok();
+    assert_eq!(setup.get_user_energy(&first_user), perpetual_energy);
+
+    // tokens are accounted as locked, so the energy is tokens * remaining epochs
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let energy = sc.get_updated_energy_entry_for_user(&managed_address!(&first_user));
+            assert_eq!(
+                energy.get_total_locked_tokens(),
+                &managed_biguint!(half_balance)
+            );
+        })
+        .assert_ok();
+
+    setup.disable_perpetual_lock(&first_user).assert_ok();
+
+    let unlock_epoch = to_start_of_month(current_epoch + LOCK_OPTIONS[0]);
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LOCKED_TOKEN_ID,
+        2,
+        &rust_biguint!(half_balance),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch,
+        }),
+    );
+
+    let expected_energy = rust_biguint!(half_balance) * (unlock_epoch - current_epoch);
+    assert_eq!(setup.get_user_energy(&first_user), expected_energy);
+
+    // normal decay after disabling
+    current_epoch += 10;
+    setup.b_mock.set_block_epoch(current_epoch);
+    let expected_energy = rust_biguint!(half_balance) * (unlock_epoch - current_epoch);
+    assert_eq!(setup.get_user_energy(&first_user), expected_energy);
+}
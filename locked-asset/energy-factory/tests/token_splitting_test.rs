@@ -0,0 +1,175 @@
+mod energy_factory_setup;
+
+use energy_factory_setup::*;
+
+use common_structs::{LockedAssetTokenAttributesEx, UnlockMilestoneEx, UnlockScheduleEx};
+use elrond_wasm::{
+    elrond_codec::Empty,
+    types::{EgldOrEsdtTokenIdentifier, ManagedVec},
+};
+use elrond_wasm_debug::{managed_token_id, rust_biguint, DebugApi};
+use simple_lock::locked_token::LockedTokenAttributes;
+
+#[test]
+fn split_locked_tokens_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    setup.b_mock.set_block_epoch(1);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    let energy_before = setup.get_user_energy(&first_user);
+
+    setup
+        .split_tokens(
+            &first_user,
+            LOCKED_TOKEN_ID,
+            1,
+            half_balance,
+            &[half_balance],
+        )
+        .assert_user_error("Invalid number of output tokens");
+
+    setup
+        .split_tokens(&first_user, LOCKED_TOKEN_ID, 1, half_balance, &[100, 200])
+        .assert_user_error("Amounts must add up to the payment amount");
+
+    setup
+        .split_tokens(
+            &first_user,
+            LOCKED_TOKEN_ID,
+            1,
+            half_balance,
+            &[0, half_balance],
+        )
+        .assert_user_error("Amount cannot be 0");
+
+    setup
+        .split_tokens(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            0,
+            half_balance,
+            &[100, half_balance - 100],
+        )
+        .assert_user_error("Invalid payment token");
+
+    let first_amount = half_balance / 4;
+    let second_amount = half_balance - first_amount;
+    setup
+        .split_tokens(
+            &first_user,
+            LOCKED_TOKEN_ID,
+            1,
+            first_amount + second_amount,
+            &[first_amount, second_amount],
+        )
+        .assert_ok();
+
+    // new token nonces, with the same unlock epoch
+    let expected_attributes = LockedTokenAttributes::<DebugApi> {
+        original_token_id: EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(BASE_ASSET_TOKEN_ID)),
+        original_token_nonce: 0,
+        unlock_epoch: to_start_of_month(1 + LOCK_OPTIONS[0]),
+    };
+    setup.b_mock.check_nft_balance::<Empty>(
+        &first_user,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(0),
+        None,
+    );
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LOCKED_TOKEN_ID,
+        2,
+        &rust_biguint!(first_amount),
+        Some(&expected_attributes),
+    );
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LOCKED_TOKEN_ID,
+        3,
+        &rust_biguint!(second_amount),
+        Some(&expected_attributes),
+    );
+    assert_eq!(setup.get_user_energy(&first_user), energy_before);
+
+    // the positions can be used separately
+    setup.unlock_early(&first_user, 2, first_amount).assert_ok();
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LOCKED_TOKEN_ID,
+        3,
+        &rust_biguint!(second_amount),
+        Some(&expected_attributes),
+    );
+}
+
+#[test]
+fn split_legacy_tokens_test() {
+    let _ = DebugApi::dummy();
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+
+    let mut unlock_milestones = ManagedVec::<DebugApi, UnlockMilestoneEx>::new();
+    unlock_milestones.push(UnlockMilestoneEx {
+        unlock_percent: 40_000,
+        unlock_epoch: 91,
+    });
+    unlock_milestones.push(UnlockMilestoneEx {
+        unlock_percent: 60_000,
+        unlock_epoch: 121,
+    });
+    let old_token_attributes = LockedAssetTokenAttributesEx {
+        is_merged: false,
+        unlock_schedule: UnlockScheduleEx { unlock_milestones },
+    };
+    setup.b_mock.set_nft_balance(
+        &first_user,
+        LEGACY_LOCKED_TOKEN_ID,
+        FIRST_UPDATED_BLOCK_NONCE,
+        &rust_biguint!(USER_BALANCE),
+        &old_token_attributes,
+    );
+    let energy_before = setup.get_user_energy(&first_user);
+
+    setup
+        .split_tokens(
+            &first_user,
+            LEGACY_LOCKED_TOKEN_ID,
+            FIRST_UPDATED_BLOCK_NONCE,
+            USER_BALANCE,
+            &[USER_BALANCE / 3],
+        )
+        .assert_user_error("Invalid number of output tokens");
+
+    // legacy tokens are sent back in parts, with the same nonce and attributes
+    let first_amount = USER_BALANCE / 3;
+    let second_amount = USER_BALANCE - first_amount;
+    setup
+        .split_tokens(
+            &first_user,
+            LEGACY_LOCKED_TOKEN_ID,
+            FIRST_UPDATED_BLOCK_NONCE,
+            USER_BALANCE,
+            &[first_amount, second_amount],
+        )
+        .assert_ok();
+
+    setup.b_mock.check_nft_balance(
+        &first_user,
+        LEGACY_LOCKED_TOKEN_ID,
+        FIRST_UPDATED_BLOCK_NONCE,
+        &rust_biguint!(USER_BALANCE),
+        Some(&old_token_attributes),
+    );
+    assert_eq!(setup.get_user_energy(&first_user), energy_before);
+}
@@ -1,6 +1,7 @@
 mod energy_factory_setup;
 
-use energy_factory::virtual_lock::VirtualLockModule;
+use elrond_wasm::types::MultiValueEncoded;
+use energy_factory::virtual_lock::{VirtualLockEntry, VirtualLockModule};
 use energy_factory_setup::*;
 use sc_whitelist_module::SCWhitelistModule;
 use simple_lock::locked_token::LockedTokenAttributes;
@@ -44,12 +45,31 @@ fn virtual_lock_test() {
         })
         .assert_user_error("May only lock the base asset token");
 
+    // whitelisted, but no quota
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.sc_whitelist_addresses()
+                .add(&managed_address!(&first_user));
+
+            sc.lock_virtual(
+                managed_token_id!(BASE_ASSET_TOKEN_ID),
+                managed_biguint!(1_000),
+                LOCK_OPTIONS[0],
+                managed_address!(&second_user),
+                managed_address!(&second_user),
+            );
+        })
+        .assert_user_error("No virtual lock quota");
+
     // lock virtual ok
     setup
         .b_mock
         .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
             sc.sc_whitelist_addresses()
                 .add(&managed_address!(&first_user));
+            sc.virtual_lock_quota(&managed_address!(&first_user))
+                .set(&managed_biguint!(1_000));
 
             sc.lock_virtual(
                 managed_token_id!(BASE_ASSET_TOKEN_ID),
@@ -73,3 +93,168 @@ fn virtual_lock_test() {
         }),
     );
 }
+
+#[test]
+fn virtual_lock_quota_and_history_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+
+    let mut current_epoch = 1;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.sc_whitelist_addresses()
+                .add(&managed_address!(&first_user));
+            sc.virtual_lock_quota(&managed_address!(&first_user))
+                .set(&managed_biguint!(1_500));
+
+            sc.lock_virtual(
+                managed_token_id!(BASE_ASSET_TOKEN_ID),
+                managed_biguint!(1_000),
+                LOCK_OPTIONS[0],
+                managed_address!(&second_user),
+                managed_address!(&second_user),
+            );
+        })
+        .assert_ok();
+
+    // over the weekly quota
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.lock_virtual(
+                managed_token_id!(BASE_ASSET_TOKEN_ID),
+                managed_biguint!(1_000),
+                LOCK_OPTIONS[0],
+                managed_address!(&second_user),
+                managed_address!(&second_user),
+            );
+        })
+        .assert_user_error("Virtual lock quota exceeded");
+
+    // quota resets next week
+    current_epoch = 7;
+    setup.b_mock.set_block_epoch(current_epoch);
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.lock_virtual(
+                managed_token_id!(BASE_ASSET_TOKEN_ID),
+                managed_biguint!(1_000),
+                LOCK_OPTIONS[1],
+                managed_address!(&second_user),
+                managed_address!(&first_user),
+            );
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert_eq!(
+                sc.total_virtual_locked_amount().get(),
+                managed_biguint!(2_000)
+            );
+            assert_eq!(
+                sc.virtual_locked_amount(&managed_address!(&first_user))
+                    .get(),
+                managed_biguint!(2_000)
+            );
+            assert_eq!(
+                sc.get_virtual_lock_history_length(managed_address!(&first_user)),
+                2
+            );
+
+            // epoch 7 is in the second week since deployment
+            let usage = sc.virtual_lock_usage(&managed_address!(&first_user)).get();
+            assert_eq!(usage.week, 2);
+            assert_eq!(usage.amount, managed_biguint!(1_000));
+
+            let history = sc
+                .get_virtual_lock_history(managed_address!(&first_user), 2, 10)
+                .into_iter()
+                .collect::<Vec<_>>();
+            assert_eq!(history.len(), 1);
+            assert_eq!(
+                history[0],
+                VirtualLockEntry {
+                    dest_address: managed_address!(&second_user),
+                    energy_address: managed_address!(&first_user),
+                    amount: managed_biguint!(1_000),
+                    unlock_epoch: to_start_of_month(current_epoch + LOCK_OPTIONS[1]),
+                    lock_epoch: current_epoch,
+                }
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn virtual_lock_quota_migration_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let owner = setup.owner.clone();
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+
+    // whitelisted before the quotas were introduced
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.sc_whitelist_addresses()
+                .add(&managed_address!(&first_user));
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let mut quotas = MultiValueEncoded::new();
+            quotas.push((managed_address!(&first_user), managed_biguint!(1_000)).into());
+            sc.set_virtual_lock_quotas(quotas);
+        })
+        .assert_user_error("Endpoint can only be called by owner");
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let mut quotas = MultiValueEncoded::new();
+            quotas.push((managed_address!(&second_user), managed_biguint!(1_000)).into());
+            sc.set_virtual_lock_quotas(quotas);
+        })
+        .assert_user_error("Item not whitelisted");
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            let mut quotas = MultiValueEncoded::new();
+            quotas.push((managed_address!(&first_user), managed_biguint!(1_000)).into());
+            sc.set_virtual_lock_quotas(quotas);
+        })
+        .assert_ok();
+
+    // lockVirtual works again after the migration, within the seeded quota
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.lock_virtual(
+                managed_token_id!(BASE_ASSET_TOKEN_ID),
+                managed_biguint!(1_000),
+                LOCK_OPTIONS[0],
+                managed_address!(&second_user),
+                managed_address!(&second_user),
+            );
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert_eq!(
+                sc.virtual_lock_quota(&managed_address!(&first_user)).get(),
+                managed_biguint!(1_000)
+            );
+        })
+        .assert_ok();
+}
@@ -9,33 +9,81 @@ elrond_wasm_node::wasm_endpoints! {
     (
         callBack
         addLockOptions
+        addLockableAsset
         addSCAddressToWhitelist
         addToTokenTransferWhitelist
+        delegateEnergy
+        disableContinuousLock
+        disablePerpetualLock
+        enablePerpetualLock
         getBaseAssetTokenId
+        getContinuousLockEpochs
+        getCurrentWeek
+        getDelegatedEnergyAmount
         getEnergyAmountForUser
+        getEnergyAmountsForUsers
+        getEnergyAtEpoch
+        getEnergyDelegations
+        getEnergyEntriesForUsers
         getEnergyEntryForUser
+        getFirstWeekStartEpoch
         getLegacyLockedTokenId
         getLockOptions
+        getLockableAssets
+        getLockedAssetPosition
+        getLockedAssetTokenId
         getLockedTokenId
+        getNrUsersWithEnergy
+        getOwnEnergyEntryForUser
         getPenaltyAmount
+        getPenaltyCurve
+        getPenaltyFeesCollectorPercentage
+        getPenaltyScheduleForToken
+        getPerpetualLock
+        getProjectedEnergy
         getTokenUnstakeScAddress
+        getTotalEnergyAtEpoch
+        getTotalLockedTokensAndEnergy
+        getTotalVirtualLockedAmount
+        getUserLockedAssetNonces
+        getUsersWithEnergy
+        getVirtualLockHistory
+        getVirtualLockHistoryLength
+        getVirtualLockQuota
+        getVirtualLockUsage
+        getVirtualLockedAmount
+        initEnergyCheckpoints
         isPaused
         isSCAddressWhitelisted
+        issueLockedAssetToken
         issueLockedToken
+        lockAsset
         lockTokens
+        lockTokensContinuous
         lockVirtual
         mergeTokens
         migrateOldTokens
         pause
         reduceLockPeriod
+        refreshPerpetualLocks
         removeFromTokenTransferWhitelist
+        removeLockableAsset
         removeSCAddressFromWhitelist
+        removeVirtualLockQuota
         revertUnstake
+        revokeEnergyDelegation
         setBurnRoleLockedToken
+        setContinuousLockEpochs
         setEnergyForOldTokens
+        setPenaltyCurve
+        setPenaltyFeesCollectorPercentage
         setTokenUnstakeAddress
         setTransferRoleLockedToken
         setUserEnergyAfterLockedTokenTransfer
+        setVirtualLockQuota
+        setVirtualLockQuotas
+        splitTokens
+        unlockAsset
         unlockEarly
         unlockTokens
         unpause
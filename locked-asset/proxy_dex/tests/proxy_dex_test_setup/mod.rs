@@ -11,7 +11,10 @@ use elrond_wasm_debug::{
     DebugApi,
 };
 use elrond_wasm_modules::pause::PauseModule;
-use energy_factory::{locked_token_transfer::LockedTokenTransferModule, SimpleLockEnergy};
+use energy_factory::{
+    locked_token_transfer::LockedTokenTransferModule, virtual_lock::VirtualLockModule,
+    SimpleLockEnergy,
+};
 use energy_query::EnergyQueryModule;
 use farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule;
 use farm_token::FarmTokenModule;
@@ -126,6 +129,10 @@ where
         b_mock
             .execute_tx(&owner, &simple_lock_wrapper, &rust_zero, |sc| {
                 sc.add_sc_address_to_whitelist(managed_address!(farm_locked_wrapper.address_ref()));
+                sc.set_virtual_lock_quota(
+                    managed_address!(farm_locked_wrapper.address_ref()),
+                    managed_biguint!(u64::MAX),
+                );
             })
             .assert_ok();
         b_mock
@@ -4,6 +4,10 @@ pub const MAX_PENALTY_PERCENTAGE: u64 = 10_000;
 static LOCKED_TOKEN_ID_STORAGE_KEY: &[u8] = b"lockedTokenId";
 
 use crate::{events, tokens_per_user::UnstakePair};
+use energy_factory::penalty::{
+    PenaltyCurve, PenaltyCurveType, PENALTY_CURVE_STORAGE_KEY,
+    PENALTY_FEES_COLLECTOR_PERCENTAGE_STORAGE_KEY,
+};
 
 pub mod fees_collector_proxy {
     elrond_wasm::imports!();
@@ -68,9 +72,17 @@ pub trait FeesHandlerModule:
         self.burn_penalty(payment);
     }
 
+    /// Part of the penalty is redirected to the fees collector, based on the percentage
+    /// set in the energy factory for the penalty curve active at this point.
+    /// The rest is split based on the fees burn percentage.
     fn burn_penalty(&self, payment: EsdtTokenPayment) {
+        let energy_factory_addr = self.energy_factory_address().get();
+        let redirect_percentage = self.get_penalty_fees_collector_percentage(&energy_factory_addr);
+        let redirected_amount = &payment.amount * redirect_percentage / MAX_PENALTY_PERCENTAGE;
+        let penalty_amount = &payment.amount - &redirected_amount;
+
         let fees_burn_percentage = self.fees_burn_percentage().get();
-        let burn_amount = &payment.amount * fees_burn_percentage / MAX_PENALTY_PERCENTAGE;
+        let burn_amount = &penalty_amount * fees_burn_percentage / MAX_PENALTY_PERCENTAGE;
         let remaining_amount = &payment.amount - &burn_amount;
 
         self.send()
@@ -107,6 +119,28 @@ pub trait FeesHandlerModule:
         )
     }
 
+    fn get_penalty_fees_collector_percentage(&self, energy_factory_addr: &ManagedAddress) -> u64 {
+        let curve_buffer: ManagedBuffer = self.storage_raw().read_from_address(
+            energy_factory_addr,
+            ManagedBuffer::new_from_bytes(PENALTY_CURVE_STORAGE_KEY),
+        );
+        let curve_type = if curve_buffer.is_empty() {
+            PenaltyCurveType::Linear
+        } else {
+            let curve: PenaltyCurve<Self::Api> = PenaltyCurve::top_decode(curve_buffer)
+                .unwrap_or_else(|_| sc_panic!("Failed decoding result from energy factory"));
+            curve.get_type()
+        };
+
+        let mut key = ManagedBuffer::new_from_bytes(PENALTY_FEES_COLLECTOR_PERCENTAGE_STORAGE_KEY);
+        curve_type
+            .dep_encode(&mut key)
+            .unwrap_or_else(|err| sc_panic!(err.message_str()));
+
+        self.storage_raw()
+            .read_from_address(energy_factory_addr, key)
+    }
+
     #[proxy]
     fn fees_collector_proxy_builder(
         &self,
@@ -4,6 +4,7 @@ use elrond_wasm::types::{EsdtTokenPayment, ManagedVec};
 use elrond_wasm_debug::{
     managed_address, managed_token_id, managed_token_id_wrapped, rust_biguint, DebugApi,
 };
+use energy_factory::penalty::{LocalPenaltyModule, PenaltyCurveType};
 use num_bigint::ToBigInt;
 use num_traits::cast::ToPrimitive;
 use simple_lock::locked_token::LockedTokenAttributes;
@@ -222,3 +223,55 @@ where
         final_penalty_amount,
     }
 }
+
+#[test]
+fn unlock_early_penalty_fees_collector_percentage_test() {
+    let mut setup =
+        TokenUnstakeSetup::new(energy_factory::contract_obj, token_unstake::contract_obj);
+    let owner = setup.owner.clone();
+    let first_user = setup.first_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    // 20% of the penalty goes to the fees collector while the linear curve is active
+    setup
+        .b_mock
+        .execute_tx(
+            &owner,
+            &setup.energy_factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_penalty_fees_collector_percentage(PenaltyCurveType::Linear, 2_000);
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.set_block_epoch(0);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    let penalty_amount = setup.get_penalty_amount(half_balance, LOCK_OPTIONS[0], 0);
+    setup.unlock_early(&first_user, 1, half_balance).assert_ok();
+
+    setup.b_mock.set_block_epoch(UNBOND_EPOCHS);
+    setup.unbond(&first_user).assert_ok();
+
+    let redirected_amount = &penalty_amount * 2_000u64 / 10_000u64;
+    let burn_amount = (&penalty_amount - &redirected_amount) * FEES_BURN_PERCENTAGE / 10_000u64;
+    setup.b_mock.check_nft_balance(
+        &setup.fees_collector_mock,
+        LOCKED_TOKEN_ID,
+        1,
+        &(&penalty_amount - &burn_amount),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(BASE_ASSET_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch: LOCK_OPTIONS[0],
+        }),
+    );
+}